@@ -0,0 +1,515 @@
+//! Bundling of specs split across multiple files into a single document.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+use derive_more::derive::{Display, Error};
+use serde_json::{Map, Value};
+
+use crate::{json_pointer, Spec};
+
+/// Bundling errors.
+#[derive(Debug, Display, Error)]
+pub enum BundleError {
+    /// I/O error while reading a spec file.
+    #[display("Failed to read {}", path.display())]
+    Io {
+        /// Path of the file being read.
+        path: PathBuf,
+
+        /// Underlying I/O error.
+        source: io::Error,
+    },
+
+    /// YAML error while parsing a spec file.
+    #[display("Failed to parse {}", path.display())]
+    Yaml {
+        /// Path of the file being parsed.
+        path: PathBuf,
+
+        /// Underlying YAML error.
+        source: serde_yml::Error,
+    },
+
+    /// Reference points to a location that does not exist in the target file.
+    #[display("Reference target not found: {}", _0)]
+    TargetNotFound(#[error(not(source))] String),
+
+    /// Component type of an external reference could not be determined.
+    #[display("Cannot determine component type of reference: {}", _0)]
+    UnknownComponentType(#[error(not(source))] String),
+
+    /// Bundled document is not a valid spec.
+    #[display("Bundled document is not a valid spec")]
+    Spec(serde_json::Error),
+}
+
+/// A spec bundled from multiple files, as returned by [`bundle()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    /// Bundled spec.
+    pub spec: Spec,
+
+    /// Hoisted components that were renamed because their name was already taken, in traversal
+    /// order.
+    pub renames: Vec<ComponentRename>,
+}
+
+/// Hoisted component given a different name than it was referenced by.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("{kind}/{name} renamed to {kind}/{new_name}")]
+pub struct ComponentRename {
+    /// Component type, e.g. `schemas`.
+    pub kind: String,
+
+    /// Name derived from the reference.
+    pub name: String,
+
+    /// Unique name the component was bundled under.
+    pub new_name: String,
+}
+
+/// Loads the spec at `root` and bundles all file-relative `$ref`s into a single document.
+///
+/// Each externally referenced definition is hoisted into the root's `components` under the
+/// matching component type and its references are rewritten to local `#/components/...` form.
+/// References of the form `other.yaml#/components/{type}/{name}` keep their type and name.
+/// Other external references take their type from where they are used and their name from the
+/// referenced file or pointer. Absolute URL references are left unchanged.
+///
+/// When a hoisted definition's name is already taken, a numeric suffix (e.g. `Pet_2`) is added in
+/// traversal order and the rename is recorded in [`Bundle::renames`].
+pub fn bundle(root: &Path) -> Result<Bundle, BundleError> {
+    let root = canonicalize(root)?;
+
+    let mut bundler = Bundler::new(root.clone());
+    let mut doc = bundler.load(&root)?.clone();
+
+    if let Some(Value::Object(components)) = doc.get("components") {
+        for (kind, defs) in components {
+            if let Value::Object(defs) = defs {
+                bundler
+                    .names
+                    .entry(kind.clone())
+                    .or_default()
+                    .extend(defs.keys().cloned());
+            }
+        }
+    }
+
+    bundler.walk(&mut doc, &root, &mut Vec::new())?;
+
+    for (kind, name, def) in bundler.hoisted {
+        // a non-object root or components field is reported when deserializing below
+        if let Some(defs) = doc
+            .as_object_mut()
+            .and_then(|doc| object_entry(doc, "components"))
+            .and_then(|components| object_entry(components, &kind))
+        {
+            defs.insert(name, def);
+        }
+    }
+
+    let spec = serde_json::from_value(doc).map_err(BundleError::Spec)?;
+
+    Ok(Bundle {
+        spec,
+        renames: bundler.renames,
+    })
+}
+
+/// Returns the object at `key` in `map`, inserting an empty one if absent.
+fn object_entry<'a>(
+    map: &'a mut Map<String, Value>,
+    key: &str,
+) -> Option<&'a mut Map<String, Value>> {
+    map.entry(key)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf, BundleError> {
+    path.canonicalize().map_err(|source| BundleError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+struct Bundler {
+    root: PathBuf,
+
+    /// Parsed documents, keyed by canonical path.
+    docs: BTreeMap<PathBuf, Value>,
+
+    /// Local references assigned to external targets, keyed by file and pointer.
+    refs: BTreeMap<(PathBuf, String), String>,
+
+    /// Component names in use, keyed by component type.
+    names: BTreeMap<String, BTreeSet<String>>,
+
+    /// Hoisted definitions as (component type, name, definition).
+    hoisted: Vec<(String, String, Value)>,
+
+    /// Hoisted definitions whose name was already taken.
+    renames: Vec<ComponentRename>,
+}
+
+impl Bundler {
+    fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            docs: BTreeMap::new(),
+            refs: BTreeMap::new(),
+            names: BTreeMap::new(),
+            hoisted: Vec::new(),
+            renames: Vec::new(),
+        }
+    }
+
+    fn load(&mut self, path: &Path) -> Result<&Value, BundleError> {
+        if !self.docs.contains_key(path) {
+            let file = File::open(path).map_err(|source| BundleError::Io {
+                path: path.to_owned(),
+                source,
+            })?;
+
+            let yaml = serde_yml::from_reader::<_, serde_yml::Value>(file)
+                .and_then(serde_yml::from_value::<Value>)
+                .map_err(|source| BundleError::Yaml {
+                    path: path.to_owned(),
+                    source,
+                })?;
+
+            self.docs.insert(path.to_owned(), yaml);
+        }
+
+        Ok(&self.docs[path])
+    }
+
+    /// Rewrites external references within `value`, which is located in `file` at `keys`.
+    fn walk(
+        &mut self,
+        value: &mut Value,
+        file: &Path,
+        keys: &mut Vec<String>,
+    ) -> Result<(), BundleError> {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(ref_path)) = map.get("$ref") {
+                    if let Some(local) = self.localize(ref_path, file, keys)? {
+                        map.insert("$ref".to_owned(), Value::String(local));
+                    }
+                }
+
+                for (key, child) in map.iter_mut().filter(|(key, _)| *key != "$ref") {
+                    keys.push(key.clone());
+                    self.walk(child, file, keys)?;
+                    keys.pop();
+                }
+            }
+
+            Value::Array(items) => {
+                for (idx, child) in items.iter_mut().enumerate() {
+                    keys.push(idx.to_string());
+                    self.walk(child, file, keys)?;
+                    keys.pop();
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns the local form of `ref_path`, hoisting its target if needed, or `None` if it should
+    /// be left unchanged.
+    fn localize(
+        &mut self,
+        ref_path: &str,
+        file: &Path,
+        keys: &[String],
+    ) -> Result<Option<String>, BundleError> {
+        let (file_part, pointer) = ref_path.split_once('#').unwrap_or((ref_path, ""));
+
+        if file_part.contains("://") || (file_part.is_empty() && file == self.root) {
+            return Ok(None);
+        }
+
+        let target_file = if file_part.is_empty() {
+            file.to_owned()
+        } else {
+            let dir = file.parent().unwrap_or_else(|| Path::new(""));
+            canonicalize(&dir.join(file_part))?
+        };
+
+        if target_file == self.root {
+            return Ok(Some(format!("#{pointer}")));
+        }
+
+        let key = (target_file.clone(), pointer.to_owned());
+        if let Some(local) = self.refs.get(&key) {
+            return Ok(Some(local.clone()));
+        }
+
+        let (kind, name) = match component_pointer(pointer) {
+            Some((kind, name)) => (kind, name),
+            None => {
+                let kind = infer_component_type(keys)
+                    .ok_or_else(|| BundleError::UnknownComponentType(ref_path.to_owned()))?;
+                (kind.to_owned(), default_name(&target_file, pointer))
+            }
+        };
+
+        let name = self.reserve_name(&kind, name);
+        let local = format!("#/components/{kind}/{}", json_pointer::escape(&name));
+        self.refs.insert(key, local.clone());
+
+        let mut def = self
+            .load(&target_file)?
+            .pointer(pointer)
+            .cloned()
+            .ok_or_else(|| BundleError::TargetNotFound(ref_path.to_owned()))?;
+
+        let mut def_keys = vec!["components".to_owned(), kind.clone(), name.clone()];
+        self.walk(&mut def, &target_file, &mut def_keys)?;
+        self.hoisted.push((kind, name, def));
+
+        Ok(Some(local))
+    }
+
+    /// Reserves a unique component name of the given type, suffixing `name` if it is taken.
+    fn reserve_name(&mut self, kind: &str, name: String) -> String {
+        let names = self.names.entry(kind.to_owned()).or_default();
+
+        let mut unique = name.clone();
+        let mut suffix = 2;
+
+        while names.contains(&unique) {
+            unique = format!("{name}_{suffix}");
+            suffix += 1;
+        }
+
+        if unique != name {
+            self.renames.push(ComponentRename {
+                kind: kind.to_owned(),
+                name,
+                new_name: unique.clone(),
+            });
+        }
+
+        names.insert(unique.clone());
+        unique
+    }
+}
+
+/// Splits a pointer of the form `/components/{type}/{name}` into its type and name.
+fn component_pointer(pointer: &str) -> Option<(String, String)> {
+    let (kind, name) = pointer.strip_prefix("/components/")?.split_once('/')?;
+
+    if name.contains('/') {
+        return None;
+    }
+
+    Some((kind.to_owned(), json_pointer::unescape(name)))
+}
+
+/// Infers the component type of a reference located at `keys`.
+fn infer_component_type(keys: &[String]) -> Option<&'static str> {
+    let last = keys.last().map(String::as_str);
+    let parent = keys.len().checked_sub(2).map(|idx| keys[idx].as_str());
+
+    Some(match (parent, last) {
+        (_, Some("schema" | "items" | "additionalProperties" | "not"))
+        | (Some("properties" | "allOf" | "anyOf" | "oneOf" | "schemas"), _) => "schemas",
+        (_, Some("requestBody")) | (Some("requestBodies"), _) => "requestBodies",
+        (Some("parameters"), _) => "parameters",
+        (Some("responses"), _) => "responses",
+        (Some("headers"), _) => "headers",
+        (Some("examples"), _) => "examples",
+        (Some("links"), _) => "links",
+        (Some("callbacks"), _) => "callbacks",
+        (Some("securitySchemes"), _) => "securitySchemes",
+        (Some("paths" | "webhooks" | "pathItems"), _) => "pathItems",
+        _ => return None,
+    })
+}
+
+/// Derives a component name from the last pointer segment, or the file stem if there is none.
+fn default_name(file: &Path, pointer: &str) -> String {
+    match pointer.rsplit('/').next() {
+        Some(segment) if !segment.is_empty() => json_pointer::unescape(segment),
+        _ => file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::spec::{ObjectOrReference, Schema};
+
+    fn write_files(dir: &Path, files: &[(&str, &str)]) {
+        for (name, contents) in files {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn bundle_external_refs() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_files(
+            dir.path(),
+            &[
+                (
+                    "root.yaml",
+                    indoc::indoc! {"
+                        openapi: 3.1.0
+                        info:
+                          title: Pets
+                          version: 1.0.0
+                        paths:
+                          /pets:
+                            get:
+                              parameters:
+                                - $ref: 'common.yaml#/components/parameters/Limit'
+                              responses:
+                                '200':
+                                  description: OK
+                                  content:
+                                    application/json:
+                                      schema:
+                                        $ref: './schemas/pet.yaml'
+                                default:
+                                  description: Error
+                                  content:
+                                    application/json:
+                                      schema:
+                                        $ref: 'common.yaml#/components/schemas/Error'
+                        components:
+                          schemas:
+                            Error:
+                              type: string
+                    "},
+                ),
+                (
+                    "common.yaml",
+                    indoc::indoc! {"
+                        components:
+                          parameters:
+                            Limit:
+                              name: limit
+                              in: query
+                              schema:
+                                type: integer
+                          schemas:
+                            Error:
+                              type: object
+                              properties:
+                                code:
+                                  $ref: '#/components/schemas/Code'
+                            Code:
+                              type: integer
+                    "},
+                ),
+                (
+                    "schemas/pet.yaml",
+                    indoc::indoc! {"
+                        type: object
+                        properties:
+                          owner:
+                            $ref: '../common.yaml#/components/schemas/Error'
+                    "},
+                ),
+            ],
+        );
+
+        let Bundle { spec, renames } = bundle(&dir.path().join("root.yaml")).unwrap();
+        let components = spec.components.as_ref().unwrap();
+
+        assert_eq!(
+            renames,
+            [ComponentRename {
+                kind: "schemas".to_owned(),
+                name: "Error".to_owned(),
+                new_name: "Error_2".to_owned(),
+            }],
+        );
+        assert_eq!(
+            renames[0].to_string(),
+            "schemas/Error renamed to schemas/Error_2",
+        );
+
+        assert_eq!(
+            components.schemas.keys().collect::<Vec<_>>(),
+            ["Code", "Error", "Error_2", "pet"],
+        );
+        assert!(components.parameters.contains_key("Limit"));
+
+        let pet = components.schemas["pet"].resolve(&spec).unwrap();
+        assert_eq!(
+            pet.properties["owner"],
+            Schema::from(ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Error_2".to_owned(),
+            }),
+        );
+
+        let error = components.schemas["Error_2"].resolve(&spec).unwrap();
+        assert_eq!(
+            error.properties["code"],
+            Schema::from(ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Code".to_owned(),
+            }),
+        );
+
+        let op = spec.operation(&http::Method::GET, "/pets").unwrap();
+        assert_eq!(op.parameters(&spec).unwrap()[0].name, "limit");
+
+        let res = &op.responses(&spec)["default"];
+        assert_eq!(
+            res.content["application/json"].schema,
+            Some(ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Error_2".to_owned(),
+            }),
+        );
+    }
+
+    #[test]
+    fn bundle_missing_target() {
+        let dir = tempfile::tempdir().unwrap();
+
+        write_files(
+            dir.path(),
+            &[
+                (
+                    "root.yaml",
+                    indoc::indoc! {"
+                        openapi: 3.1.0
+                        info:
+                          title: Pets
+                          version: 1.0.0
+                        components:
+                          schemas:
+                            Pet:
+                              $ref: 'other.yaml#/components/schemas/Missing'
+                    "},
+                ),
+                ("other.yaml", "components: {}\n"),
+            ],
+        );
+
+        let err = bundle(&dir.path().join("root.yaml")).unwrap_err();
+        assert!(matches!(err, BundleError::TargetNotFound(_)));
+    }
+}
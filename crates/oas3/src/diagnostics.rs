@@ -0,0 +1,314 @@
+//! Error-tolerant parsing that reports every problem found in a spec.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+use crate::{json_pointer, strict, Spec};
+
+/// A problem found while parsing a spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// JSON pointer to the offending value, if the document could be parsed as YAML.
+    pub pointer: Option<String>,
+
+    /// Line (1-based) of the problem in the source, if known.
+    pub line: Option<usize>,
+
+    /// Column (1-based) of the problem in the source, if known.
+    pub column: Option<usize>,
+
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.pointer, self.line, self.column) {
+            (Some(ptr), _, _) => write!(f, "{ptr}: {}", self.message),
+            (None, Some(line), Some(col)) => write!(f, "{line}:{col}: {}", self.message),
+            _ => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Parses a YAML (or JSON) spec, collecting a diagnostic for each invalid value.
+///
+/// Invalid values are removed from the document and parsing is retried, so that the rest of the
+/// spec can still be salvaged.
+pub(crate) fn from_yaml_diagnostics(val: &str) -> (Option<Spec>, Vec<Diagnostic>) {
+    let mut doc = match serde_yml::from_str::<serde_yml::Value>(val).and_then(strict::yaml_to_json)
+    {
+        Ok(doc) => doc,
+        Err(err) => {
+            let location = err.location();
+
+            let diagnostic = Diagnostic {
+                pointer: None,
+                line: location.as_ref().map(|loc| loc.line()),
+                column: location.as_ref().map(|loc| loc.column()),
+                message: err.to_string(),
+            };
+
+            return (None, vec![diagnostic]);
+        }
+    };
+
+    let mut pruner = Pruner::default();
+    let mut diagnostics = Vec::<Diagnostic>::new();
+
+    loop {
+        let err = match serde_path_to_error::deserialize::<_, Spec>(&doc) {
+            Ok(spec) => return (Some(spec), diagnostics),
+            Err(err) => err,
+        };
+
+        let tokens = existing_tokens(&doc, err.path().iter());
+        let ptr = pruner.original_pointer(&tokens);
+
+        // failures of ancestors are caused by removing their invalid descendants
+        let is_ancestor = diagnostics.iter().any(|diagnostic| {
+            diagnostic
+                .pointer
+                .as_deref()
+                .is_some_and(|reported| reported.starts_with(&format!("{ptr}/")))
+        });
+
+        if !is_ancestor {
+            diagnostics.push(Diagnostic {
+                pointer: Some(ptr.clone()),
+                line: None,
+                column: None,
+                message: err.into_inner().to_string(),
+            });
+        }
+
+        if !pruner.remove(&mut doc, &tokens, &ptr) {
+            return (None, diagnostics);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Key(String),
+    Index(usize),
+}
+
+/// Converts a deserialization error path to tokens, truncated to the longest prefix that exists
+/// in `doc`.
+fn existing_tokens<'a>(mut doc: &Value, path: impl Iterator<Item = &'a Segment>) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    for segment in path {
+        let (token, next) = match segment {
+            Segment::Map { key } => (Token::Key(key.clone()), doc.get(key)),
+            Segment::Seq { index } => (Token::Index(*index), doc.get(index)),
+            Segment::Enum { .. } | Segment::Unknown => break,
+        };
+
+        let Some(next) = next else { break };
+
+        tokens.push(token);
+        doc = next;
+    }
+
+    tokens
+}
+
+/// Removes invalid values from a document, keeping track of removed array items so that pointers
+/// into the pruned document can be mapped back to the original one.
+#[derive(Debug, Default)]
+struct Pruner {
+    /// Sorted original indices of removed items, keyed by original pointer of their array.
+    removed: BTreeMap<String, Vec<usize>>,
+}
+
+impl Pruner {
+    /// Returns the pointer into the original document for tokens of the pruned document.
+    fn original_pointer(&self, tokens: &[Token]) -> String {
+        let mut ptr = "#".to_owned();
+
+        for token in tokens {
+            match token {
+                Token::Key(key) => {
+                    ptr.push('/');
+                    ptr.push_str(&json_pointer::escape(key));
+                }
+
+                Token::Index(idx) => {
+                    let idx = self.original_index(&ptr, *idx);
+                    ptr.push_str(&format!("/{idx}"));
+                }
+            }
+        }
+
+        ptr
+    }
+
+    fn original_index(&self, array_ptr: &str, mut idx: usize) -> usize {
+        for &removed in self.removed.get(array_ptr).into_iter().flatten() {
+            if removed <= idx {
+                idx += 1;
+            }
+        }
+
+        idx
+    }
+
+    /// Removes the value at `tokens`, returning false if it is the document root.
+    fn remove(&mut self, doc: &mut Value, tokens: &[Token], ptr: &str) -> bool {
+        let Some((last, parent_tokens)) = tokens.split_last() else {
+            return false;
+        };
+
+        let mut parent = doc;
+
+        for token in parent_tokens {
+            parent = match token {
+                Token::Key(key) => &mut parent[key.as_str()],
+                Token::Index(idx) => &mut parent[*idx],
+            };
+        }
+
+        match (last, parent) {
+            (Token::Key(key), Value::Object(obj)) => {
+                obj.remove(key);
+            }
+
+            (Token::Index(idx), Value::Array(arr)) => {
+                arr.remove(*idx);
+
+                let (array_ptr, original_idx) = ptr.rsplit_once('/').expect("pointer has a parent");
+                let removed = self.removed.entry(array_ptr.to_owned()).or_default();
+                removed.push(
+                    original_idx
+                        .parse()
+                        .expect("array pointer ends with an index"),
+                );
+                removed.sort_unstable();
+            }
+
+            _ => unreachable!("tokens point to an existing value"),
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_all_errors() {
+        let (spec, diagnostics) = from_yaml_diagnostics(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  parameters:
+                    - name: a
+                      in: nowhere
+                    - name: b
+                      in: nowhere
+                    - name: c
+                      in: query
+                  responses:
+                    '200':
+                      description: OK
+            components:
+              schemas:
+                Pet:
+                  type: [1]
+        "});
+
+        let spec = spec.unwrap();
+
+        let params = &spec.paths.as_ref().unwrap()["/pets"]
+            .get
+            .as_ref()
+            .unwrap()
+            .parameters;
+        assert_eq!(params.len(), 1);
+        assert!(spec.components.unwrap().schemas.is_empty());
+
+        let mut ptrs = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.pointer.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        ptrs.sort_unstable();
+        assert_eq!(
+            ptrs,
+            [
+                "#/components/schemas/Pet",
+                "#/paths/~1pets/get/parameters/0",
+                "#/paths/~1pets/get/parameters/1",
+            ],
+        );
+    }
+
+    #[test]
+    fn unquoted_status_codes() {
+        let yaml = indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  responses:
+                    200:
+                      description: OK
+                    404:
+                      description: [1]
+        "};
+
+        let (spec, diagnostics) = from_yaml_diagnostics(yaml);
+        assert!(spec.is_some());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].pointer.as_deref(),
+            Some("#/paths/~1pets/get/responses/404"),
+        );
+
+        let yaml = yaml.replace("[1]", "Not found");
+        let (spec, diagnostics) = from_yaml_diagnostics(&yaml);
+        assert!(diagnostics.is_empty());
+        assert_eq!(spec.unwrap(), crate::from_str(yaml).unwrap());
+    }
+
+    #[test]
+    fn unsalvageable_spec() {
+        let (spec, diagnostics) = from_yaml_diagnostics(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+        "});
+
+        assert!(spec.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pointer.as_deref(), Some("#/info"));
+        assert!(diagnostics[0].message.contains("version"));
+    }
+
+    #[test]
+    fn syntax_error() {
+        let (spec, diagnostics) = from_yaml_diagnostics("openapi: [3.1.0\ninfo: {}");
+
+        assert!(spec.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].pointer.is_none());
+        assert!(diagnostics[0].line.is_some());
+        assert!(diagnostics[0].to_string().starts_with(&format!(
+            "{}:{}: ",
+            diagnostics[0].line.unwrap(),
+            diagnostics[0].column.unwrap(),
+        )));
+    }
+}
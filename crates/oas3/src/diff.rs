@@ -0,0 +1,863 @@
+//! Comparison of specs for backwards-incompatible changes.
+
+use std::collections::{BTreeMap, HashSet};
+
+use derive_more::derive::Display;
+use http::Method;
+
+use crate::{
+    json_pointer,
+    spec::{
+        MediaType, ObjectOrReference, ObjectSchema, Operation, Parameter, PathItem, RequestBody,
+        Schema, SchemaType, SchemaTypeSet, SecurityRequirement,
+    },
+    Spec,
+};
+
+/// Changes found between two versions of a spec.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpecDiff {
+    /// All changes found, both breaking and non-breaking.
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    /// Returns `true` if any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.breaking)
+    }
+
+    /// Returns iterator over breaking changes.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+}
+
+/// A single change between two versions of a spec.
+#[derive(Debug, Clone, PartialEq, Display)]
+#[display("{pointer}: {kind}")]
+pub struct Change {
+    /// JSON pointer to the changed value.
+    ///
+    /// Points into the old spec for removals and the new spec otherwise. Pointers into schemas
+    /// follow the structure of the operation, through any references.
+    pub pointer: String,
+
+    /// Type of change.
+    pub kind: ChangeKind,
+
+    /// Whether the change may break existing clients.
+    pub breaking: bool,
+}
+
+/// Type of a [`Change`].
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum ChangeKind {
+    /// Path was removed.
+    #[display("path removed")]
+    PathRemoved,
+
+    /// Path was added.
+    #[display("path added")]
+    PathAdded,
+
+    /// Operation was removed.
+    #[display("operation removed")]
+    OperationRemoved,
+
+    /// Operation was added.
+    #[display("operation added")]
+    OperationAdded,
+
+    /// Required parameter was added, or an existing parameter became required.
+    #[display("required parameter added")]
+    RequiredParameterAdded,
+
+    /// Required request body was added, or an existing request body became required.
+    #[display("required request body added")]
+    RequiredRequestBodyAdded,
+
+    /// Required request body property was added, or an existing property became required.
+    #[display("required request field added")]
+    RequiredFieldAdded,
+
+    /// Response status was removed.
+    #[display("response removed")]
+    ResponseRemoved,
+
+    /// Request or response media type was removed.
+    #[display("media type removed")]
+    MediaTypeRemoved,
+
+    /// Response body property was removed.
+    #[display("response field removed")]
+    ResponseFieldRemoved,
+
+    /// Schema accepts fewer types than before.
+    ///
+    /// Breaking for request schemas only. A missing type set accepts any type.
+    #[display("type narrowed")]
+    TypeNarrowed {
+        /// Old type set.
+        old: Option<SchemaTypeSet>,
+
+        /// New type set.
+        new: Option<SchemaTypeSet>,
+    },
+
+    /// Effective security requirements changed.
+    ///
+    /// Breaking if any previously accepted requirement is no longer accepted.
+    #[display("security requirements changed")]
+    SecurityChanged,
+}
+
+/// Compares two versions of a spec, reporting changes to paths, operations, parameters, request
+/// and response schemas, and security requirements.
+///
+/// # Examples
+///
+/// ```
+/// let old = oas3::from_str("
+/// openapi: 3.1.0
+/// info: { title: Pets, version: 1.0.0 }
+/// paths:
+///   /pets:
+///     get: {}
+///     delete: {}
+/// ").unwrap();
+///
+/// let new = oas3::from_str("
+/// openapi: 3.1.0
+/// info: { title: Pets, version: 2.0.0 }
+/// paths:
+///   /pets:
+///     get: {}
+/// ").unwrap();
+///
+/// let diff = oas3::diff(&old, &new);
+/// assert!(diff.is_breaking());
+/// assert_eq!(diff.changes[0].to_string(), "#/paths/~1pets/delete: operation removed");
+/// ```
+pub fn diff(old: &Spec, new: &Spec) -> SpecDiff {
+    let mut differ = Differ {
+        old,
+        new,
+        changes: Vec::new(),
+        compared_refs: HashSet::new(),
+    };
+
+    differ.paths();
+
+    SpecDiff {
+        changes: differ.changes,
+    }
+}
+
+/// Whether a schema describes data sent by clients or received by them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Request,
+    Response,
+}
+
+struct Differ<'a> {
+    old: &'a Spec,
+    new: &'a Spec,
+    changes: Vec<Change>,
+
+    /// Old and new `$ref`s of schemas already compared, guarding against self-referential schemas.
+    compared_refs: HashSet<(Option<String>, Option<String>, Direction)>,
+}
+
+impl Differ<'_> {
+    fn push(&mut self, pointer: String, kind: ChangeKind, breaking: bool) {
+        self.changes.push(Change {
+            pointer,
+            kind,
+            breaking,
+        });
+    }
+
+    fn paths(&mut self) {
+        let old_paths = self.old.paths.iter().flatten().collect::<BTreeMap<_, _>>();
+        let new_paths = self.new.paths.iter().flatten().collect::<BTreeMap<_, _>>();
+
+        for (path, old_item) in &old_paths {
+            let ptr = json_pointer::append("#/paths", path);
+
+            match new_paths.get(path) {
+                Some(new_item) => self.path_item(&ptr, old_item, new_item),
+                None => self.push(ptr, ChangeKind::PathRemoved, true),
+            }
+        }
+
+        for path in new_paths.keys() {
+            if !old_paths.contains_key(path) {
+                self.push(
+                    json_pointer::append("#/paths", path),
+                    ChangeKind::PathAdded,
+                    false,
+                );
+            }
+        }
+    }
+
+    fn path_item(&mut self, ptr: &str, old_item: &PathItem, new_item: &PathItem) {
+        let old_item = old_item
+            .resolve(self.old)
+            .unwrap_or_else(|_| old_item.clone());
+        let new_item = new_item
+            .resolve(self.new)
+            .unwrap_or_else(|_| new_item.clone());
+
+        let old_ops = operations(&old_item);
+        let new_ops = operations(&new_item);
+
+        for (method, old_op) in &old_ops {
+            let op_ptr = json_pointer::append(ptr, method);
+
+            match new_ops.get(method) {
+                Some(new_op) => {
+                    self.operation(&op_ptr, (&old_item, old_op), (&new_item, new_op));
+                }
+                None => self.push(op_ptr, ChangeKind::OperationRemoved, true),
+            }
+        }
+
+        for method in new_ops.keys() {
+            if !old_ops.contains_key(method) {
+                self.push(
+                    json_pointer::append(ptr, method),
+                    ChangeKind::OperationAdded,
+                    false,
+                );
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        ptr: &str,
+        (old_item, old_op): (&PathItem, &Operation),
+        (new_item, new_op): (&PathItem, &Operation),
+    ) {
+        self.parameters(ptr, (old_item, old_op), (new_item, new_op));
+
+        let old_body = old_op
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(self.old).ok());
+        let new_body = new_op
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(self.new).ok());
+
+        let body_ptr = json_pointer::append(ptr, "requestBody");
+
+        let was_required = old_body.as_ref().is_some_and(RequestBody::is_required);
+        let is_required = new_body.as_ref().is_some_and(RequestBody::is_required);
+
+        if is_required && !was_required {
+            self.push(body_ptr.clone(), ChangeKind::RequiredRequestBodyAdded, true);
+        }
+
+        if let (Some(old_body), Some(new_body)) = (old_body, new_body) {
+            self.content(
+                &body_ptr,
+                &old_body.content,
+                &new_body.content,
+                Direction::Request,
+            );
+        }
+
+        let old_responses = old_op.responses(self.old);
+        let new_responses = new_op.responses(self.new);
+
+        for (status, old_response) in &old_responses {
+            let response_ptr =
+                json_pointer::append(&json_pointer::append(ptr, "responses"), status);
+
+            match new_responses.get(status) {
+                Some(new_response) => self.content(
+                    &response_ptr,
+                    &old_response.content,
+                    &new_response.content,
+                    Direction::Response,
+                ),
+                None => self.push(response_ptr, ChangeKind::ResponseRemoved, true),
+            }
+        }
+
+        let old_security = old_op.security.as_ref().unwrap_or(&self.old.security);
+        let new_security = new_op.security.as_ref().unwrap_or(&self.new.security);
+
+        if old_security != new_security {
+            let breaking = security_is_breaking(old_security, new_security);
+            self.push(
+                json_pointer::append(ptr, "security"),
+                ChangeKind::SecurityChanged,
+                breaking,
+            );
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        ptr: &str,
+        (old_item, old_op): (&PathItem, &Operation),
+        (new_item, new_op): (&PathItem, &Operation),
+    ) {
+        let old_params = parameters(self.old, ptr, old_item, old_op);
+        let new_params = parameters(self.new, ptr, new_item, new_op);
+
+        for (key, (param_ptr, new_param)) in &new_params {
+            let old_param = old_params.get(key).map(|(_, param)| param);
+
+            let was_required = old_param.is_some_and(|param| param.required == Some(true));
+
+            if new_param.required == Some(true) && !was_required {
+                self.push(param_ptr.clone(), ChangeKind::RequiredParameterAdded, true);
+            }
+
+            let old_schema = old_param.and_then(|param| param.schema.as_ref());
+
+            if let (Some(old_schema), Some(new_schema)) = (old_schema, &new_param.schema) {
+                self.schema_or_ref(
+                    &json_pointer::append(param_ptr, "schema"),
+                    old_schema,
+                    new_schema,
+                    Direction::Request,
+                );
+            }
+        }
+    }
+
+    fn content(
+        &mut self,
+        ptr: &str,
+        old_content: &BTreeMap<String, MediaType>,
+        new_content: &BTreeMap<String, MediaType>,
+        direction: Direction,
+    ) {
+        for (media_type, old_media_type) in old_content {
+            let media_type_ptr =
+                json_pointer::append(&json_pointer::append(ptr, "content"), media_type);
+
+            let Some(new_media_type) = new_content.get(media_type) else {
+                self.push(media_type_ptr, ChangeKind::MediaTypeRemoved, true);
+                continue;
+            };
+
+            // media types need not define a schema
+            let (Some(old_schema), Some(new_schema)) =
+                (&old_media_type.schema, &new_media_type.schema)
+            else {
+                continue;
+            };
+
+            let schema_ptr = json_pointer::append(&media_type_ptr, "schema");
+            self.schema_or_ref(&schema_ptr, old_schema, new_schema, direction);
+        }
+    }
+
+    /// Resolves and compares two schemas, unless the same pair of references was already compared.
+    fn schema_or_ref(
+        &mut self,
+        ptr: &str,
+        old: &ObjectOrReference<ObjectSchema>,
+        new: &ObjectOrReference<ObjectSchema>,
+        direction: Direction,
+    ) {
+        let key = (ref_path(old), ref_path(new), direction);
+
+        if (key.0.is_some() || key.1.is_some()) && !self.compared_refs.insert(key) {
+            return;
+        }
+
+        if let (Ok(old), Ok(new)) = (old.resolve(self.old), new.resolve(self.new)) {
+            self.schema(ptr, &old, &new, direction);
+        }
+    }
+
+    fn schema(&mut self, ptr: &str, old: &ObjectSchema, new: &ObjectSchema, direction: Direction) {
+        if is_narrowed(old.schema_type.as_ref(), new.schema_type.as_ref()) {
+            self.push(
+                ptr.to_owned(),
+                ChangeKind::TypeNarrowed {
+                    old: old.schema_type.clone(),
+                    new: new.schema_type.clone(),
+                },
+                direction == Direction::Request,
+            );
+        }
+
+        let props_ptr = json_pointer::append(ptr, "properties");
+
+        match direction {
+            Direction::Request => {
+                for name in &new.required {
+                    if !old.required.contains(name) {
+                        self.push(
+                            json_pointer::append(&props_ptr, name),
+                            ChangeKind::RequiredFieldAdded,
+                            true,
+                        );
+                    }
+                }
+            }
+
+            Direction::Response => {
+                for name in old.properties.keys() {
+                    if !new.properties.contains_key(name) {
+                        self.push(
+                            json_pointer::append(&props_ptr, name),
+                            ChangeKind::ResponseFieldRemoved,
+                            true,
+                        );
+                    }
+                }
+            }
+        }
+
+        for (name, old_prop) in &old.properties {
+            let Some(new_prop) = new.properties.get(name) else {
+                continue;
+            };
+
+            if let (Some(old_prop), Some(new_prop)) = (old_prop.as_object(), new_prop.as_object()) {
+                self.schema_or_ref(
+                    &json_pointer::append(&props_ptr, name),
+                    old_prop,
+                    new_prop,
+                    direction,
+                );
+            }
+        }
+
+        if let (Some(Schema::Object(old_items)), Some(Schema::Object(new_items))) =
+            (&old.items, &new.items)
+        {
+            self.schema_or_ref(
+                &json_pointer::append(ptr, "items"),
+                old_items,
+                new_items,
+                direction,
+            );
+        }
+    }
+}
+
+/// Returns path item's operations keyed by lowercase method name.
+fn operations(item: &PathItem) -> BTreeMap<String, &Operation> {
+    item.methods()
+        .into_iter()
+        .map(|(method, op): (Method, _)| (method.as_str().to_lowercase(), op))
+        .collect()
+}
+
+/// Returns resolved path and operation parameters, keyed by name and location, along with a
+/// pointer to each.
+///
+/// Operation parameters override path item parameters with the same name and location.
+fn parameters(
+    spec: &Spec,
+    op_ptr: &str,
+    item: &PathItem,
+    op: &Operation,
+) -> BTreeMap<(String, String), (String, Parameter)> {
+    let path_ptr = op_ptr.rsplit_once('/').map_or("", |(path_ptr, _)| path_ptr);
+
+    let item_params = item.parameters.iter().enumerate().map(|(idx, param)| {
+        (
+            json_pointer::append(
+                &json_pointer::append(path_ptr, "parameters"),
+                &idx.to_string(),
+            ),
+            param,
+        )
+    });
+
+    let op_params = op.parameters.iter().enumerate().map(|(idx, param)| {
+        (
+            json_pointer::append(
+                &json_pointer::append(op_ptr, "parameters"),
+                &idx.to_string(),
+            ),
+            param,
+        )
+    });
+
+    item_params
+        .chain(op_params)
+        .filter_map(|(ptr, param): (String, &ObjectOrReference<Parameter>)| {
+            let param = param.resolve(spec).ok()?;
+            let location = serde_json::to_value(param.location).ok()?;
+            let key = (param.name.clone(), location.as_str()?.to_owned());
+            Some((key, (ptr, param)))
+        })
+        .collect()
+}
+
+/// Returns the reference path of `schema`, if it is a reference.
+fn ref_path(schema: &ObjectOrReference<ObjectSchema>) -> Option<String> {
+    match schema {
+        ObjectOrReference::Ref { ref_path } => Some(ref_path.clone()),
+        ObjectOrReference::Object(_) => None,
+    }
+}
+
+/// Returns `true` if `new` does not accept every type accepted by `old`.
+fn is_narrowed(old: Option<&SchemaTypeSet>, new: Option<&SchemaTypeSet>) -> bool {
+    let Some(new) = new else {
+        return false;
+    };
+
+    let Some(old) = old else {
+        return true;
+    };
+
+    let old_types = match old {
+        SchemaTypeSet::Single(type_) => vec![*type_],
+        SchemaTypeSet::Multiple(types) => types.clone(),
+    };
+
+    old_types.into_iter().any(|type_| {
+        // integers are also numbers
+        !(new.contains(type_) || (type_ == SchemaType::Integer && new.contains(SchemaType::Number)))
+    })
+}
+
+/// Returns `true` if a previously accepted security requirement is no longer accepted.
+fn security_is_breaking(old: &[SecurityRequirement], new: &[SecurityRequirement]) -> bool {
+    // no requirements means no authentication is needed
+    !new.is_empty() && (old.is_empty() || old.iter().any(|req| !new.contains(req)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::slice;
+
+    use super::*;
+
+    #[test]
+    fn diff_specs() {
+        let old = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            security:
+              - apiKey: []
+            paths:
+              /pets:
+                get:
+                  parameters:
+                    - name: limit
+                      in: query
+                      schema:
+                        type: [integer, string]
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            $ref: '#/components/schemas/Pet'
+                post:
+                  requestBody:
+                    content:
+                      application/json:
+                        schema:
+                          $ref: '#/components/schemas/Pet'
+                  responses:
+                    '201':
+                      description: Created
+              /stores:
+                get: {}
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  required: [name]
+                  properties:
+                    name:
+                      type: string
+                    tag:
+                      type: string
+        "})
+        .unwrap();
+
+        let new = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v2
+            security:
+              - apiKey: []
+              - oauth: []
+            paths:
+              /owners:
+                get: {}
+              /pets:
+                get:
+                  parameters:
+                    - name: limit
+                      in: query
+                      required: true
+                      schema:
+                        type: integer
+                    - name: offset
+                      in: query
+                      schema:
+                        type: integer
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            $ref: '#/components/schemas/Pet'
+                post:
+                  requestBody:
+                    content:
+                      application/json:
+                        schema:
+                          $ref: '#/components/schemas/Pet'
+                  responses:
+                    '201':
+                      description: Created
+                delete: {}
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  required: [name, age]
+                  properties:
+                    age:
+                      type: integer
+                    name:
+                      type: string
+        "})
+        .unwrap();
+
+        let diff = diff(&old, &new);
+
+        let changes = diff
+            .changes
+            .iter()
+            .map(|change| (change.to_string(), change.breaking))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            [
+                (
+                    "#/paths/~1pets/get/parameters/0: required parameter added".to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/get/parameters/0/schema: type narrowed".to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/get/responses/200/content/application~1json/schema/properties/tag: response field removed".to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/get/security: security requirements changed".to_owned(),
+                    false,
+                ),
+                (
+                    "#/paths/~1pets/post/requestBody/content/application~1json/schema/properties/age: required request field added".to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/post/security: security requirements changed".to_owned(),
+                    false,
+                ),
+                ("#/paths/~1pets/delete: operation added".to_owned(), false),
+                ("#/paths/~1stores: path removed".to_owned(), true),
+                ("#/paths/~1owners: path added".to_owned(), false),
+            ],
+        );
+
+        assert!(diff.is_breaking());
+        assert_eq!(diff.breaking_changes().count(), 5);
+
+        assert!(!super::diff(&old, &old).is_breaking());
+        assert!(super::diff(&old, &old).changes.is_empty());
+    }
+
+    #[test]
+    fn request_body_and_response_changes() {
+        let old = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                post:
+                  requestBody:
+                    required: false
+                    content:
+                      application/json: {}
+                      application/xml: {}
+                  responses:
+                    '201':
+                      description: Created
+                      content:
+                        application/json: {}
+                        text/plain: {}
+                    '400':
+                      description: Bad Request
+                put:
+                  responses:
+                    '204':
+                      description: No Content
+        "})
+        .unwrap();
+
+        let new = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v2
+            paths:
+              /pets:
+                post:
+                  requestBody:
+                    required: true
+                    content:
+                      application/json: {}
+                  responses:
+                    '201':
+                      description: Created
+                      content:
+                        application/json: {}
+                put:
+                  requestBody:
+                    required: true
+                    content:
+                      application/json: {}
+                  responses:
+                    '204':
+                      description: No Content
+        "})
+        .unwrap();
+
+        let changes = diff(&old, &new)
+            .changes
+            .iter()
+            .map(|change| (change.to_string(), change.breaking))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            [
+                (
+                    "#/paths/~1pets/post/requestBody: required request body added".to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/post/requestBody/content/application~1xml: media type removed"
+                        .to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/post/responses/201/content/text~1plain: media type removed"
+                        .to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/post/responses/400: response removed".to_owned(),
+                    true,
+                ),
+                (
+                    "#/paths/~1pets/put/requestBody: required request body added".to_owned(),
+                    true,
+                ),
+            ],
+        );
+
+        // relaxing requirements and adding responses or media types is not reported
+        assert!(diff(&new, &old)
+            .changes
+            .iter()
+            .all(|change| change.kind != ChangeKind::RequiredRequestBodyAdded));
+    }
+
+    #[test]
+    fn recursive_schemas() {
+        let spec = |required: &str| {
+            serde_yml::from_str::<Spec>(&format!(
+                indoc::indoc! {"
+                    openapi: 3.1.0
+                    info:
+                      title: test
+                      version: v1
+                    paths:
+                      /nodes:
+                        post:
+                          requestBody:
+                            content:
+                              application/json:
+                                schema:
+                                  $ref: '#/components/schemas/Node'
+                    components:
+                      schemas:
+                        Node:
+                          type: object
+                          required: [{}]
+                          properties:
+                            p0:
+                              $ref: '#/components/schemas/Node'
+                            p1:
+                              $ref: '#/components/schemas/Node'
+                            p2:
+                              type: array
+                              items:
+                                $ref: '#/components/schemas/Node'
+                "},
+                required,
+            ))
+            .unwrap()
+        };
+
+        let old = spec("p0");
+        let new = spec("p0, p1");
+
+        let changes = diff(&old, &new)
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        // each pair of references is compared once
+        assert_eq!(
+            changes,
+            [
+                "#/paths/~1nodes/post/requestBody/content/application~1json/schema/properties/p1: \
+                 required request field added",
+            ],
+        );
+
+        assert!(diff(&new, &new).changes.is_empty());
+    }
+
+    #[test]
+    fn security_changes() {
+        let api_key = SecurityRequirement([("apiKey".to_owned(), vec![])].into());
+        let oauth = SecurityRequirement([("oauth".to_owned(), vec![])].into());
+        let both = [api_key.clone(), oauth.clone()];
+
+        let api_key = slice::from_ref(&api_key);
+        let oauth = slice::from_ref(&oauth);
+
+        assert!(!security_is_breaking(api_key, &[]));
+        assert!(security_is_breaking(&[], api_key));
+        assert!(security_is_breaking(api_key, oauth));
+        assert!(!security_is_breaking(api_key, &both));
+    }
+}
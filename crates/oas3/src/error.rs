@@ -21,6 +21,23 @@ pub enum Error {
     #[display("JSON error")]
     Serialize(serde_json::Error),
 
+    /// TOML deserialization error.
+    #[cfg(feature = "toml-spec")]
+    #[display("TOML error")]
+    TomlDe(toml::de::Error),
+
+    /// TOML serialization error.
+    #[cfg(feature = "toml-spec")]
+    #[display("TOML error")]
+    TomlSer(toml::ser::Error),
+
+    /// Spec contains fields that are not part of the OpenAPI model.
+    ///
+    /// Holds a JSON pointer to each unknown field.
+    #[display("Unknown fields: {}", _0.join(", "))]
+    #[from(ignore)]
+    UnknownFields(#[error(not(source))] Vec<String>),
+
     /// Spec error.
     #[display("Spec error")]
     Spec(SpecError),
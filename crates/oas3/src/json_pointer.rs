@@ -0,0 +1,31 @@
+//! Helpers for building and reading [JSON Pointer] tokens.
+//!
+//! [JSON Pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+
+/// Escapes `~` and `/` in a reference token.
+pub fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverses [`escape`], turning `~1` back into `/` and `~0` into `~`.
+pub fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Appends `token`, escaped, as a new segment of the pointer `base`.
+pub fn append(base: &str, token: &str) -> String {
+    format!("{base}/{}", escape(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_tokens() {
+        assert_eq!(escape("a/b~c"), "a~1b~0c");
+        assert_eq!(unescape("a~1b~0c"), "a/b~c");
+        assert_eq!(unescape("~01"), "~1");
+        assert_eq!(append("#/paths", "/pets/{id}"), "#/paths/~1pets~1{id}");
+    }
+}
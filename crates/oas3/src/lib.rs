@@ -1,7 +1,7 @@
 //! Structures and tools to parse, navigate and validate [OpenAPI v3.1] specifications.
 //!
 //! Note that due to v3.1 being a breaking change from v3.0, you may have trouble correctly parsing
-//! specs in the older format.
+//! specs in the older format. Use [`upgrade_from_3_0`] to convert them first.
 //!
 //! # Example
 //!
@@ -19,10 +19,23 @@
 
 use std::{fs::File, io::Read, path::Path};
 
+mod bundle;
+mod diagnostics;
+mod diff;
 mod error;
+pub mod json_pointer;
 pub mod spec;
+mod strict;
+mod upgrade;
 
-pub use self::{error::Error, spec::Spec};
+pub use self::{
+    bundle::{bundle, Bundle, BundleError, ComponentRename},
+    diagnostics::Diagnostic,
+    diff::{diff, Change, ChangeKind, SpecDiff},
+    error::Error,
+    spec::Spec,
+    upgrade::{upgrade_from_3_0, UpgradeError},
+};
 
 /// Version 3.1.0 of the OpenAPI specification.
 ///
@@ -41,17 +54,151 @@ where
 
 /// Try deserializing an OpenAPI spec (YAML or JSON) from a [`Read`] type.
 pub fn from_reader<R>(read: R) -> Result<OpenApiV3Spec, Error>
+where
+    R: Read,
+{
+    from_yaml_reader(read)
+}
+
+/// Try deserializing an OpenAPI spec from a [`Read`] type containing YAML.
+///
+/// Since YAML is a superset of JSON, JSON input is also accepted.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let yaml = "
+/// openapi: 3.1.0
+/// info:
+///   title: Pets
+///   version: 1.0.0
+/// paths: {}
+/// ";
+///
+/// let spec = oas3::from_yaml_reader(Cursor::new(yaml)).unwrap();
+/// assert_eq!(spec.info.title, "Pets");
+/// ```
+pub fn from_yaml_reader<R>(read: R) -> Result<OpenApiV3Spec, Error>
 where
     R: Read,
 {
     Ok(serde_yml::from_reader::<R, OpenApiV3Spec>(read)?)
 }
 
+/// Try deserializing an OpenAPI spec from a [`Read`] type containing JSON.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let json = r#"{
+///   "openapi": "3.1.0",
+///   "info": { "title": "Pets", "version": "1.0.0" },
+///   "paths": {}
+/// }"#;
+///
+/// let spec = oas3::from_json_reader(Cursor::new(json)).unwrap();
+/// assert_eq!(spec.info.title, "Pets");
+/// ```
+pub fn from_json_reader<R>(read: R) -> Result<OpenApiV3Spec, Error>
+where
+    R: Read,
+{
+    Ok(serde_json::from_reader::<R, OpenApiV3Spec>(read)?)
+}
+
 /// Try deserializing an OpenAPI spec (YAML or JSON) from string.
 pub fn from_str(val: impl AsRef<str>) -> Result<OpenApiV3Spec, Error> {
     Ok(serde_yml::from_str::<OpenApiV3Spec>(val.as_ref())?)
 }
 
+/// Try deserializing an OpenAPI spec (YAML or JSON) from string, rejecting unknown fields.
+///
+/// Unlike [`from_str`], fields that are not part of the OpenAPI model (e.g., a misspelled
+/// `requierd`) cause an [`Error::UnknownFields`] error listing a JSON pointer to each of them.
+/// Specification extensions (fields prefixed with `x-`) are still allowed.
+///
+/// Unknown fields holding `null` or an empty object or array are not detected, since they cannot
+/// be distinguished from known fields that are omitted when empty.
+///
+/// # Examples
+///
+/// ```
+/// let yaml = "
+/// openapi: 3.1.0
+/// info:
+///   title: Pets
+///   version: 1.0.0
+///   x-internal-id: 42
+/// paths: {}
+/// webhook:
+///   newPet: {}
+/// ";
+///
+/// let err = oas3::from_yaml_strict(yaml).unwrap_err();
+/// assert_eq!(err.to_string(), "Unknown fields: #/webhook");
+/// ```
+pub fn from_yaml_strict(val: impl AsRef<str>) -> Result<OpenApiV3Spec, Error> {
+    let value =
+        serde_yml::from_str::<serde_yml::Value>(val.as_ref()).and_then(strict::yaml_to_json)?;
+    strict::from_value_strict(value)
+}
+
+/// Try deserializing an OpenAPI spec from a JSON string, rejecting unknown fields.
+///
+/// See [`from_yaml_strict`] for details.
+pub fn from_json_strict(val: impl AsRef<str>) -> Result<OpenApiV3Spec, Error> {
+    let value = serde_json::from_str::<serde_json::Value>(val.as_ref())?;
+    strict::from_value_strict(value)
+}
+
+/// Deserialize an OpenAPI spec (YAML or JSON) from string, collecting all problems found.
+///
+/// Rather than stopping at the first error, invalid values are reported as [`Diagnostic`]s and
+/// removed so that parsing can continue. The salvaged spec is returned if the remaining document
+/// is valid. Failures of objects caused only by the removal of their invalid descendants (e.g.,
+/// a now-missing required field) are not reported separately.
+///
+/// Syntax errors are reported with a line and column; all other diagnostics carry a JSON pointer
+/// to the invalid value.
+///
+/// # Examples
+///
+/// ```
+/// let yaml = "
+/// openapi: 3.1.0
+/// info:
+///   title: Pets
+///   version: 1.0.0
+/// paths:
+///   /pets:
+///     get:
+///       parameters:
+///         - name: id
+///           in: nowhere
+/// ";
+///
+/// let (spec, diagnostics) = oas3::from_yaml_diagnostics(yaml);
+/// assert!(spec.is_some());
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(
+///     diagnostics[0].pointer.as_deref(),
+///     Some("#/paths/~1pets/get/parameters/0"),
+/// );
+/// ```
+pub fn from_yaml_diagnostics(val: impl AsRef<str>) -> (Option<OpenApiV3Spec>, Vec<Diagnostic>) {
+    diagnostics::from_yaml_diagnostics(val.as_ref())
+}
+
+/// Try deserializing an OpenAPI spec from a TOML string.
+#[cfg(feature = "toml-spec")]
+pub fn from_toml(val: impl AsRef<str>) -> Result<OpenApiV3Spec, Error> {
+    Ok(toml::from_str::<OpenApiV3Spec>(val.as_ref())?)
+}
+
 /// Try serializing to a YAML string.
 pub fn to_yaml(spec: &OpenApiV3Spec) -> Result<String, Error> {
     Ok(serde_yml::to_string(spec)?)
@@ -62,6 +209,12 @@ pub fn to_json(spec: &OpenApiV3Spec) -> Result<String, Error> {
     Ok(serde_json::to_string_pretty(spec)?)
 }
 
+/// Try serializing to a TOML string.
+#[cfg(feature = "toml-spec")]
+pub fn to_toml(spec: &OpenApiV3Spec) -> Result<String, Error> {
+    Ok(toml::to_string_pretty(spec)?)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -211,5 +364,153 @@ components:
             from_reader(json.as_bytes()).unwrap(),
             from_reader(yaml.as_bytes()).unwrap()
         );
+
+        assert_eq!(
+            from_json_reader(json.as_bytes()).unwrap(),
+            from_yaml_reader(yaml.as_bytes()).unwrap()
+        );
+
+        // YAML input is not valid JSON
+        from_json_reader(yaml.as_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn strict_parsing() {
+        let yaml = indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Test API
+              version: '0.1'
+            paths:
+              /pets:
+                get:
+                  tags: []
+                  x-rate-limit: 10
+                  responsess:
+                    '200':
+                      description: OK
+                  responses:
+                    '200':
+                      description: OK
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  requierd: [name]
+        "};
+
+        // lenient parsing ignores unknown fields
+        from_str(yaml).unwrap();
+
+        let Error::UnknownFields(mut ptrs) = from_yaml_strict(yaml).unwrap_err() else {
+            panic!("expected unknown fields error");
+        };
+        ptrs.sort();
+        assert_eq!(
+            ptrs,
+            [
+                "#/components/schemas/Pet/requierd",
+                "#/paths/~1pets/get/responsess",
+            ],
+        );
+
+        let json = r#"{
+            "openapi": "3.1.0",
+            "info": { "title": "Test API", "version": "0.1", "x-logo": "logo.png" },
+            "paths": {}
+        }"#;
+        assert_eq!(from_json_strict(json).unwrap(), from_str(json).unwrap());
+    }
+
+    #[test]
+    fn strict_parsing_accepts_valid_specs() {
+        let yaml = indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Test API
+              version: '0.1'
+            paths:
+              /pets:
+                get:
+                  responses:
+                    200:
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            $ref: '#/components/schemas/B'
+                            description: A pet.
+            components:
+              schemas:
+                A:
+                  type: object
+                B:
+                  $ref: '#/components/schemas/A'
+                  summary: Alias of A.
+                  description: Alias of A.
+        "};
+
+        assert_eq!(from_yaml_strict(yaml).unwrap(), from_str(yaml).unwrap());
+    }
+
+    #[cfg(feature = "toml-spec")]
+    #[test]
+    fn toml_round_trip() {
+        let yaml = indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Test API
+              version: '0.1'
+            paths:
+              /pets:
+                get:
+                  operationId: listPets
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            type: array
+                            items:
+                              $ref: '#/components/schemas/Pet'
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  required: [name]
+                  properties:
+                    name:
+                      type: string
+        "};
+
+        let toml = indoc::indoc! {r##"
+            openapi = "3.1.0"
+
+            [info]
+            title = "Test API"
+            version = "0.1"
+
+            [paths."/pets".get]
+            operationId = "listPets"
+
+            [paths."/pets".get.responses.200]
+            description = "OK"
+
+            [paths."/pets".get.responses.200.content."application/json".schema]
+            type = "array"
+            items = { "$ref" = "#/components/schemas/Pet" }
+
+            [components.schemas.Pet]
+            type = "object"
+            required = ["name"]
+
+            [components.schemas.Pet.properties.name]
+            type = "string"
+        "##};
+
+        let spec = from_str(yaml).unwrap();
+        assert_eq!(from_toml(toml).unwrap(), spec);
+        assert_eq!(from_toml(to_toml(&spec).unwrap()).unwrap(), spec);
     }
 }
@@ -0,0 +1,378 @@
+//! Builder for constructing specs programmatically.
+
+use std::collections::BTreeMap;
+
+use url::Url;
+
+use super::{
+    Components, Contact, ContactError, ExternalDoc, Info, License, OrderedMap, PathItem,
+    SecurityRequirement, Server, Spec, Tag,
+};
+
+/// Fluent builder for [`Spec`].
+///
+/// Created using [`Spec::builder()`]. Unset collections are left empty and `openapi` defaults to
+/// `3.1.0`.
+///
+/// # Examples
+/// ```
+/// use oas3::spec::{Info, PathItem, Spec};
+///
+/// let spec = Spec::builder()
+///     .info(Info {
+///         title: "Pet Store".to_owned(),
+///         version: "1.0.0".to_owned(),
+///         ..Info::default()
+///     })
+///     .add_path("/pets", PathItem::default())
+///     .build();
+///
+/// assert_eq!(spec.openapi, "3.1.0");
+/// assert!(spec.paths.unwrap().contains_key("/pets"));
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct SpecBuilder {
+    spec: Spec,
+}
+
+impl Default for SpecBuilder {
+    fn default() -> Self {
+        Self {
+            spec: Spec {
+                openapi: "3.1.0".to_owned(),
+                info: Info::default(),
+                servers: Vec::new(),
+                paths: None,
+                components: None,
+                security: Vec::new(),
+                tags: Vec::new(),
+                webhooks: BTreeMap::new(),
+                external_docs: None,
+                extensions: BTreeMap::new(),
+            },
+        }
+    }
+}
+
+impl SpecBuilder {
+    /// Sets the OpenAPI version.
+    pub fn openapi(mut self, version: impl Into<String>) -> Self {
+        self.spec.openapi = version.into();
+        self
+    }
+
+    /// Sets the API metadata.
+    pub fn info(mut self, info: Info) -> Self {
+        self.spec.info = info;
+        self
+    }
+
+    /// Appends a server.
+    pub fn add_server(mut self, server: Server) -> Self {
+        self.spec.servers.push(server);
+        self
+    }
+
+    /// Adds a path item, replacing any existing item at the same `path`.
+    pub fn add_path(mut self, path: impl Into<String>, item: PathItem) -> Self {
+        self.spec
+            .paths
+            .get_or_insert_with(OrderedMap::new)
+            .insert(path.into(), item);
+        self
+    }
+
+    /// Sets the reusable components.
+    pub fn components(mut self, components: Components) -> Self {
+        self.spec.components = Some(components);
+        self
+    }
+
+    /// Appends a top-level security requirement.
+    pub fn add_security(mut self, requirement: SecurityRequirement) -> Self {
+        self.spec.security.push(requirement);
+        self
+    }
+
+    /// Appends a tag.
+    pub fn add_tag(mut self, tag: Tag) -> Self {
+        self.spec.tags.push(tag);
+        self
+    }
+
+    /// Adds a webhook, replacing any existing webhook with the same `name`.
+    pub fn add_webhook(mut self, name: impl Into<String>, item: PathItem) -> Self {
+        self.spec.webhooks.insert(name.into(), item);
+        self
+    }
+
+    /// Sets the external documentation.
+    pub fn external_docs(mut self, docs: ExternalDoc) -> Self {
+        self.spec.external_docs = Some(docs);
+        self
+    }
+
+    /// Adds a specification extension. The `x-` prefix is added when serializing.
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.spec.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the built spec.
+    pub fn build(self) -> Spec {
+        self.spec
+    }
+}
+
+impl Spec {
+    /// Returns a builder for constructing a spec programmatically.
+    pub fn builder() -> SpecBuilder {
+        SpecBuilder::default()
+    }
+}
+
+/// Fluent builder for [`Info`].
+///
+/// Created using [`Info::builder()`].
+///
+/// # Examples
+/// ```
+/// use oas3::spec::{Info, License};
+///
+/// let info = Info::builder()
+///     .title("Pet Store")
+///     .version("1.0.0")
+///     .license(License::spdx("MIT"))
+///     .build();
+///
+/// assert_eq!(info.title, "Pet Store");
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct InfoBuilder {
+    info: Info,
+}
+
+impl InfoBuilder {
+    /// Sets the title of the API.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.info.title = title.into();
+        self
+    }
+
+    /// Sets the version of the API document.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.info.version = version.into();
+        self
+    }
+
+    /// Sets the short summary of the API.
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.info.summary = Some(summary.into());
+        self
+    }
+
+    /// Sets the description of the API.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.info.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URL of the terms of service.
+    pub fn terms_of_service(mut self, url: Url) -> Self {
+        self.info.terms_of_service = Some(url);
+        self
+    }
+
+    /// Sets the contact information.
+    pub fn contact(mut self, contact: Contact) -> Self {
+        self.info.contact = Some(contact);
+        self
+    }
+
+    /// Sets the license information.
+    pub fn license(mut self, license: License) -> Self {
+        self.info.license = Some(license);
+        self
+    }
+
+    /// Adds a specification extension. The `x-` prefix is added when serializing.
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.info.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the built info.
+    pub fn build(self) -> Info {
+        self.info
+    }
+}
+
+impl Info {
+    /// Returns a builder for constructing API metadata programmatically.
+    pub fn builder() -> InfoBuilder {
+        InfoBuilder::default()
+    }
+}
+
+/// Fluent builder for [`Contact`].
+///
+/// Created using [`Contact::builder()`]. The URL and email address are validated when building.
+///
+/// # Examples
+/// ```
+/// use oas3::spec::Contact;
+///
+/// let contact = Contact::builder()
+///     .name("API Team")
+///     .email("api@example.com")
+///     .url("https://example.com/support")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(contact.email.as_deref(), Some("api@example.com"));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct ContactBuilder {
+    contact: Contact,
+    url: Option<String>,
+}
+
+impl ContactBuilder {
+    /// Sets the identifying name of the contact person/organization.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.contact.name = Some(name.into());
+        self
+    }
+
+    /// Sets the email address of the contact person/organization.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.contact.email = Some(email.into());
+        self
+    }
+
+    /// Sets the URL pointing to the contact information.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Adds a specification extension. The `x-` prefix is added when serializing.
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.contact.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the built contact, validating its URL and email address.
+    pub fn build(self) -> Result<Contact, ContactError> {
+        let mut contact = self.contact;
+
+        if let Some(url) = self.url {
+            let parsed = Url::parse(&url).map_err(|_| ContactError::Url(url))?;
+            contact.url = Some(parsed);
+        }
+
+        contact.validate()?;
+
+        Ok(contact)
+    }
+}
+
+impl Contact {
+    /// Returns a builder for constructing contact information programmatically.
+    pub fn builder() -> ContactBuilder {
+        ContactBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_round_trips() {
+        let spec = Spec::builder()
+            .info(Info {
+                title: "Pet Store".to_owned(),
+                version: "1.0.0".to_owned(),
+                ..Info::default()
+            })
+            .add_server(Server::default())
+            .add_path("/pets", PathItem::default())
+            .extension("internal", serde_json::Value::Bool(true))
+            .build();
+
+        let yaml = crate::to_yaml(&spec).unwrap();
+        assert_eq!(
+            yaml,
+            indoc::indoc! {"
+                openapi: '3.1.0'
+                info:
+                  title: Pet Store
+                  version: '1.0.0'
+                servers:
+                - url: /
+                paths:
+                  /pets: {}
+                x-internal: true
+            "},
+        );
+
+        assert_eq!(crate::from_str(yaml).unwrap(), spec);
+    }
+
+    #[test]
+    fn info_builder_serializes() {
+        let info = Info::builder()
+            .title("Pet Store")
+            .version("1.0.0")
+            .summary("Pets.")
+            .terms_of_service(Url::parse("https://example.com/terms").unwrap())
+            .contact(Contact {
+                name: Some("API Team".to_owned()),
+                url: None,
+                email: None,
+                extensions: BTreeMap::new(),
+            })
+            .license(License::spdx("MIT"))
+            .build();
+
+        assert_eq!(
+            serde_yml::to_string(&info).unwrap(),
+            indoc::indoc! {"
+                title: Pet Store
+                summary: Pets.
+                termsOfService: https://example.com/terms
+                version: '1.0.0'
+                contact:
+                  name: API Team
+                license:
+                  name: MIT
+                  identifier: MIT
+            "},
+        );
+    }
+
+    #[test]
+    fn contact_builder_validates() {
+        let contact = Contact::builder()
+            .name("API Team")
+            .email("api@example.com")
+            .url("https://example.com/support")
+            .build()
+            .unwrap();
+        assert_eq!(
+            contact.url,
+            Some(Url::parse("https://example.com/support").unwrap()),
+        );
+
+        let err = Contact::builder().url("not a url").build().unwrap_err();
+        assert_eq!(err, ContactError::Url("not a url".to_owned()));
+        assert_eq!(err.to_string(), "Contact url is not valid: not a url");
+
+        let err = Contact::builder().email("api").build().unwrap_err();
+        assert_eq!(err, ContactError::Email("api".to_owned()));
+    }
+}
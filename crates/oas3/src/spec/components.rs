@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     schema::ObjectSchema, spec_extensions, Callback, Example, Header, Link, ObjectOrReference,
-    Parameter, PathItem, RequestBody, Response, SecurityScheme,
+    OrderedMap, Parameter, PathItem, RequestBody, Response, SecurityScheme,
 };
 
 /// Holds a set of reusable objects for different aspects of the OAS.
@@ -13,11 +13,11 @@ use super::{
 /// they are explicitly referenced from properties outside the components object.
 ///
 /// See <https://spec.openapis.org/oas/v3.1.0#components-object>.
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct Components {
     /// An object to hold reusable [Schema Objects](ObjectSchema).
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub schemas: BTreeMap<String, ObjectOrReference<ObjectSchema>>,
+    #[serde(default, skip_serializing_if = "OrderedMap::is_empty")]
+    pub schemas: OrderedMap<String, ObjectOrReference<ObjectSchema>>,
 
     /// An object to hold reusable [Response Objects](Response).
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
@@ -75,3 +75,36 @@ pub struct Components {
     #[serde(flatten, with = "spec_extensions")]
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions_round_trip() {
+        let components = serde_yml::from_str::<Components>(indoc::indoc! {"
+            schemas:
+              Pet:
+                type: object
+            x-generated-by: spec-tool
+            x-owners: [pets-team]
+            unknown: ignored
+        "})
+        .unwrap();
+
+        assert_eq!(
+            components.extensions,
+            BTreeMap::from([
+                ("generated-by".to_owned(), serde_json::json!("spec-tool")),
+                ("owners".to_owned(), serde_json::json!(["pets-team"])),
+            ]),
+        );
+
+        let yaml = serde_yml::to_string(&components).unwrap();
+        assert!(!yaml.contains("unknown"));
+        assert_eq!(
+            serde_yml::from_str::<Components>(&yaml).unwrap(),
+            components
+        );
+    }
+}
@@ -12,10 +12,22 @@ use super::spec_extensions;
 #[non_exhaustive]
 pub struct InvalidEmail;
 
+/// Contact validation errors.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum ContactError {
+    /// The `email` field is not a valid email address.
+    #[display("Contact email is not valid: {}", _0)]
+    Email(#[error(not(source))] String),
+
+    /// The `url` field is not a valid URL.
+    #[display("Contact url is not valid: {}", _0)]
+    Url(#[error(not(source))] String),
+}
+
 /// Contact information for the exposed API.
 ///
 /// See <https://spec.openapis.org/oas/v3.1.0#contact-object>.
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 pub struct Contact {
     /// Identifying name of the contact person/organization.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -48,10 +60,65 @@ impl Contact {
             return Ok(());
         };
 
-        if email.contains("@") {
+        if is_email_like(email) {
             Ok(())
         } else {
             Err(InvalidEmail)
         }
     }
+
+    /// Validates contact fields.
+    ///
+    /// The `url` field is checked when parsing, so only the email address is validated here. Use
+    /// [`ContactBuilder`](super::ContactBuilder) to also validate URLs provided as strings.
+    pub fn validate(&self) -> Result<(), ContactError> {
+        match &self.email {
+            Some(email) if !is_email_like(email) => Err(ContactError::Email(email.clone())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns true if `email` has the shape of an email address.
+fn is_email_like(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.contains('@')
+        && !email.contains(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let contact = Contact {
+            email: Some("api@example.com".to_owned()),
+            ..Contact::default()
+        };
+        contact.validate().unwrap();
+        contact.validate_email().unwrap();
+
+        for email in [
+            "api",
+            "@example.com",
+            "api@",
+            "api@@example.com",
+            "api @example.com",
+        ] {
+            let contact = Contact {
+                email: Some(email.to_owned()),
+                ..Contact::default()
+            };
+            assert_eq!(
+                contact.validate().unwrap_err(),
+                ContactError::Email(email.to_owned()),
+            );
+        }
+    }
 }
@@ -0,0 +1,199 @@
+//! Best-effort conversion of specs to OpenAPI 3.0.
+
+use derive_more::derive::{Display, Error, From};
+use log::warn;
+use serde_json::{Map, Value};
+
+use super::Spec;
+
+/// Version written to the `openapi` field of downgraded specs.
+const OPENAPI_3_0_VERSION: &str = "3.0.3";
+
+/// Spec downgrade errors.
+#[derive(Debug, Display, Error, From)]
+pub enum DowngradeError {
+    /// Spec could not be serialized.
+    #[display("Failed to serialize spec")]
+    Serialize(serde_json::Error),
+
+    /// Schema declares a set of types that OpenAPI 3.0 cannot express.
+    #[display("{}: type set cannot be represented in OpenAPI 3.0", _0)]
+    #[from(ignore)]
+    UnrepresentableType(#[error(not(source))] String),
+}
+
+impl Spec {
+    /// Converts this spec to an OpenAPI 3.0 document, on a best-effort basis.
+    ///
+    /// The conversion is lossy. Schemas are rewritten as follows:
+    /// - `type: [T, "null"]` becomes `type: T` with `nullable: true`;
+    /// - `examples` is replaced by an `example` holding its first entry;
+    /// - `const` becomes a single-value `enum`;
+    /// - numeric `exclusiveMinimum`/`exclusiveMaximum` become `minimum`/`maximum` with a boolean
+    ///   exclusivity flag.
+    ///
+    /// Fields without a 3.0 equivalent (`webhooks`, `info.summary`, `license.identifier`, and
+    /// `components.pathItems`) are dropped, logging a warning. Other 3.1-only schema keywords are
+    /// left as-is.
+    ///
+    /// Schemas with more than one non-null type, or only the `null` type, cannot be represented
+    /// and return an error.
+    pub fn downgrade_to_3_0(&self) -> Result<Value, DowngradeError> {
+        let mut doc = serde_json::to_value(self)?;
+
+        for (ptr, _) in self.schemas() {
+            if let Some(Value::Object(schema)) = doc.pointer_mut(&ptr[1..]) {
+                downgrade_schema(&ptr, schema)?;
+            }
+        }
+
+        let root = doc.as_object_mut().expect("spec serializes to an object");
+
+        root.insert(
+            "openapi".to_owned(),
+            Value::String(OPENAPI_3_0_VERSION.to_owned()),
+        );
+        root.entry("paths")
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        drop_field(root, "#", "webhooks");
+
+        if let Some(Value::Object(info)) = root.get_mut("info") {
+            drop_field(info, "#/info", "summary");
+
+            if let Some(Value::Object(license)) = info.get_mut("license") {
+                drop_field(license, "#/info/license", "identifier");
+            }
+        }
+
+        if let Some(Value::Object(components)) = root.get_mut("components") {
+            drop_field(components, "#/components", "pathItems");
+        }
+
+        Ok(doc)
+    }
+}
+
+/// Removes `field` from `obj`, logging a warning if it was present.
+fn drop_field(obj: &mut Map<String, Value>, ptr: &str, field: &str) {
+    if obj.remove(field).is_some() {
+        warn!("{ptr}/{field} is not supported in OpenAPI 3.0 and was dropped");
+    }
+}
+
+/// Rewrites a single schema object's 3.1 keywords in place.
+fn downgrade_schema(ptr: &str, schema: &mut Map<String, Value>) -> Result<(), DowngradeError> {
+    if let Some(Value::Array(types)) = schema.get("type") {
+        let nullable = types.iter().any(|type_| type_ == "null");
+        let mut types = types.iter().filter(|type_| *type_ != "null");
+
+        let (Some(type_), None) = (types.next(), types.next()) else {
+            return Err(DowngradeError::UnrepresentableType(ptr.to_owned()));
+        };
+
+        schema.insert("type".to_owned(), type_.clone());
+
+        if nullable {
+            schema.insert("nullable".to_owned(), Value::Bool(true));
+        }
+    }
+
+    if let Some(Value::Array(examples)) = schema.remove("examples") {
+        if let Some(example) = examples.into_iter().next() {
+            schema.entry("example").or_insert(example);
+        }
+    }
+
+    if let Some(value) = schema.remove("const") {
+        schema.insert("enum".to_owned(), Value::Array(vec![value]));
+    }
+
+    for (exclusive, inclusive) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(bound @ Value::Number(_)) = schema.get(exclusive).cloned() {
+            schema.insert(inclusive.to_owned(), bound);
+            schema.insert(exclusive.to_owned(), Value::Bool(true));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn downgrade_nullable_type() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              summary: Test API
+              version: v1
+            webhooks:
+              newPet:
+                post:
+                  responses:
+                    '200':
+                      description: OK
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  properties:
+                    name:
+                      type: [string, 'null']
+                      examples: [Rex, Fido]
+                    kind:
+                      const: dog
+                    age:
+                      type: integer
+                      exclusiveMinimum: 0
+        "})
+        .unwrap();
+
+        let doc = spec.downgrade_to_3_0().unwrap();
+
+        assert_eq!(doc["openapi"], "3.0.3");
+        assert_eq!(doc["paths"], json!({}));
+        assert!(doc.get("webhooks").is_none());
+        assert!(doc["info"].get("summary").is_none());
+
+        let props = &doc["components"]["schemas"]["Pet"]["properties"];
+        assert_eq!(
+            props["name"],
+            json!({ "type": "string", "nullable": true, "example": "Rex" }),
+        );
+        assert_eq!(props["kind"], json!({ "enum": ["dog"] }));
+        assert_eq!(
+            props["age"],
+            json!({ "type": "integer", "minimum": 0, "exclusiveMinimum": true }),
+        );
+    }
+
+    #[test]
+    fn downgrade_multiple_types() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Id:
+                  type: [string, integer]
+        "})
+        .unwrap();
+
+        let err = spec.downgrade_to_3_0().unwrap_err();
+        assert!(matches!(
+            err,
+            DowngradeError::UnrepresentableType(ptr) if ptr == "#/components/schemas/Id",
+        ));
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::{Header, ObjectOrReference};
+use super::{Header, ObjectOrReference, RefError, Spec};
 
 /// A single encoding definition applied to a single schema property.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -52,3 +52,75 @@ pub struct Encoding {
     #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
     pub allow_reserved: Option<bool>,
 }
+
+impl Encoding {
+    /// Resolves and returns map of this encoding's headers, keyed by header name.
+    pub fn headers_resolved(&self, spec: &Spec) -> Result<BTreeMap<String, Header>, RefError> {
+        self.headers
+            .iter()
+            .map(|(name, oor)| oor.resolve(spec).map(|header| (name.clone(), header)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::MediaType;
+
+    #[test]
+    fn media_type_encoding_round_trip() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              headers:
+                Rate:
+                  schema:
+                    type: integer
+        "})
+        .unwrap();
+
+        let yaml = indoc::indoc! {"
+            schema:
+              type: object
+              properties:
+                avatar:
+                  type: string
+                  contentEncoding: base64
+                tags:
+                  type: array
+                  items:
+                    type: string
+            encoding:
+              avatar:
+                contentType: image/png
+                headers:
+                  X-Rate:
+                    $ref: '#/components/headers/Rate'
+              tags:
+                style: form
+                explode: false
+                allowReserved: true
+        "};
+
+        let media_type = serde_yml::from_str::<MediaType>(yaml).unwrap();
+
+        let avatar = &media_type.encoding["avatar"];
+        assert_eq!(avatar.content_type.as_deref(), Some("image/png"));
+
+        let headers = avatar.headers_resolved(&spec).unwrap();
+        assert!(headers["X-Rate"].schema.is_some());
+
+        let tags = &media_type.encoding["tags"];
+        assert_eq!(tags.style.as_deref(), Some("form"));
+        assert_eq!(tags.explode, Some(false));
+        assert_eq!(tags.allow_reserved, Some(true));
+
+        let round_tripped =
+            serde_yml::from_str::<MediaType>(&serde_yml::to_string(&media_type).unwrap()).unwrap();
+        assert_eq!(round_tripped, media_type);
+    }
+}
@@ -21,4 +21,19 @@ pub enum Error {
     /// Unsupported spec file version.
     #[display("Unsupported spec file version ({})", _0)]
     UnsupportedSpecFileVersion(#[error(not(source))] Version),
+
+    /// None of `paths`, `components`, or `webhooks` are present.
+    #[display("At least one of `paths`, `components`, or `webhooks` must be present")]
+    #[from(ignore)]
+    NoContainers,
+
+    /// Tag name is declared more than once.
+    #[display("Duplicate tag name: {}", _0)]
+    #[from(ignore)]
+    DuplicateTagName(#[error(not(source))] String),
+
+    /// Operation ID is used by more than one operation.
+    #[display("Duplicate operation ID: {}", _0)]
+    #[from(ignore)]
+    DuplicateOperationId(#[error(not(source))] String),
 }
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::{spec_extensions, FromRef, Ref, RefError, RefType, Spec};
+use super::{resolve_component, spec_extensions, FromRef, RefError, RefType, Spec};
 
 /// Multi-purpose example objects.
 ///
@@ -55,17 +55,8 @@ impl Example {
 
 impl FromRef for Example {
     fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
-        let refpath = path.parse::<Ref>()?;
-
-        match refpath.kind {
-            RefType::Example => spec
-                .components
-                .as_ref()
-                .and_then(|cs| cs.examples.get(&refpath.name))
-                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
-                .and_then(|oor| oor.resolve(spec)),
-
-            typ => Err(RefError::MismatchedType(typ, RefType::Example)),
-        }
+        resolve_component(spec, path, RefType::Example, |cs, name| {
+            cs.examples.get(name)
+        })
     }
 }
@@ -0,0 +1,151 @@
+//! Typed access to specification extensions.
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+
+use super::{
+    Callback, Components, Contact, Example, ExternalDoc, Flows, Header, ImplicitFlow, Info,
+    License, ObjectSchema, Operation, Parameter, PathItem, Response, Server, ServerVariable, Spec,
+    Tag,
+};
+
+/// Types that carry specification extensions.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#specification-extensions>.
+pub trait Extensible {
+    /// Returns this object's specification extensions, keyed without their `x-` prefix.
+    fn extensions(&self) -> &BTreeMap<String, serde_json::Value>;
+
+    /// Returns a mutable reference to this object's specification extensions.
+    ///
+    /// Keys are inserted without their `x-` prefix, which is added when serializing.
+    fn extensions_mut(&mut self) -> &mut BTreeMap<String, serde_json::Value>;
+
+    /// Deserializes the specification extension named `key`, or returns `None` if it is absent.
+    ///
+    /// The `x-` prefix of `key` is optional.
+    fn extension<T: DeserializeOwned>(&self, key: &str) -> Option<Result<T, serde_json::Error>> {
+        let key = key.strip_prefix("x-").unwrap_or(key);
+        self.extensions().get(key).map(T::deserialize)
+    }
+}
+
+macro_rules! impl_extensible {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl Extensible for $ty {
+                fn extensions(&self) -> &BTreeMap<String, serde_json::Value> {
+                    &self.extensions
+                }
+
+                fn extensions_mut(&mut self) -> &mut BTreeMap<String, serde_json::Value> {
+                    &mut self.extensions
+                }
+            }
+        )+
+    };
+}
+
+impl_extensible!(
+    Spec,
+    Info,
+    Contact,
+    License,
+    Server,
+    ServerVariable,
+    Components,
+    PathItem,
+    Operation,
+    ExternalDoc,
+    Parameter,
+    Response,
+    Callback,
+    Flows,
+    ImplicitFlow,
+    Example,
+    Header,
+    Tag,
+    ObjectSchema,
+);
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct RateLimit {
+        requests: u32,
+        window: String,
+    }
+
+    #[test]
+    fn typed_extension() {
+        let op = serde_yml::from_str::<Operation>(indoc::indoc! {"
+            x-rate-limit:
+              requests: 10
+              window: 1m
+            x-internal: yes
+        "})
+        .unwrap();
+
+        let expected = RateLimit {
+            requests: 10,
+            window: "1m".to_owned(),
+        };
+
+        assert_eq!(
+            op.extension::<RateLimit>("rate-limit").unwrap().unwrap(),
+            expected
+        );
+        assert_eq!(
+            op.extension::<RateLimit>("x-rate-limit").unwrap().unwrap(),
+            expected
+        );
+        assert!(op.extension::<bool>("internal").unwrap().is_err());
+        assert!(op.extension::<bool>("missing").is_none());
+    }
+
+    /// Collects extension keys from any extensible object.
+    fn extension_keys(obj: &impl Extensible) -> Vec<&str> {
+        obj.extensions().keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn generic_extensions() {
+        let mut server = serde_yml::from_str::<Server>(indoc::indoc! {"
+            url: https://example.com
+            x-region: eu
+            variables:
+              port:
+                default: '443'
+                x-internal: true
+        "})
+        .unwrap();
+
+        assert_eq!(extension_keys(&server), ["region"]);
+        assert_eq!(extension_keys(&server.variables["port"]), ["internal"]);
+
+        server
+            .extensions_mut()
+            .insert("tier".to_owned(), serde_json::json!("gold"));
+        assert_eq!(extension_keys(&server), ["region", "tier"]);
+
+        let yaml = serde_yml::to_string(&server).unwrap();
+        assert!(yaml.contains("x-tier: gold"));
+        assert_eq!(serde_yml::from_str::<Server>(&yaml).unwrap(), server);
+
+        let flows = serde_yml::from_str::<Flows>(indoc::indoc! {"
+            implicit:
+              authorizationUrl: https://example.com/auth
+              x-pkce: true
+            x-provider: example
+        "})
+        .unwrap();
+
+        assert_eq!(extension_keys(&flows), ["provider"]);
+        assert_eq!(extension_keys(flows.implicit.as_ref().unwrap()), ["pkce"]);
+    }
+}
@@ -11,6 +11,8 @@ use super::spec_extensions;
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct ExternalDoc {
     /// The URL for the target documentation.
+    ///
+    /// Must be an absolute URL; relative references fail to deserialize.
     pub url: Url,
 
     /// A short description of the target documentation.
@@ -26,3 +28,17 @@ pub struct ExternalDoc {
     #[serde(flatten, with = "spec_extensions")]
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_url_is_rejected() {
+        let doc = serde_yml::from_str::<ExternalDoc>("url: https://example.com/docs").unwrap();
+        assert_eq!(doc.url.as_str(), "https://example.com/docs");
+
+        serde_yml::from_str::<ExternalDoc>("url: '#/docs'").unwrap_err();
+        serde_yml::from_str::<ExternalDoc>("url: docs/index.html").unwrap_err();
+    }
+}
@@ -1,9 +1,12 @@
 use std::collections::BTreeMap;
 
-use serde::{Deserialize, Serialize};
+use http::Method;
+use serde::{de, Deserialize, Deserializer, Serialize};
 use url::Url;
 
-use super::spec_extensions;
+use super::{
+    resolve_component, spec_extensions, FromRef, Operation, PathItem, RefError, RefType, Spec,
+};
 
 /// Allows configuration of the supported OAuth Flows.
 ///
@@ -145,7 +148,6 @@ pub struct AuthorizationCodeFlow {
     pub scopes: BTreeMap<String, String>,
 }
 
-// TODO: Implement
 /// Map of possible out-of band callbacks related to the parent operation.
 ///
 /// Each value in the map is a Path Item Object that describes a set of requests that may be
@@ -154,11 +156,67 @@ pub struct AuthorizationCodeFlow {
 /// callback operation.
 ///
 /// See <https://spec.openapis.org/oas/v3.1.0#callback-object>.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
-pub struct Callback(
-    /// A Path Item Object used to define a callback request and expected responses.
-    serde_json::Value, // TODO: Add "Specification Extensions" https://spec.openapis.org/oas/v3.1.0#specificationExtensions}
-);
+#[derive(Clone, Debug, Serialize, PartialEq, Default)]
+pub struct Callback {
+    /// Path Items used to define callback requests and expected responses, keyed by runtime
+    /// expression.
+    #[serde(flatten)]
+    pub paths: BTreeMap<String, PathItem>,
+
+    /// Specification extensions.
+    ///
+    /// Only "x-" prefixed keys are collected, and the prefix is stripped.
+    ///
+    /// See <https://spec.openapis.org/oas/v3.1.0#specification-extensions>.
+    #[serde(flatten, with = "spec_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl Callback {
+    /// Returns an iterator over the operations of this callback's path items.
+    ///
+    /// Items are keyed by the runtime expression of their path item.
+    pub fn operations(&self) -> impl Iterator<Item = (&str, Method, &Operation)> {
+        self.paths.iter().flat_map(|(expression, item)| {
+            item.methods()
+                .into_iter()
+                .map(move |(method, op)| (expression.as_str(), method, op))
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Callback {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+
+        let mut callback = Callback::default();
+
+        for (key, value) in fields {
+            match key.strip_prefix("x-") {
+                Some(ext) => {
+                    callback.extensions.insert(ext.to_owned(), value);
+                }
+                None => {
+                    let item = serde_json::from_value(value).map_err(de::Error::custom)?;
+                    callback.paths.insert(key, item);
+                }
+            }
+        }
+
+        Ok(callback)
+    }
+}
+
+impl FromRef for Callback {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        resolve_component(spec, path, RefType::Callback, |cs, name| {
+            cs.callbacks.get(name)
+        })
+    }
+}
 
 // FIXME: Implement
 // /// Allows configuration of the supported OAuth Flows.
@@ -1,10 +1,12 @@
 use std::collections::BTreeMap;
 
+use derive_more::derive::{Display, Error};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use super::{
-    spec_extensions, Example, FromRef, MediaType, ObjectOrReference, ObjectSchema, ParameterStyle,
-    Ref, RefError, RefType, Spec,
+    resolve_component, spec_extensions, Example, FromRef, MediaType, ObjectOrReference,
+    ObjectSchema, ParameterStyle, RefError, RefType, Spec,
 };
 
 /// The Header Object mostly follows the structure of the [Parameter Object].
@@ -108,19 +110,139 @@ pub struct Header {
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
+/// Header value serialization errors.
+#[derive(Debug, Clone, PartialEq, Display, Error)]
+pub enum HeaderSerError {
+    /// Header uses a style other than `simple`.
+    #[display("Headers must use the simple style; found {:?}", _0)]
+    UnsupportedStyle(#[error(not(source))] ParameterStyle),
+
+    /// Array item or object value is itself an array or object.
+    #[display("Header values cannot contain nested arrays or objects")]
+    NestedValue,
+}
+
+impl Header {
+    /// Serializes `value` to an HTTP header value using this header's `style` and `explode`.
+    ///
+    /// Only the `simple` style (the default) is supported. Scalars are rendered as-is, arrays as
+    /// comma-separated items, and objects as comma-separated `key,value` pairs, or `key=value`
+    /// pairs when exploded. Following [RFC 6570], `null` items and object values are omitted.
+    ///
+    /// [RFC 6570]: https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.1
+    pub fn serialize_value(&self, value: &Value) -> Result<String, HeaderSerError> {
+        match self.style {
+            None | Some(ParameterStyle::Simple) => {}
+            Some(style) => return Err(HeaderSerError::UnsupportedStyle(style)),
+        }
+
+        let explode = self.explode.unwrap_or(false);
+
+        let parts = match value {
+            Value::Array(items) => items
+                .iter()
+                .filter(|item| !item.is_null())
+                .map(scalar_to_string)
+                .collect::<Result<Vec<_>, _>>()?,
+
+            Value::Object(entries) => {
+                let mut parts = vec![];
+
+                for (key, value) in entries.iter().filter(|(_, value)| !value.is_null()) {
+                    let value = scalar_to_string(value)?;
+
+                    if explode {
+                        parts.push(format!("{key}={value}"));
+                    } else {
+                        parts.push(key.clone());
+                        parts.push(value);
+                    }
+                }
+
+                parts
+            }
+
+            value => vec![scalar_to_string(value)?],
+        };
+
+        Ok(parts.join(","))
+    }
+}
+
+/// Renders a scalar JSON value without quotes, rendering `null` as an empty string.
+fn scalar_to_string(value: &Value) -> Result<String, HeaderSerError> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::Bool(bool) => Ok(bool.to_string()),
+        Value::Number(num) => Ok(num.to_string()),
+        Value::String(string) => Ok(string.clone()),
+        Value::Array(_) | Value::Object(_) => Err(HeaderSerError::NestedValue),
+    }
+}
+
 impl FromRef for Header {
     fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
-        let refpath = path.parse::<Ref>()?;
+        resolve_component(spec, path, RefType::Header, |cs, name| cs.headers.get(name))
+    }
+}
 
-        match refpath.kind {
-            RefType::Header => spec
-                .components
-                .as_ref()
-                .and_then(|cs| cs.headers.get(&refpath.name))
-                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
-                .and_then(|oor| oor.resolve(spec)),
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serialize_simple_style() {
+        let header = Header::default();
+
+        assert_eq!(header.serialize_value(&json!(5)).unwrap(), "5");
+        assert_eq!(header.serialize_value(&json!("abc")).unwrap(), "abc");
+        assert_eq!(header.serialize_value(&json!(true)).unwrap(), "true");
+        assert_eq!(header.serialize_value(&json!(null)).unwrap(), "");
+        assert_eq!(
+            header.serialize_value(&json!([3, 4, null, 5])).unwrap(),
+            "3,4,5",
+        );
+
+        let object = json!({ "firstName": "Alex", "role": "admin" });
+        assert_eq!(
+            header.serialize_value(&object).unwrap(),
+            "firstName,Alex,role,admin",
+        );
+
+        let exploded = Header {
+            explode: Some(true),
+            ..Header::default()
+        };
+        assert_eq!(
+            exploded.serialize_value(&object).unwrap(),
+            "firstName=Alex,role=admin",
+        );
+        assert_eq!(exploded.serialize_value(&json!([3, 4])).unwrap(), "3,4");
+    }
 
-            typ => Err(RefError::MismatchedType(typ, RefType::Example)),
-        }
+    #[test]
+    fn serialize_errors() {
+        let header = Header::default();
+        assert_eq!(
+            header.serialize_value(&json!([[1, 2]])).unwrap_err(),
+            HeaderSerError::NestedValue,
+        );
+        assert_eq!(
+            header
+                .serialize_value(&json!({ "a": { "b": 1 } }))
+                .unwrap_err(),
+            HeaderSerError::NestedValue,
+        );
+
+        let header = Header {
+            style: Some(ParameterStyle::Form),
+            ..Header::default()
+        };
+        assert_eq!(
+            header.serialize_value(&json!(5)).unwrap_err(),
+            HeaderSerError::UnsupportedStyle(ParameterStyle::Form),
+        );
     }
 }
@@ -9,7 +9,7 @@ use super::{spec_extensions, Contact, License};
 ///
 ///
 /// See <https://spec.openapis.org/oas/v3.1.0#info-object>.
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 // #[serde(rename_all = "lowercase")]
 pub struct Info {
     /// The title of the application.
@@ -48,3 +48,29 @@ pub struct Info {
     #[serde(flatten, with = "spec_extensions")]
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_and_extensions_round_trip() {
+        let info = indoc::indoc! {"
+            title: Pet Store
+            summary: A pet store.
+            version: '1.0.0'
+            x-logo:
+              url: https://example.com/logo.png
+        "};
+
+        let parsed_info = serde_yml::from_str::<Info>(info).unwrap();
+        assert_eq!(parsed_info.summary.as_deref(), Some("A pet store."));
+        assert_eq!(
+            parsed_info.extensions["logo"],
+            serde_json::json!({ "url": "https://example.com/logo.png" }),
+        );
+
+        let round_trip_info = serde_yml::to_string(&parsed_info).unwrap();
+        assert_eq!(info, round_trip_info);
+    }
+}
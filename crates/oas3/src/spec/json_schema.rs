@@ -0,0 +1,184 @@
+//! Export of component schemas as standalone JSON Schema documents.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use serde_json::Value;
+
+use super::{RefError, Spec};
+
+/// JSON Schema dialect declared by exported schemas.
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Reference prefix of component schemas.
+const COMPONENT_SCHEMA_PREFIX: &str = "#/components/schemas/";
+
+impl Spec {
+    /// Exports the component schema `name` as a self-contained JSON Schema (2020-12) document.
+    ///
+    /// Component schemas referenced by the schema, directly or transitively, are inlined into
+    /// `$defs` and their references rewritten to `#/$defs/{name}`. References back to the exported
+    /// schema itself are rewritten to `#`. References into a component schema, such as
+    /// `#/components/schemas/{name}/properties/id`, are rewritten likewise, keeping the rest of the
+    /// pointer. Other references are left as-is.
+    ///
+    /// Returns an error if the schema or any of its dependencies are not declared.
+    pub fn json_schema_for(&self, name: &str) -> Result<Value, RefError> {
+        let mut root = self.component_schema_value(name)?;
+        let mut defs = BTreeMap::new();
+        let mut queue = VecDeque::new();
+
+        rewrite_refs(&mut root, name, &mut queue);
+
+        while let Some(dep) = queue.pop_front() {
+            if defs.contains_key(&dep) {
+                continue;
+            }
+
+            let mut schema = self.component_schema_value(&dep)?;
+            rewrite_refs(&mut schema, name, &mut queue);
+            defs.insert(dep, schema);
+        }
+
+        let mut doc = serde_json::Map::new();
+        doc.insert("$schema".to_owned(), Value::from(JSON_SCHEMA_DIALECT));
+
+        if let Value::Object(root) = root {
+            doc.extend(root);
+        }
+
+        if !defs.is_empty() {
+            doc.insert(
+                "$defs".to_owned(),
+                Value::Object(defs.into_iter().collect()),
+            );
+        }
+
+        Ok(Value::Object(doc))
+    }
+
+    /// Returns the serialized component schema `name`, unresolved.
+    fn component_schema_value(&self, name: &str) -> Result<Value, RefError> {
+        self.components
+            .as_ref()
+            .and_then(|components| components.schemas.get(name))
+            .and_then(|schema| serde_json::to_value(schema).ok())
+            .ok_or_else(|| RefError::Unresolvable(format!("{COMPONENT_SCHEMA_PREFIX}{name}")))
+    }
+}
+
+/// Rewrites component schema references in `value` to point into `$defs`, queueing the
+/// referenced schemas other than `root`.
+fn rewrite_refs(value: &mut Value, root: &str, queue: &mut VecDeque<String>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::String(ref_path)) = obj.get_mut("$ref") {
+                if let Some(pointer) = ref_path.strip_prefix(COMPONENT_SCHEMA_PREFIX) {
+                    // references may point into a component schema
+                    let (token, rest) = match pointer.find('/') {
+                        Some(idx) => pointer.split_at(idx),
+                        None => (pointer, ""),
+                    };
+                    let name = crate::json_pointer::unescape(token);
+
+                    if name == root {
+                        *ref_path = format!("#{rest}");
+                    } else {
+                        *ref_path = format!("#/$defs/{token}{rest}");
+                        queue.push_back(name);
+                    }
+                }
+            }
+
+            for child in obj.values_mut() {
+                rewrite_refs(child, root, queue);
+            }
+        }
+
+        Value::Array(items) => {
+            for item in items {
+                rewrite_refs(item, root, queue);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn json_schema_with_defs() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  properties:
+                    owner:
+                      $ref: '#/components/schemas/Owner'
+                    parent:
+                      $ref: '#/components/schemas/Pet'
+                    name:
+                      $ref: '#/components/schemas/Owner/properties/name'
+                    nickname:
+                      $ref: '#/components/schemas/Pet/properties/name'
+                    tag:
+                      $ref: '#/components/schemas/Tag~1v2/properties/label'
+                Owner:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                Tag/v2:
+                  type: object
+                  properties:
+                    label:
+                      type: string
+                Broken:
+                  $ref: '#/components/schemas/Missing'
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.json_schema_for("Pet").unwrap(),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "owner": { "$ref": "#/$defs/Owner" },
+                    "parent": { "$ref": "#" },
+                    "name": { "$ref": "#/$defs/Owner/properties/name" },
+                    "nickname": { "$ref": "#/properties/name" },
+                    "tag": { "$ref": "#/$defs/Tag~1v2/properties/label" },
+                },
+                "$defs": {
+                    "Owner": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                        },
+                    },
+                    "Tag/v2": {
+                        "type": "object",
+                        "properties": {
+                            "label": { "type": "string" },
+                        },
+                    },
+                },
+            }),
+        );
+
+        assert_eq!(
+            spec.json_schema_for("Broken").unwrap_err(),
+            RefError::Unresolvable("#/components/schemas/Missing".to_owned()),
+        );
+    }
+}
@@ -1,10 +1,19 @@
 use std::collections::BTreeMap;
 
+use derive_more::derive::{Display, Error};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use super::spec_extensions;
 
+/// License validation errors.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum LicenseError {
+    /// Both `identifier` and `url` are present.
+    #[display("License identifier and url are mutually exclusive")]
+    IdentifierAndUrl,
+}
+
 /// License information for the exposed API.
 ///
 /// See <https://spec.openapis.org/oas/v3.1.0#license-object>.
@@ -29,3 +38,66 @@ pub struct License {
     #[serde(flatten, with = "spec_extensions")]
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
+
+impl License {
+    /// Constructs a license from an SPDX license expression, which is also used as its name.
+    pub fn spdx(id: &str) -> Self {
+        Self {
+            name: id.to_owned(),
+            identifier: Some(id.to_owned()),
+            url: None,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Checks that at most one of `identifier` and `url` is present.
+    pub fn validate(&self) -> Result<(), LicenseError> {
+        if self.identifier.is_some() && self.url.is_some() {
+            return Err(LicenseError::IdentifierAndUrl);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Spec;
+
+    #[test]
+    fn identifier_sample() {
+        // same as the `license_identifier.yaml` sample, which is outside the crate package
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: API
+              summary: My lovely API
+              version: 1.0.0
+              license:
+                name: Apache
+                identifier: Apache-2.0
+            components: {}
+        "})
+        .unwrap();
+
+        let license = spec.info.license.unwrap();
+        assert_eq!(license.identifier.as_deref(), Some("Apache-2.0"));
+        assert!(license.url.is_none());
+        assert!(license.validate().is_ok());
+    }
+
+    #[test]
+    fn spdx_and_validate() {
+        let license = License::spdx("MIT");
+        assert_eq!(license.name, "MIT");
+        assert_eq!(license.identifier.as_deref(), Some("MIT"));
+        assert!(license.validate().is_ok());
+
+        let license = License {
+            url: Some(Url::parse("https://opensource.org/licenses/MIT").unwrap()),
+            ..license
+        };
+        assert_eq!(license.validate(), Err(LicenseError::IdentifierAndUrl));
+    }
+}
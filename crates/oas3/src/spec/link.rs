@@ -1,8 +1,11 @@
 use std::collections::BTreeMap;
 
+use http::Method;
 use serde::{Deserialize, Serialize};
 
-use super::{spec_extensions, Server};
+use super::{
+    resolve_component, spec_extensions, Error, FromRef, Operation, RefError, RefType, Server, Spec,
+};
 
 /// The Link object represents a possible design-time link for a response.
 ///
@@ -128,3 +131,116 @@ pub enum Link {
         extensions: BTreeMap<String, serde_json::Value>,
     },
 }
+
+impl Link {
+    /// Finds the operation targeted by this link in `spec`, along with its path and method.
+    ///
+    /// Links by `operationId` are matched against the operations of the spec's paths. Links by
+    /// `operationRef` must be local JSON pointers to an operation, like `#/paths/~1pets/get`, and
+    /// may be percent-encoded; other references return an error. Referenced path items are not followed.
+    ///
+    /// Returns `None` if no operation matches.
+    pub fn target_operation<'a>(
+        &self,
+        spec: &'a Spec,
+    ) -> Result<Option<(&'a str, Method, &'a Operation)>, Error> {
+        let mut operations = spec.paths.iter().flatten().flat_map(|(path, item)| {
+            item.methods()
+                .into_iter()
+                .map(move |(method, op)| (path.as_str(), method, op))
+        });
+
+        match self {
+            Link::Id { operation_id, .. } => Ok(operations
+                .find(|(_, _, op)| op.operation_id.as_deref() == Some(operation_id.as_str()))),
+
+            Link::Ref { operation_ref, .. } => {
+                let unresolvable = || Error::Ref(RefError::Unresolvable(operation_ref.clone()));
+
+                let (path, method) = operation_ref
+                    .strip_prefix("#/paths/")
+                    .and_then(|ptr| ptr.rsplit_once('/'))
+                    .ok_or_else(unresolvable)?;
+
+                // the pointer is a URI fragment, so undo percent-encoding before `~` escapes
+                let path = percent_encoding::percent_decode_str(path)
+                    .decode_utf8()
+                    .map_err(|_| unresolvable())?;
+                let path = crate::json_pointer::unescape(&path);
+                let method = Method::from_bytes(method.to_uppercase().as_bytes())
+                    .map_err(|_| unresolvable())?;
+
+                Ok(operations
+                    .find(|(op_path, op_method, _)| *op_path == path && *op_method == method))
+            }
+        }
+    }
+}
+
+impl FromRef for Link {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        resolve_component(spec, path, RefType::Link, |cs, name| cs.links.get(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_operation() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets/{id}:
+                get:
+                  operationId: getPet
+                  responses:
+                    '200':
+                      description: OK
+                      links:
+                        self:
+                          operationId: getPet
+                        byRef:
+                          operationRef: '#/paths/~1pets~1{id}/get'
+        "})
+        .unwrap();
+
+        let op = spec.operation_by_id("getPet").unwrap();
+        let links = op.responses(&spec)["200"].links_resolved(&spec).unwrap();
+
+        for name in ["self", "byRef"] {
+            let (path, method, target) = links[name].target_operation(&spec).unwrap().unwrap();
+            assert_eq!(path, "/pets/{id}");
+            assert_eq!(method, Method::GET);
+            assert_eq!(target.operation_id.as_deref(), Some("getPet"));
+        }
+
+        let link = serde_yml::from_str::<Link>("operationId: missing").unwrap();
+        assert!(link.target_operation(&spec).unwrap().is_none());
+
+        let link =
+            serde_yml::from_str::<Link>("operationRef: '#/paths/~1pets~1{id}/post'").unwrap();
+        assert!(link.target_operation(&spec).unwrap().is_none());
+
+        let link =
+            serde_yml::from_str::<Link>("operationRef: '#/paths/~1pets~1%7Bid%7D/get'").unwrap();
+        let (path, method, _) = link.target_operation(&spec).unwrap().unwrap();
+        assert_eq!(path, "/pets/{id}");
+        assert_eq!(method, Method::GET);
+
+        let link =
+            serde_yml::from_str::<Link>("operationRef: '#/paths/%7E1pets%7E1{id}/get'").unwrap();
+        let (path, _, _) = link.target_operation(&spec).unwrap().unwrap();
+        assert_eq!(path, "/pets/{id}");
+
+        let link = serde_yml::from_str::<Link>(
+            "operationRef: 'https://example.com/spec.yaml#/paths/~1pets/get'",
+        )
+        .unwrap();
+        link.target_operation(&spec).unwrap_err();
+    }
+}
@@ -0,0 +1,845 @@
+//! Opinionated checks for specs that are valid but likely incomplete.
+
+use std::collections::BTreeSet;
+
+use derive_more::derive::Display;
+use http::Method;
+
+use super::{
+    FromRef, MediaTypeError, MediaTypeLocation, ObjectOrReference, ObjectSchema, RefType,
+    SchemaError, Spec,
+};
+
+/// Error responses an operation must document to satisfy the response status coverage lint.
+///
+/// A `default` response satisfies any requirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseCoverage {
+    /// At least one client (4xx) or server (5xx) error response.
+    #[default]
+    AnyError,
+
+    /// At least one client (4xx) and one server (5xx) error response.
+    ClientAndServerErrors,
+}
+
+/// Configuration for [`Spec::lint`].
+///
+/// Each lint is disabled by setting its field to `None` or `false`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    /// Required error response coverage for each operation.
+    pub response_status_coverage: Option<ResponseCoverage>,
+
+    /// Check inline schemas for internal consistency using [`ObjectSchema::validate`].
+    ///
+    /// [`ObjectSchema::validate`]: super::ObjectSchema::validate
+    pub invalid_schemas: bool,
+
+    /// Report request and response media types that declare neither a schema nor examples.
+    pub media_types_without_schema: bool,
+
+    /// Check request and response media types using [`MediaType::validate`].
+    ///
+    /// [`MediaType::validate`]: super::MediaType::validate
+    pub invalid_media_types: bool,
+
+    /// Report request bodies on methods whose semantics do not define one.
+    pub unexpected_request_bodies: bool,
+
+    /// Report component names that cannot be referenced using
+    /// [`Spec::invalid_component_names`].
+    pub invalid_component_names: bool,
+
+    /// Report operation tags missing from the spec's top-level `tags` list.
+    pub undeclared_tags: bool,
+
+    /// Check discriminator mappings using [`Spec::validate_discriminators`].
+    pub discriminators: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            response_status_coverage: Some(ResponseCoverage::default()),
+            invalid_schemas: true,
+            media_types_without_schema: true,
+            invalid_media_types: true,
+            unexpected_request_bodies: true,
+            invalid_component_names: true,
+            undeclared_tags: true,
+            discriminators: true,
+        }
+    }
+}
+
+/// A problem reported by [`Spec::lint`].
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum LintFinding {
+    /// Operation does not document enough error responses.
+    #[display("{method} {path} does not document error responses")]
+    MissingErrorResponses {
+        /// HTTP method of the operation.
+        method: Method,
+
+        /// Path of the operation.
+        path: String,
+    },
+
+    /// Operation declares a request body on a method that does not define one.
+    #[display("{method} {path} declares a request body")]
+    UnexpectedRequestBody {
+        /// HTTP method of the operation.
+        method: Method,
+
+        /// Path of the operation.
+        path: String,
+    },
+
+    /// Operation uses a tag that is not declared at the top level.
+    #[display("{method} {path} uses undeclared tag {tag:?}")]
+    UndeclaredTag {
+        /// HTTP method of the operation.
+        method: Method,
+
+        /// Path of the operation.
+        path: String,
+
+        /// Name of the tag.
+        tag: String,
+    },
+
+    /// Component name does not match `^[a-zA-Z0-9._-]+$`.
+    #[display("{kind} component name {name:?} is not valid")]
+    InvalidComponentName {
+        /// Type of the component.
+        kind: RefType,
+
+        /// Name of the component.
+        name: String,
+    },
+
+    /// Schema is not internally consistent.
+    #[display("{pointer}: {error}")]
+    InvalidSchema {
+        /// JSON pointer to the schema.
+        pointer: String,
+
+        /// Schema error.
+        error: SchemaError,
+    },
+
+    /// Media type declares neither a schema nor examples.
+    #[display(
+        "{} {} content {} declares neither schema nor examples",
+        _0.method,
+        _0.path,
+        _0.content_type
+    )]
+    MediaTypeWithoutSchema(MediaTypeLocation),
+
+    /// Media type is not valid.
+    #[display(
+        "{} {} content {}: {}",
+        location.method,
+        location.path,
+        location.content_type,
+        error
+    )]
+    InvalidMediaType {
+        /// Location of the media type.
+        location: MediaTypeLocation,
+
+        /// Media type error.
+        error: MediaTypeError,
+    },
+
+    /// Discriminator mapping is invalid.
+    #[display("{}", _0)]
+    Discriminator(DiscriminatorFinding),
+}
+
+/// A problem with a discriminator mapping, reported by [`Spec::validate_discriminators`].
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum DiscriminatorFinding {
+    /// Mapping target does not resolve to a schema.
+    #[display("{pointer}: discriminator mapping {value:?} targets missing schema {target}")]
+    UnresolvableTarget {
+        /// JSON pointer to the schema containing the discriminator.
+        pointer: String,
+
+        /// Discriminator value.
+        value: String,
+
+        /// Mapping target.
+        target: String,
+    },
+
+    /// Mapping target is not a member of the schema's `oneOf` or `anyOf`.
+    #[display("{pointer}: discriminator mapping {value:?} targets {target}, which is not in oneOf or anyOf")]
+    TargetNotInComposition {
+        /// JSON pointer to the schema containing the discriminator.
+        pointer: String,
+
+        /// Discriminator value.
+        value: String,
+
+        /// Mapping target.
+        target: String,
+    },
+}
+
+impl Spec {
+    /// Runs the lints enabled in `config`, returning all findings.
+    pub fn lint(&self, config: &LintConfig) -> Vec<LintFinding> {
+        let mut findings = vec![];
+
+        if let Some(coverage) = config.response_status_coverage {
+            findings.extend(
+                self.response_status_coverage(coverage)
+                    .into_iter()
+                    .map(|(method, path)| LintFinding::MissingErrorResponses { method, path }),
+            );
+        }
+
+        if config.unexpected_request_bodies {
+            findings.extend(
+                self.unexpected_request_bodies()
+                    .into_iter()
+                    .map(|(method, path)| LintFinding::UnexpectedRequestBody { method, path }),
+            );
+        }
+
+        if config.undeclared_tags {
+            findings.extend(
+                self.undeclared_tags()
+                    .into_iter()
+                    .map(|(path, method, tag)| LintFinding::UndeclaredTag { method, path, tag }),
+            );
+        }
+
+        if config.invalid_component_names {
+            findings.extend(
+                self.invalid_component_names()
+                    .into_iter()
+                    .map(|(kind, name)| LintFinding::InvalidComponentName { kind, name }),
+            );
+        }
+
+        if config.invalid_schemas {
+            for (pointer, schema) in self.schemas() {
+                if let ObjectOrReference::Object(schema) = schema {
+                    if let Err(error) = schema.validate() {
+                        findings.push(LintFinding::InvalidSchema { pointer, error });
+                    }
+                }
+            }
+        }
+
+        if config.discriminators {
+            findings.extend(
+                self.validate_discriminators()
+                    .into_iter()
+                    .map(LintFinding::Discriminator),
+            );
+        }
+
+        if config.media_types_without_schema {
+            findings.extend(
+                self.media_types_without_schema()
+                    .into_iter()
+                    .map(LintFinding::MediaTypeWithoutSchema),
+            );
+        }
+
+        if config.invalid_media_types {
+            self.for_each_media_type(|location, media_type| {
+                if let Err(error) = media_type.validate() {
+                    findings.push(LintFinding::InvalidMediaType { location, error });
+                }
+            });
+        }
+
+        findings
+    }
+
+    /// Checks that every discriminator mapping target resolves to a schema and, if the schema
+    /// declares `oneOf` or `anyOf`, that the target is one of their members.
+    ///
+    /// Mapping targets may be references or component schema names.
+    pub fn validate_discriminators(&self) -> Vec<DiscriminatorFinding> {
+        let mut findings = vec![];
+
+        for (pointer, schema) in self.schemas() {
+            let ObjectOrReference::Object(schema) = schema else {
+                continue;
+            };
+
+            let Some(mapping) = schema
+                .discriminator
+                .as_ref()
+                .and_then(|disc| disc.mapping.as_ref())
+            else {
+                continue;
+            };
+
+            let members = schema
+                .one_of
+                .iter()
+                .chain(&schema.any_of)
+                .filter_map(|member| match member.as_object() {
+                    Some(ObjectOrReference::Ref { ref_path }) => Some(ref_path.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            let has_composition = !schema.one_of.is_empty() || !schema.any_of.is_empty();
+
+            for (value, target) in mapping {
+                let ref_path = if target.contains('#') {
+                    target.clone()
+                } else {
+                    format!("#/components/schemas/{target}")
+                };
+
+                let finding = if ObjectSchema::from_ref(self, &ref_path).is_err() {
+                    DiscriminatorFinding::UnresolvableTarget {
+                        pointer: pointer.clone(),
+                        value: value.clone(),
+                        target: target.clone(),
+                    }
+                } else if has_composition && !members.contains(&ref_path.as_str()) {
+                    DiscriminatorFinding::TargetNotInComposition {
+                        pointer: pointer.clone(),
+                        value: value.clone(),
+                        target: target.clone(),
+                    }
+                } else {
+                    continue;
+                };
+
+                findings.push(finding);
+            }
+        }
+
+        findings
+    }
+
+    /// Returns locations of request and response media types that declare neither a `schema` nor
+    /// `example`/`examples`.
+    pub fn media_types_without_schema(&self) -> Vec<MediaTypeLocation> {
+        let mut locations = vec![];
+
+        self.for_each_media_type(|location, media_type| {
+            if media_type.schema.is_none() && media_type.examples.is_none() {
+                locations.push(location);
+            }
+        });
+
+        locations
+    }
+
+    /// Returns the path, method, and tag name of each operation tag that is not declared in the
+    /// spec's top-level `tags` list.
+    ///
+    /// Undeclared tags are permitted by the specification but are often typos.
+    pub fn undeclared_tags(&self) -> Vec<(String, Method, String)> {
+        let declared = self
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<BTreeSet<_>>();
+
+        self.operations()
+            .flat_map(|(path, method, op)| {
+                op.tags
+                    .iter()
+                    .filter(|tag| !declared.contains(tag.as_str()))
+                    .map(move |tag| (path.clone(), method.clone(), tag.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the type and name of components whose names do not match `^[a-zA-Z0-9._-]+$`.
+    ///
+    /// Such components cannot be referenced reliably using `$ref`.
+    pub fn invalid_component_names(&self) -> Vec<(RefType, String)> {
+        let Some(components) = &self.components else {
+            return vec![];
+        };
+
+        let is_valid = |name: &str| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        };
+
+        let names = components
+            .schemas
+            .keys()
+            .map(|name| (RefType::Schema, name))
+            .chain(
+                components
+                    .responses
+                    .keys()
+                    .map(|name| (RefType::Response, name)),
+            )
+            .chain(
+                components
+                    .parameters
+                    .keys()
+                    .map(|name| (RefType::Parameter, name)),
+            )
+            .chain(
+                components
+                    .examples
+                    .keys()
+                    .map(|name| (RefType::Example, name)),
+            )
+            .chain(
+                components
+                    .request_bodies
+                    .keys()
+                    .map(|name| (RefType::RequestBody, name)),
+            )
+            .chain(
+                components
+                    .headers
+                    .keys()
+                    .map(|name| (RefType::Header, name)),
+            )
+            .chain(
+                components
+                    .security_schemes
+                    .keys()
+                    .map(|name| (RefType::SecurityScheme, name)),
+            )
+            .chain(components.links.keys().map(|name| (RefType::Link, name)))
+            .chain(
+                components
+                    .callbacks
+                    .keys()
+                    .map(|name| (RefType::Callback, name)),
+            )
+            .chain(
+                components
+                    .path_items
+                    .keys()
+                    .map(|name| (RefType::PathItem, name)),
+            );
+
+        names
+            .filter(|(_, name)| !is_valid(name))
+            .map(|(kind, name)| (kind, name.clone()))
+            .collect()
+    }
+
+    /// Returns `GET`, `HEAD`, and `DELETE` operations that declare a request body.
+    ///
+    /// HTTP does not define semantics for request bodies on these methods, so they are usually a
+    /// copy-paste mistake.
+    pub fn unexpected_request_bodies(&self) -> Vec<(Method, String)> {
+        self.operations()
+            .filter(|(_, method, op)| {
+                matches!(*method, Method::GET | Method::HEAD | Method::DELETE)
+                    && op.request_body.is_some()
+            })
+            .map(|(path, method, _)| (method, path))
+            .collect()
+    }
+
+    /// Returns operations that do not document the error responses required by `coverage`.
+    ///
+    /// Status codes and ranges (e.g. `4XX`) both count towards coverage, as does a `default`
+    /// response.
+    pub fn response_status_coverage(&self, coverage: ResponseCoverage) -> Vec<(Method, String)> {
+        self.operations()
+            .filter(|(_, _, op)| {
+                let statuses = op.responses.iter().flatten().map(|(status, _)| status);
+
+                let mut client_error = false;
+                let mut server_error = false;
+
+                for status in statuses {
+                    match status.chars().next() {
+                        Some('4') => client_error = true,
+                        Some('5') => server_error = true,
+                        _ if status == "default" => return false,
+                        _ => {}
+                    }
+                }
+
+                match coverage {
+                    ResponseCoverage::AnyError => !(client_error || server_error),
+                    ResponseCoverage::ClientAndServerErrors => !(client_error && server_error),
+                }
+            })
+            .map(|(path, method, _)| (method, path))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::MediaTypeDirection;
+
+    fn spec() -> Spec {
+        serde_yml::from_str(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  responses:
+                    '200':
+                      description: OK
+                post:
+                  responses:
+                    '201':
+                      description: Created
+                    4XX:
+                      description: Bad request
+                delete:
+                  responses:
+                    '204':
+                      description: Deleted
+                    default:
+                      description: Error
+        "})
+        .unwrap()
+    }
+
+    #[test]
+    fn response_status_coverage() {
+        let spec = spec();
+
+        assert_eq!(
+            spec.response_status_coverage(ResponseCoverage::AnyError),
+            [(Method::GET, "/pets".to_owned())],
+        );
+
+        assert_eq!(
+            spec.response_status_coverage(ResponseCoverage::ClientAndServerErrors),
+            [
+                (Method::GET, "/pets".to_owned()),
+                (Method::POST, "/pets".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn lint_config() {
+        let spec = spec();
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings,
+            [LintFinding::MissingErrorResponses {
+                method: Method::GET,
+                path: "/pets".to_owned(),
+            }],
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            "GET /pets does not document error responses",
+        );
+
+        let config = LintConfig {
+            response_status_coverage: None,
+            ..LintConfig::default()
+        };
+        assert!(spec.lint(&config).is_empty());
+    }
+
+    #[test]
+    fn unexpected_request_bodies() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  requestBody:
+                    content:
+                      application/json:
+                        schema:
+                          type: object
+                  responses:
+                    default:
+                      description: Error
+                post:
+                  requestBody:
+                    content:
+                      application/json:
+                        schema:
+                          type: object
+                  responses:
+                    default:
+                      description: Error
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.unexpected_request_bodies(),
+            [(Method::GET, "/pets".to_owned())],
+        );
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings,
+            [LintFinding::UnexpectedRequestBody {
+                method: Method::GET,
+                path: "/pets".to_owned(),
+            }],
+        );
+        assert_eq!(findings[0].to_string(), "GET /pets declares a request body");
+    }
+
+    #[test]
+    fn undeclared_tags() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            tags:
+              - name: pets
+            paths:
+              /pets:
+                get:
+                  tags: [pets, stores]
+                  responses:
+                    default:
+                      description: Error
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.undeclared_tags(),
+            [("/pets".to_owned(), Method::GET, "stores".to_owned())],
+        );
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings[0].to_string(),
+            r#"GET /pets uses undeclared tag "stores""#,
+        );
+    }
+
+    #[test]
+    fn invalid_component_names() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                my schema:
+                  type: object
+                Pet.v1_final-2:
+                  type: object
+              parameters:
+                page/size:
+                  name: size
+                  in: query
+                  schema:
+                    type: integer
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.invalid_component_names(),
+            [
+                (RefType::Schema, "my schema".to_owned()),
+                (RefType::Parameter, "page/size".to_owned()),
+            ],
+        );
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings[0].to_string(),
+            r#"Schema component name "my schema" is not valid"#,
+        );
+    }
+
+    #[test]
+    fn media_types_without_schema() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json: {}
+                        text/plain:
+                          example: ok
+                    default:
+                      description: Error
+                      content:
+                        application/json:
+                          schema:
+                            type: object
+        "})
+        .unwrap();
+
+        let locations = spec.media_types_without_schema();
+        assert_eq!(
+            locations,
+            [MediaTypeLocation {
+                path: "/pets".to_owned(),
+                method: Method::GET,
+                direction: MediaTypeDirection::Response {
+                    status: "200".to_owned(),
+                },
+                content_type: "application/json".to_owned(),
+            }],
+        );
+
+        let findings = spec.lint(&LintConfig {
+            response_status_coverage: None,
+            ..LintConfig::default()
+        });
+        assert_eq!(
+            findings[0].to_string(),
+            "GET /pets content application/json declares neither schema nor examples",
+        );
+    }
+
+    #[test]
+    fn lint_example_and_examples() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                post:
+                  requestBody:
+                    content:
+                      application/json:
+                        schema:
+                          type: object
+                        example: { name: Rex }
+                        examples:
+                          Rex:
+                            value: { name: Rex }
+                  responses:
+                    default:
+                      description: Error
+        "})
+        .unwrap();
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings,
+            [LintFinding::InvalidMediaType {
+                location: MediaTypeLocation {
+                    path: "/pets".to_owned(),
+                    method: Method::POST,
+                    direction: MediaTypeDirection::Request,
+                    content_type: "application/json".to_owned(),
+                },
+                error: MediaTypeError::ExampleAndExamples,
+            }],
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            "POST /pets content application/json: Media type `example` and `examples` are mutually exclusive",
+        );
+    }
+
+    #[test]
+    fn validate_discriminators() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  oneOf:
+                    - $ref: '#/components/schemas/Cat'
+                    - $ref: '#/components/schemas/Dog'
+                  discriminator:
+                    propertyName: petType
+                    mapping:
+                      cat: '#/components/schemas/Cat'
+                      dog: Dog
+                      fish: '#/components/schemas/Fish'
+                      lizard: Lizard
+                Cat:
+                  type: object
+                Dog:
+                  type: object
+                Lizard:
+                  type: object
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.validate_discriminators(),
+            [
+                DiscriminatorFinding::UnresolvableTarget {
+                    pointer: "#/components/schemas/Pet".to_owned(),
+                    value: "fish".to_owned(),
+                    target: "#/components/schemas/Fish".to_owned(),
+                },
+                DiscriminatorFinding::TargetNotInComposition {
+                    pointer: "#/components/schemas/Pet".to_owned(),
+                    value: "lizard".to_owned(),
+                    target: "Lizard".to_owned(),
+                },
+            ],
+        );
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings[0].to_string(),
+            r#"#/components/schemas/Pet: discriminator mapping "fish" targets missing schema #/components/schemas/Fish"#,
+        );
+    }
+
+    #[test]
+    fn lint_invalid_enum() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Status:
+                  type: integer
+                  enum: [a, b]
+        "})
+        .unwrap();
+
+        let findings = spec.lint(&LintConfig::default());
+        assert_eq!(
+            findings,
+            [LintFinding::InvalidSchema {
+                pointer: "#/components/schemas/Status".to_owned(),
+                error: SchemaError::InvalidEnumValue(serde_json::json!("a")),
+            }],
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            r#"#/components/schemas/Status: Enum value "a" does not conform to schema"#,
+        );
+    }
+}
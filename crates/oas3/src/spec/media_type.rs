@@ -1,8 +1,19 @@
 use std::collections::BTreeMap;
 
+use derive_more::derive::{Display, Error};
 use serde::{Deserialize, Serialize};
 
-use super::{Encoding, Error, Example, MediaTypeExamples, ObjectOrReference, ObjectSchema, Spec};
+use super::{
+    Encoding, Error as SpecError, Example, MediaTypeExamples, ObjectOrReference, ObjectSchema, Spec,
+};
+
+/// Media type validation errors.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum MediaTypeError {
+    /// Both `example` and `examples` are present.
+    #[display("Media type `example` and `examples` are mutually exclusive")]
+    ExampleAndExamples,
+}
 
 /// Each Media Type Object provides schema and examples for the media type identified by its key.
 ///
@@ -29,13 +40,23 @@ pub struct MediaType {
 }
 
 impl MediaType {
+    /// Validates that this media type does not specify both `example` and `examples`.
+    pub fn validate(&self) -> Result<(), MediaTypeError> {
+        match self.examples {
+            Some(MediaTypeExamples::ExampleAndExamples { .. }) => {
+                Err(MediaTypeError::ExampleAndExamples)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Resolves and returns the JSON schema definition for this media type.
-    pub fn schema(&self, spec: &Spec) -> Result<ObjectSchema, Error> {
+    pub fn schema(&self, spec: &Spec) -> Result<ObjectSchema, SpecError> {
         self.schema
             .as_ref()
             .unwrap()
             .resolve(spec)
-            .map_err(Error::Ref)
+            .map_err(SpecError::Ref)
     }
 
     /// Resolves and returns the provided examples for this media type.
@@ -47,4 +68,196 @@ impl MediaType {
             .map(|examples| examples.resolve_all(spec))
             .unwrap_or_default()
     }
+
+    /// Resolves and returns the most appropriate example for this media type.
+    ///
+    /// Candidates are considered in the following order:
+    /// 1. the `example` field;
+    /// 1. the first (by name) entry of the `examples` field that resolves and has a `value`;
+    /// 1. the schema's `default`;
+    /// 1. the schema's `example`;
+    /// 1. the first of the schema's `examples`.
+    ///
+    /// The `example` field is preferred even when `examples` is also (invalidly) present.
+    ///
+    /// Returns `None` if no candidate is present or the schema fails to resolve.
+    pub fn first_example(&self, spec: &Spec) -> Option<serde_json::Value> {
+        if let Some(
+            MediaTypeExamples::Example { example }
+            | MediaTypeExamples::ExampleAndExamples { example, .. },
+        ) = &self.examples
+        {
+            return Some(example.clone());
+        }
+
+        if let Some(example) = self
+            .examples(spec)
+            .into_values()
+            .find_map(|example| example.value)
+        {
+            return Some(example);
+        }
+
+        let schema = self.schema.as_ref()?.resolve(spec).ok()?;
+
+        schema
+            .default
+            .or(schema.example)
+            .or_else(|| schema.examples.into_iter().next())
+    }
+}
+
+/// Finds the entry of `content` that best matches `media_type`.
+///
+/// Media type parameters (e.g. `charset`) and case are ignored. Exact matches are preferred over
+/// `type/*` ranges, which are preferred over `*/*`.
+pub fn find_media_type<'a>(
+    content: &'a BTreeMap<String, MediaType>,
+    media_type: &str,
+) -> Option<&'a MediaType> {
+    let essence = |media_type: &str| {
+        media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    };
+
+    let target = essence(media_type);
+    let type_range = format!("{}/*", target.split('/').next().unwrap_or_default());
+
+    content
+        .iter()
+        .filter_map(|(key, media_type)| {
+            let key = essence(key);
+
+            let rank = if key == target {
+                0
+            } else if key == type_range {
+                1
+            } else if key == "*/*" {
+                2
+            } else {
+                return None;
+            };
+
+            Some((rank, media_type))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, media_type)| media_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn spec() -> Spec {
+        serde_yml::from_str(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              examples:
+                Whiskers:
+                  value: { name: Whiskers }
+              schemas:
+                Pet:
+                  type: object
+                  example: { name: Rex }
+        "})
+        .unwrap()
+    }
+
+    fn media_type(yaml: &str) -> MediaType {
+        serde_yml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn first_example_precedence() {
+        let spec = spec();
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              $ref: '#/components/schemas/Pet'
+            example: { name: Tom }
+        "});
+        assert_eq!(mt.first_example(&spec), Some(json!({ "name": "Tom" })));
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              $ref: '#/components/schemas/Pet'
+            example: { name: Tom }
+            examples:
+              whiskers:
+                $ref: '#/components/examples/Whiskers'
+        "});
+        assert_eq!(mt.first_example(&spec), Some(json!({ "name": "Tom" })));
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              $ref: '#/components/schemas/Pet'
+            examples:
+              whiskers:
+                $ref: '#/components/examples/Whiskers'
+              missing:
+                $ref: '#/components/examples/Missing'
+        "});
+        assert_eq!(mt.first_example(&spec), Some(json!({ "name": "Whiskers" })));
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              type: string
+              default: abc
+              example: xyz
+        "});
+        assert_eq!(mt.first_example(&spec), Some(json!("abc")));
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              $ref: '#/components/schemas/Pet'
+        "});
+        assert_eq!(mt.first_example(&spec), Some(json!({ "name": "Rex" })));
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              type: integer
+              examples: [1, 2]
+        "});
+        assert_eq!(mt.first_example(&spec), Some(json!(1)));
+
+        let mt = media_type(indoc::indoc! {"
+            schema:
+              type: integer
+        "});
+        assert_eq!(mt.first_example(&spec), None);
+        assert_eq!(MediaType::default().first_example(&spec), None);
+    }
+
+    #[test]
+    fn find_media_type_ranking() {
+        let content = serde_yml::from_str::<BTreeMap<String, MediaType>>(indoc::indoc! {"
+            application/json:
+              schema: { type: object }
+            application/*:
+              schema: { type: string }
+            '*/*':
+              schema: { type: boolean }
+        "})
+        .unwrap();
+
+        let find = |media_type| find_media_type(&content, media_type);
+
+        assert_eq!(
+            find("Application/JSON; charset=utf-8"),
+            content.get("application/json"),
+        );
+        assert_eq!(find("application/xml"), content.get("application/*"));
+        assert_eq!(find("application/*"), content.get("application/*"));
+        assert_eq!(find("text/plain"), content.get("*/*"));
+        assert!(find_media_type(&BTreeMap::new(), "application/json").is_none());
+    }
 }
@@ -9,6 +9,20 @@ use super::{Example, ObjectOrReference, Spec};
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum MediaTypeExamples {
+    /// Both the `example` and `examples` fields are present.
+    ///
+    /// The fields are mutually exclusive so this is invalid, but it is retained in order for
+    /// [`MediaType::validate()`] to report it. When resolving, the `example` field is ignored.
+    ///
+    /// [`MediaType::validate()`]: super::MediaType::validate
+    ExampleAndExamples {
+        /// Example of the media type.
+        example: serde_json::Value,
+
+        /// Examples of the media type.
+        examples: BTreeMap<String, ObjectOrReference<Example>>,
+    },
+
     /// Example of the media type.
     ///
     /// The example object SHOULD be in the correct format as specified by the media type. The
@@ -44,7 +58,9 @@ impl MediaTypeExamples {
     /// Returns true if no examples are provided.
     pub fn is_empty(&self) -> bool {
         match self {
-            MediaTypeExamples::Example { .. } => false,
+            MediaTypeExamples::Example { .. } | MediaTypeExamples::ExampleAndExamples { .. } => {
+                false
+            }
             MediaTypeExamples::Examples { examples } => examples.is_empty(),
         }
     }
@@ -68,7 +84,7 @@ impl MediaTypeExamples {
                 map
             }
 
-            Self::Examples { examples } => examples
+            Self::Examples { examples } | Self::ExampleAndExamples { examples, .. } => examples
                 .iter()
                 .filter_map(|(name, oor)| {
                     oor.resolve(spec)
@@ -116,6 +132,18 @@ mod tests {
                 example: json!(null)
             },
         );
+
+        assert_eq!(
+            serde_yml::from_str::<MediaTypeExamples>(indoc::indoc! {"
+                    example: ferris
+                    examples: {}
+                "})
+            .unwrap(),
+            MediaTypeExamples::ExampleAndExamples {
+                example: json!("ferris"),
+                examples: BTreeMap::new(),
+            },
+        );
     }
 
     #[test]
@@ -9,46 +9,65 @@ use http::Method;
 use log::debug;
 use serde::{Deserialize, Serialize};
 
+mod builder;
 mod components;
 mod contact;
 mod encoding;
 
 mod discriminator;
+mod downgrade;
 mod error;
 mod example;
+mod extensible;
 mod external_doc;
 mod flows;
 mod header;
 mod info;
+mod json_schema;
 mod license;
 mod link;
+mod lint;
 mod media_type;
 mod media_type_examples;
 mod operation;
 mod parameter;
 mod path_item;
+mod prune;
 mod r#ref;
+mod references;
 mod request_body;
+mod resolved_operation;
 mod response;
+mod response_key;
 mod schema;
+mod schema_scope;
+mod security_requirement;
 mod security_scheme;
 mod server;
 mod spec_extensions;
+#[cfg(feature = "lenient")]
+mod status_keys;
 mod tag;
+mod walk;
+mod xml;
 
 pub use self::{
+    builder::*,
     components::*,
     contact::*,
     discriminator::*,
+    downgrade::*,
     encoding::*,
     error::Error,
     example::*,
+    extensible::*,
     external_doc::*,
     flows::*,
     header::*,
     info::*,
     license::*,
     link::*,
+    lint::*,
     media_type::*,
     media_type_examples::*,
     operation::*,
@@ -56,18 +75,38 @@ pub use self::{
     path_item::*,
     r#ref::*,
     request_body::*,
+    resolved_operation::*,
     response::*,
+    response_key::*,
     schema::{
-        BooleanSchema, Error as SchemaError, ObjectSchema, Schema, Type as SchemaType,
+        BooleanSchema, Composition, Error as SchemaError, ObjectSchema, Schema, Type as SchemaType,
         TypeSet as SchemaTypeSet,
     },
+    schema_scope::SchemaResolver,
+    security_requirement::*,
     security_scheme::*,
     server::*,
     tag::*,
+    walk::*,
+    xml::*,
 };
 
 const OPENAPI_SUPPORTED_VERSION_RANGE: &str = "~3.1";
 
+/// Map type used for [`Spec::paths`], [`Components::schemas`], and [`ObjectSchema::properties`].
+///
+/// This is a [`BTreeMap`], sorted by key, unless the `preserve-order` feature is enabled, in which
+/// case it is an [`IndexMap`](indexmap::IndexMap) that retains document order.
+#[cfg(not(feature = "preserve-order"))]
+pub type OrderedMap<K, V> = BTreeMap<K, V>;
+
+/// Map type used for [`Spec::paths`], [`Components::schemas`], and [`ObjectSchema::properties`].
+///
+/// This is a [`BTreeMap`], sorted by key, unless the `preserve-order` feature is enabled, in which
+/// case it is an [`IndexMap`](indexmap::IndexMap) that retains document order.
+#[cfg(feature = "preserve-order")]
+pub type OrderedMap<K, V> = indexmap::IndexMap<K, V>;
+
 /// A complete OpenAPI specification.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Spec {
@@ -101,20 +140,20 @@ pub struct Spec {
     /// in order to construct the full URL. The Paths MAY be empty, due to
     /// [ACL constraints](https://spec.openapis.org/oas/v3.1.0#securityFiltering).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub paths: Option<BTreeMap<String, PathItem>>,
+    pub paths: Option<OrderedMap<String, PathItem>>,
 
     /// An element to hold various schemas for the specification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
 
-    // FIXME: Implement
-    // /// A declaration of which security mechanisms can be used across the API.
-    // /// The list of  values includes alternative security requirement objects that can be used.
-    // /// Only one of the security requirement objects need to be satisfied to authorize a request.
-    // /// Individual operations can override this definition.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub security: Option<SecurityRequirement>,
-    //
+    /// A declaration of which security mechanisms can be used across the API.
+    ///
+    /// The list of values includes alternative security requirement objects that can be used.
+    /// Only one of the security requirement objects need to be satisfied to authorize a request.
+    /// Individual operations can override this definition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+
     /// A list of tags used by the specification with additional metadata.
     ///The order of the tags can be used to reflect on their order by the parsing tools.
     /// Not all tags that are used by the
@@ -151,10 +190,16 @@ pub struct Spec {
 }
 
 impl Spec {
+    /// Parses spec version field, without checking that it is supported.
+    ///
+    /// Use [`validate_version`](Self::validate_version) to also check the version is supported.
+    pub fn openapi_version(&self) -> Result<semver::Version, semver::Error> {
+        semver::Version::parse(&self.openapi)
+    }
+
     /// Validates spec version field.
     pub fn validate_version(&self) -> Result<semver::Version, Error> {
-        let spec_version = &self.openapi;
-        let sem_ver = semver::Version::parse(spec_version)?;
+        let sem_ver = self.openapi_version()?;
         let required_version = semver::VersionReq::parse(OPENAPI_SUPPORTED_VERSION_RANGE).unwrap();
 
         if required_version.matches(&sem_ver) {
@@ -164,17 +209,118 @@ impl Spec {
         }
     }
 
+    /// Validates document-level invariants, collecting all violations.
+    ///
+    /// The following are checked:
+    /// - at least one of `paths`, `components`, or `webhooks` is present;
+    /// - each declared tag name is unique;
+    /// - each operation ID is unique across all path and webhook operations.
+    ///
+    /// Note that an empty `webhooks` map is indistinguishable from an absent one.
+    pub fn validate(&self) -> Result<(), Vec<Error>> {
+        let mut errors = vec![];
+
+        if self.paths.is_none() && self.components.is_none() && self.webhooks.is_empty() {
+            errors.push(Error::NoContainers);
+        }
+
+        let mut tag_names = BTreeMap::<&str, usize>::new();
+
+        for tag in &self.tags {
+            *tag_names.entry(&tag.name).or_default() += 1;
+        }
+
+        errors.extend(
+            tag_names
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(name, _)| Error::DuplicateTagName(name.to_owned())),
+        );
+
+        let mut operation_ids = BTreeMap::<&str, usize>::new();
+
+        for (_, _, op) in self.operations().chain(self.webhook_operations()) {
+            if let Some(id) = op.operation_id.as_deref() {
+                *operation_ids.entry(id).or_default() += 1;
+            }
+        }
+
+        errors.extend(
+            operation_ids
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(id, _)| Error::DuplicateOperationId(id.to_owned())),
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Returns a reference to the operation with given `operation_id`, or `None` if not found.
     pub fn operation_by_id(&self, operation_id: &str) -> Option<&Operation> {
         self.operations()
             .find(|(_, _, op)| {
                 op.operation_id
                     .as_deref()
-                    .map_or(false, |id| id == operation_id)
+                    .is_some_and(|id| id == operation_id)
             })
             .map(|(_, _, op)| op)
     }
 
+    /// Returns each operation ID that is shared by more than one operation, along with the method
+    /// and path of every operation using it.
+    ///
+    /// Results are ordered by operation ID.
+    pub fn find_duplicate_operation_ids(&self) -> Vec<(String, Vec<(Method, String)>)> {
+        let mut operations = BTreeMap::<String, Vec<(Method, String)>>::new();
+
+        for (path, method, op) in self.operations() {
+            if let Some(id) = &op.operation_id {
+                operations
+                    .entry(id.clone())
+                    .or_default()
+                    .push((method, path));
+            }
+        }
+
+        operations
+            .into_iter()
+            .filter(|(_, ops)| ops.len() > 1)
+            .collect()
+    }
+
+    /// Returns the security requirements that apply to the operation at `method` and `path`.
+    ///
+    /// An operation-level `security` declaration overrides the top-level one; an empty list means
+    /// no security is required. Returns `None` if the operation does not exist.
+    pub fn effective_security(
+        &self,
+        method: &http::Method,
+        path: &str,
+    ) -> Option<&[SecurityRequirement]> {
+        let op = self.operation(method, path)?;
+        Some(op.security.as_deref().unwrap_or(&self.security))
+    }
+
+    /// Finds and resolves the security scheme named `name` in this spec's components.
+    ///
+    /// Returns `None` if no such scheme is declared.
+    pub fn security_scheme(&self, name: &str) -> Result<Option<SecurityScheme>, RefError> {
+        self.components
+            .as_ref()
+            .and_then(|cs| cs.security_schemes.get(name))
+            .map(|oor| oor.resolve(self))
+            .transpose()
+    }
+
+    /// Returns the declared tag with given `name`, or `None` if not found.
+    pub fn tag(&self, name: &str) -> Option<&Tag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
     /// Returns a reference to the operation with given `method` and `path`, or `None` if not found.
     pub fn operation(&self, method: &http::Method, path: &str) -> Option<&Operation> {
         let resource = self.paths.as_ref()?.get(path)?;
@@ -222,6 +368,133 @@ impl Spec {
         ops.into_iter()
     }
 
+    /// Returns an iterator over all the webhook operations defined in this spec.
+    ///
+    /// Items are keyed by webhook name rather than path.
+    pub fn webhook_operations(&self) -> impl Iterator<Item = (String, Method, &Operation)> {
+        self.webhooks.iter().flat_map(|(name, item)| {
+            item.methods()
+                .into_iter()
+                .map(move |(method, op)| (name.to_owned(), method, op))
+        })
+    }
+
+    /// Returns all operations marked as deprecated.
+    pub fn deprecated_operations(&self) -> Vec<(String, Method, &Operation)> {
+        self.operations()
+            .filter(|(_, _, op)| op.deprecated == Some(true))
+            .collect()
+    }
+
+    /// Returns the method and path of each operation, keyed by the tags it bears.
+    ///
+    /// Operations with multiple tags appear under each of them. Untagged operations are omitted.
+    pub fn operations_by_all_tags(&self) -> BTreeMap<String, Vec<(Method, String)>> {
+        let mut index = BTreeMap::<String, Vec<(Method, String)>>::new();
+
+        for (path, method, op) in self.operations() {
+            for tag in &op.tags {
+                index
+                    .entry(tag.clone())
+                    .or_default()
+                    .push((method.clone(), path.clone()));
+            }
+        }
+
+        index
+    }
+
+    /// Returns the names of deprecated parameters of each operation, keyed by path and method.
+    ///
+    /// Path item parameters are included, as overridden by the operation's parameters (see
+    /// [`Operation::merged_parameters()`]). Referenced parameters are resolved; those that fail to
+    /// resolve are skipped.
+    pub fn deprecated_parameters(&self) -> Vec<(String, Method, String)> {
+        let resolve_all = |params: &[ObjectOrReference<Parameter>]| {
+            params
+                .iter()
+                .filter_map(|param| param.resolve(self).ok())
+                .collect::<Vec<_>>()
+        };
+
+        let mut deprecated = vec![];
+
+        for (path, item) in self.paths.iter().flatten() {
+            for (method, op) in item.methods() {
+                let params = operation::merge_parameters(
+                    resolve_all(&item.parameters),
+                    resolve_all(&op.parameters),
+                );
+
+                deprecated.extend(
+                    params
+                        .into_iter()
+                        .filter(|param| param.deprecated == Some(true))
+                        .map(|param| (path.clone(), method.clone(), param.name)),
+                );
+            }
+        }
+
+        deprecated
+    }
+
+    /// Returns the JSON pointers of all schemas marked as deprecated.
+    ///
+    /// See [`schemas()`](Self::schemas) for which schemas are considered.
+    pub fn deprecated_schemas(&self) -> Vec<String> {
+        self.schemas()
+            .filter(|(_, schema)| {
+                matches!(schema, ObjectOrReference::Object(schema) if schema.deprecated == Some(true))
+            })
+            .map(|(pointer, _)| pointer)
+            .collect()
+    }
+
+    /// Removes operations for which `predicate` returns false.
+    ///
+    /// Path items left without operations are removed, unless they had none to begin with.
+    pub fn filter_operations(&mut self, predicate: impl Fn(&Operation) -> bool) {
+        let Some(paths) = self.paths.as_mut() else {
+            return;
+        };
+
+        paths.retain(|_, item| {
+            let mut removed = false;
+            let mut remaining = false;
+
+            for op in [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.options,
+                &mut item.head,
+                &mut item.patch,
+                &mut item.trace,
+            ] {
+                match op {
+                    Some(inner) if !predicate(inner) => {
+                        *op = None;
+                        removed = true;
+                    }
+                    Some(_) => remaining = true,
+                    None => {}
+                }
+            }
+
+            remaining || !removed
+        });
+    }
+
+    /// Removes operations marked with the `x-internal: true` extension.
+    ///
+    /// See [`filter_operations()`](Self::filter_operations).
+    pub fn strip_internal(&mut self) {
+        self.filter_operations(|op| {
+            op.extensions.get("internal") != Some(&serde_json::Value::Bool(true))
+        });
+    }
+
     /// Returns a reference to the primary (first) server definition.
     pub fn primary_server(&self) -> Option<&Server> {
         self.servers.first()
@@ -253,6 +526,392 @@ mod tests {
         assert_eq!(spec.extensions.get("bar").unwrap(), true);
     }
 
+    #[test]
+    fn validate_ok() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            tags:
+              - name: pets
+              - name: stores
+            paths:
+              /pets:
+                get:
+                  operationId: listPets
+                post:
+                  operationId: createPet
+        "})
+        .unwrap();
+
+        spec.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_collects_all_errors() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            tags:
+              - name: pets
+              - name: pets
+        "})
+        .unwrap();
+
+        let errors = spec.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], Error::NoContainers));
+        assert!(matches!(&errors[1], Error::DuplicateTagName(name) if name == "pets"));
+
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  operationId: getPets
+              /pets/{id}:
+                get:
+                  operationId: getPets
+            webhooks:
+              newPet:
+                post:
+                  operationId: getPets
+        "})
+        .unwrap();
+
+        let errors = spec.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], Error::DuplicateOperationId(id) if id == "getPets"));
+    }
+
+    #[test]
+    fn webhook_operations() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  operationId: getPets
+            webhooks:
+              newPet:
+                post:
+                  operationId: newPetWebhook
+        "})
+        .unwrap();
+
+        let ops = spec.webhook_operations().collect::<Vec<_>>();
+        assert_eq!(ops.len(), 1);
+
+        let (name, method, op) = &ops[0];
+        assert_eq!(name, "newPet");
+        assert_eq!(method, Method::POST);
+        assert_eq!(op.operation_id.as_deref(), Some("newPetWebhook"));
+    }
+
+    #[test]
+    fn openapi_version() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.0.3
+            info:
+              title: test
+              version: v1
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.openapi_version().unwrap(),
+            semver::Version::new(3, 0, 3),
+        );
+        assert!(matches!(
+            spec.validate_version(),
+            Err(Error::UnsupportedSpecFileVersion(version)) if version.minor == 0,
+        ));
+
+        let spec = Spec {
+            openapi: "3.1".to_owned(),
+            ..spec
+        };
+        spec.openapi_version().unwrap_err();
+    }
+
+    #[test]
+    fn tag_lookup() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            tags:
+              - name: pets
+                description: Pet operations.
+              - name: admin
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.tag("pets").unwrap().description.as_deref(),
+            Some("Pet operations."),
+        );
+        assert!(spec.tag("stores").is_none());
+    }
+
+    #[test]
+    fn operations_by_all_tags() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  tags: [pets]
+                post:
+                  tags: [pets, admin]
+              /health:
+                get: {}
+        "})
+        .unwrap();
+
+        let index = spec.operations_by_all_tags();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index["admin"], [(Method::POST, "/pets".to_owned())]);
+        assert_eq!(
+            index["pets"],
+            [
+                (Method::GET, "/pets".to_owned()),
+                (Method::POST, "/pets".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn find_duplicate_operation_ids() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  operationId: getPets
+                post:
+                  operationId: createPet
+              /pets/{id}:
+                get:
+                  operationId: getPets
+                delete:
+                  operationId: deletePet
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.find_duplicate_operation_ids(),
+            [(
+                "getPets".to_owned(),
+                vec![
+                    (Method::GET, "/pets".to_owned()),
+                    (Method::GET, "/pets/{id}".to_owned()),
+                ],
+            )],
+        );
+    }
+
+    #[test]
+    fn deprecations() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                parameters:
+                  - name: tenant
+                    in: header
+                    deprecated: true
+                  - name: region
+                    in: query
+                    deprecated: true
+                get:
+                  deprecated: true
+                  parameters:
+                    - $ref: '#/components/parameters/Limit'
+                    - $ref: '#/components/parameters/Missing'
+                    - name: offset
+                      in: query
+                      schema:
+                        type: integer
+                    - name: region
+                      in: query
+                post:
+                  deprecated: false
+            components:
+              parameters:
+                Limit:
+                  name: limit
+                  in: query
+                  deprecated: true
+                  schema:
+                    type: integer
+              schemas:
+                OldPet:
+                  type: object
+                  deprecated: true
+        "})
+        .unwrap();
+
+        let ops = spec.deprecated_operations();
+        assert_eq!(ops.len(), 1);
+        assert_eq!((ops[0].0.as_str(), &ops[0].1), ("/pets", &Method::GET));
+
+        // operation parameters override path item ones; unresolvable ones are skipped
+        assert_eq!(
+            spec.deprecated_parameters(),
+            [
+                ("/pets".to_owned(), Method::GET, "tenant".to_owned()),
+                ("/pets".to_owned(), Method::GET, "limit".to_owned()),
+                ("/pets".to_owned(), Method::POST, "tenant".to_owned()),
+                ("/pets".to_owned(), Method::POST, "region".to_owned()),
+            ],
+        );
+
+        assert_eq!(spec.deprecated_schemas(), ["#/components/schemas/OldPet"]);
+    }
+
+    #[test]
+    fn strip_internal() {
+        let mut spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get: {}
+                post:
+                  x-internal: true
+              /admin:
+                delete:
+                  x-internal: true
+              /shared:
+                $ref: '#/components/pathItems/Shared'
+        "})
+        .unwrap();
+
+        spec.strip_internal();
+
+        let paths = spec.paths.as_ref().unwrap();
+        assert_eq!(paths.keys().collect::<Vec<_>>(), ["/pets", "/shared"]);
+        assert!(paths["/pets"].get.is_some());
+        assert!(paths["/pets"].post.is_none());
+    }
+
+    #[test]
+    fn effective_security() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            security:
+              - apiKey: []
+            paths:
+              /pets:
+                get: {}
+                post:
+                  security:
+                    - oauth: [write:pets]
+              /health:
+                get:
+                  security: []
+        "})
+        .unwrap();
+
+        let api_key = SecurityRequirement(BTreeMap::from([("apiKey".to_owned(), vec![])]));
+        let oauth = SecurityRequirement(BTreeMap::from([(
+            "oauth".to_owned(),
+            vec!["write:pets".to_owned()],
+        )]));
+
+        assert_eq!(
+            spec.effective_security(&Method::GET, "/pets").unwrap(),
+            [api_key],
+        );
+        assert_eq!(
+            spec.effective_security(&Method::POST, "/pets").unwrap(),
+            [oauth],
+        );
+        assert!(spec
+            .effective_security(&Method::GET, "/health")
+            .unwrap()
+            .is_empty());
+        assert!(spec.effective_security(&Method::PUT, "/pets").is_none());
+    }
+
+    #[test]
+    fn security_webhooks_and_extensions_parsed() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            security:
+              - bearer: []
+            webhooks:
+              newPet:
+                post:
+                  operationId: newPetHook
+            x-internal: true
+        "})
+        .unwrap();
+
+        assert_eq!(spec.security.len(), 1);
+        assert!(spec.security[0].0.contains_key("bearer"));
+        assert!(spec.webhooks["newPet"].post.is_some());
+        assert_eq!(spec.extensions["internal"], serde_json::Value::Bool(true));
+    }
+
+    #[cfg(feature = "preserve-order")]
+    #[test]
+    fn preserve_order_round_trip() {
+        let spec = indoc::indoc! {"
+            openapi: '3.1.0'
+            info:
+              title: test
+              version: v1
+            paths:
+              /zebras: {}
+              /apes: {}
+            components:
+              schemas:
+                Zebra:
+                  properties:
+                    stripes:
+                      type: integer
+                    name:
+                      type: string
+                Ape: {}
+        "};
+
+        let parsed_spec = serde_yml::from_str::<Spec>(spec).unwrap();
+        let paths = parsed_spec.paths.as_ref().unwrap();
+        assert_eq!(paths.keys().collect::<Vec<_>>(), ["/zebras", "/apes"]);
+
+        let round_trip_spec = serde_yml::to_string(&parsed_spec).unwrap();
+        assert_eq!(spec, round_trip_spec);
+    }
+
     #[test]
     fn spec_extensions_serialize() {
         let spec = indoc::indoc! {"
@@ -4,7 +4,8 @@ use log::error;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Callback, Error, ExternalDoc, ObjectOrReference, Parameter, RequestBody, Response, Server, Spec,
+    Callback, Error, ExternalDoc, ObjectOrReference, Parameter, PathItem, RefError, RequestBody,
+    Response, ResponseKey, SecurityRequirement, Server, Spec,
 };
 use crate::spec::spec_extensions;
 
@@ -80,7 +81,14 @@ pub struct Operation {
     /// The `Responses Object` MUST contain at least one response code, and it SHOULD be the
     /// response for a successful operation call.
     ///
+    /// With the `lenient` crate feature enabled, integer status code keys are also accepted from
+    /// deserializers that do not coerce them to strings and are normalized to strings.
+    ///
     /// See <https://spec.openapis.org/oas/v3.1.0#responses-object>.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "super::status_keys::deserialize")
+    )]
     pub responses: Option<BTreeMap<String, ObjectOrReference<Response>>>,
 
     /// A map of possible out-of band callbacks related to the parent operation.
@@ -92,7 +100,7 @@ pub struct Operation {
     ///
     /// [Callback Object]: https://spec.openapis.org/oas/v3.1.0#callback-object
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub callbacks: BTreeMap<String, Callback>,
+    pub callbacks: BTreeMap<String, ObjectOrReference<Callback>>,
 
     /// Declares this operation to be deprecated.
     ///
@@ -100,15 +108,15 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
 
-    // FIXME: Implement
-    // /// A declaration of which security mechanisms can be used for this operation. The list of
-    // /// values includes alternative security requirement objects that can be used. Only one
-    // /// of the security requirement objects need to be satisfied to authorize a request.
-    // /// This definition overrides any declared top-level
-    // /// [`security`](https://spec.openapis.org/oas/v3.1.0#oasSecurity).
-    // /// To remove a top-level security declaration, an empty array can be used.
-    // pub security: Option<SecurityRequirement>,
-    //
+    /// A declaration of which security mechanisms can be used for this operation. The list of
+    /// values includes alternative security requirement objects that can be used. Only one
+    /// of the security requirement objects need to be satisfied to authorize a request.
+    /// This definition overrides any declared top-level
+    /// [`security`](https://spec.openapis.org/oas/v3.1.0#oasSecurity).
+    /// To remove a top-level security declaration, an empty array can be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
     /// An alternative `server` array to service this operation.
     ///
     /// If an alternative `server` object is specified at the Path Item Object or Root level, it
@@ -136,6 +144,9 @@ impl Operation {
     }
 
     /// Resolves and returns map of this operation's responses, keyed by status code.
+    ///
+    /// Responses that fail to resolve are logged and skipped. Use
+    /// [`try_responses()`](Self::try_responses) to return an error instead.
     pub fn responses(&self, spec: &Spec) -> BTreeMap<String, Response> {
         self.responses
             .iter()
@@ -150,7 +161,59 @@ impl Operation {
             .collect()
     }
 
+    /// Resolves and returns map of this operation's responses, keyed by status code.
+    ///
+    /// Returns an error if any response fails to resolve.
+    pub fn try_responses(&self, spec: &Spec) -> Result<BTreeMap<String, Response>, RefError> {
+        self.responses
+            .iter()
+            .flatten()
+            .map(|(name, oor)| oor.resolve(spec).map(|obj| (name.clone(), obj)))
+            .collect()
+    }
+
+    /// Returns the keys of this operation's declared responses.
+    ///
+    /// Keys that are not valid status codes, ranges, or `default` are skipped.
+    pub fn status_keys(&self) -> Vec<ResponseKey> {
+        self.responses
+            .iter()
+            .flatten()
+            .filter_map(|(key, _)| key.parse().map_err(|err| error!("{}", err)).ok())
+            .collect()
+    }
+
+    /// Resolves and returns the response describing `status`, if any.
+    ///
+    /// An exact status code key takes precedence over a range key (e.g. `2XX`), which in turn takes
+    /// precedence over `default`. Keys that fail to parse are ignored.
+    pub fn response_for_status(
+        &self,
+        status: http::StatusCode,
+        spec: &Spec,
+    ) -> Result<Option<Response>, RefError> {
+        self.responses
+            .iter()
+            .flatten()
+            .filter_map(|(key, oor)| Some((key.parse::<ResponseKey>().ok()?, oor)))
+            .filter(|(key, _)| key.matches(status))
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, oor)| oor.resolve(spec))
+            .transpose()
+    }
+
+    /// Resolves and returns map of this operation's callbacks, keyed by callback name.
+    pub fn callbacks_resolved(&self, spec: &Spec) -> Result<BTreeMap<String, Callback>, RefError> {
+        self.callbacks
+            .iter()
+            .map(|(name, oor)| oor.resolve(spec).map(|cb| (name.clone(), cb)))
+            .collect()
+    }
+
     /// Resolves and returns list of this operation's parameters.
+    ///
+    /// Parameters that fail to resolve are logged and skipped. Use
+    /// [`try_parameters()`](Self::try_parameters) to return an error instead.
     pub fn parameters(&self, spec: &Spec) -> Result<Vec<Parameter>, Error> {
         let params = self
             .parameters
@@ -162,6 +225,16 @@ impl Operation {
         Ok(params)
     }
 
+    /// Resolves and returns list of this operation's parameters.
+    ///
+    /// Returns an error if any parameter fails to resolve.
+    pub fn try_parameters(&self, spec: &Spec) -> Result<Vec<Parameter>, RefError> {
+        self.parameters
+            .iter()
+            .map(|oor| oor.resolve(spec))
+            .collect()
+    }
+
     /// Finds, resolves, and returns one of this operation's parameters by name.
     pub fn parameter(&self, search: &str, spec: &Spec) -> Result<Option<Parameter>, Error> {
         let param = self
@@ -172,4 +245,361 @@ impl Operation {
 
         Ok(param)
     }
+
+    /// Resolves and returns this operation's parameters combined with those of its `path_item`.
+    ///
+    /// Path-level parameters come first, in declaration order. An operation-level parameter with
+    /// the same name and location overrides the path-level one in place; others are appended.
+    ///
+    /// Returns an error if any parameter fails to resolve.
+    pub fn merged_parameters(
+        &self,
+        path_item: &PathItem,
+        spec: &Spec,
+    ) -> Result<Vec<Parameter>, RefError> {
+        let params = path_item
+            .parameters
+            .iter()
+            .map(|oor| oor.resolve(spec))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(merge_parameters(params, self.try_parameters(spec)?))
+    }
+
+    /// Returns the servers that service this operation.
+    ///
+    /// Servers declared on the operation override those declared on its `path_item`, which in turn
+    /// override the spec's root servers. If no servers are declared at any level, a single server
+    /// with a URL of `/` is returned.
+    pub fn effective_servers(&self, path_item: &PathItem, spec: &Spec) -> Vec<Server> {
+        [&self.servers, &path_item.servers, &spec.servers]
+            .into_iter()
+            .find(|servers| !servers.is_empty())
+            .cloned()
+            .unwrap_or_else(|| vec![Server::default()])
+    }
+}
+
+/// Combines resolved path-level and operation-level parameters, as described for
+/// [`Operation::merged_parameters()`].
+pub(crate) fn merge_parameters(
+    mut params: Vec<Parameter>,
+    op_params: impl IntoIterator<Item = Parameter>,
+) -> Vec<Parameter> {
+    for param in op_params {
+        let existing = params
+            .iter_mut()
+            .find(|existing| existing.name == param.name && existing.location == param.location);
+
+        match existing {
+            Some(existing) => *existing = param,
+            None => params.push(param),
+        }
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use super::*;
+
+    fn server_urls(servers: &[Server]) -> Vec<&str> {
+        servers.iter().map(|server| server.url.as_str()).collect()
+    }
+
+    #[test]
+    fn effective_servers() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Servers
+              version: 0.0.0
+            servers:
+              - url: https://root.example.com
+            paths:
+              /inherit:
+                get: {}
+              /path-level:
+                servers:
+                  - url: https://path.example.com
+                get: {}
+                post:
+                  servers:
+                    - url: https://op.example.com
+                    - url: https://op2.example.com
+        "})
+        .unwrap();
+
+        let paths = spec.paths.as_ref().unwrap();
+
+        let item = &paths["/inherit"];
+        let op = spec.operation(&Method::GET, "/inherit").unwrap();
+        assert_eq!(
+            server_urls(&op.effective_servers(item, &spec)),
+            ["https://root.example.com"],
+        );
+
+        let item = &paths["/path-level"];
+        let op = spec.operation(&Method::GET, "/path-level").unwrap();
+        assert_eq!(
+            server_urls(&op.effective_servers(item, &spec)),
+            ["https://path.example.com"],
+        );
+
+        let op = spec.operation(&Method::POST, "/path-level").unwrap();
+        assert_eq!(
+            server_urls(&op.effective_servers(item, &spec)),
+            ["https://op.example.com", "https://op2.example.com"],
+        );
+    }
+
+    #[test]
+    fn effective_servers_default() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Servers
+              version: 0.0.0
+            paths:
+              /:
+                get: {}
+        "})
+        .unwrap();
+
+        let item = &spec.paths.as_ref().unwrap()["/"];
+        let op = spec.operation(&Method::GET, "/").unwrap();
+        assert_eq!(op.effective_servers(item, &spec), [Server::default()]);
+        assert_eq!(server_urls(&op.effective_servers(item, &spec)), ["/"]);
+    }
+
+    #[test]
+    fn callbacks_ref_round_trip_and_resolve() {
+        let spec_str = indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Callbacks
+              version: 0.0.0
+            paths:
+              /subscribe:
+                post:
+                  callbacks:
+                    onEvent:
+                      $ref: '#/components/callbacks/Event'
+                    onInline:
+                      x-internal: true
+                      '{$request.body#/url}':
+                        post:
+                          responses:
+                            '200':
+                              description: OK
+            components:
+              callbacks:
+                Event:
+                  '{$request.body#/callbackUrl}':
+                    post:
+                      responses:
+                        '204':
+                          description: Received
+        "};
+
+        let spec = serde_yml::from_str::<Spec>(spec_str).unwrap();
+        let op = spec.operation(&Method::POST, "/subscribe").unwrap();
+
+        assert_eq!(
+            op.callbacks["onEvent"],
+            ObjectOrReference::Ref {
+                ref_path: "#/components/callbacks/Event".to_owned(),
+            },
+        );
+
+        let round_trip = serde_yml::to_string(&spec).unwrap();
+        assert_eq!(serde_yml::from_str::<Spec>(&round_trip).unwrap(), spec);
+
+        let callbacks = op.callbacks_resolved(&spec).unwrap();
+        assert_eq!(callbacks.len(), 2);
+        assert_eq!(callbacks["onInline"].extensions["internal"], true);
+        let event = serde_json::to_value(&callbacks["onEvent"]).unwrap();
+        assert_eq!(
+            event["{$request.body#/callbackUrl}"]["post"]["responses"]["204"]["description"],
+            "Received",
+        );
+
+        let ops = callbacks["onEvent"].operations().collect::<Vec<_>>();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].0, "{$request.body#/callbackUrl}");
+        assert_eq!(ops[0].1, Method::POST);
+        assert!(ops[0].2.responses.as_ref().unwrap().contains_key("204"));
+
+        let mut op = op.clone();
+        op.callbacks.insert(
+            "missing".to_owned(),
+            ObjectOrReference::Ref {
+                ref_path: "#/components/callbacks/Missing".to_owned(),
+            },
+        );
+        assert_eq!(
+            op.callbacks_resolved(&spec).unwrap_err(),
+            RefError::Unresolvable("#/components/callbacks/Missing".to_owned()),
+        );
+    }
+
+    #[test]
+    fn try_responses_and_parameters() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths: {}
+            components:
+              parameters:
+                Limit:
+                  name: limit
+                  in: query
+                  schema:
+                    type: integer
+              responses:
+                NotFound:
+                  description: Not found
+        "})
+        .unwrap();
+
+        let op = serde_yml::from_str::<Operation>(indoc::indoc! {"
+            parameters:
+              - $ref: '#/components/parameters/Limit'
+              - $ref: '#/components/parameters/Offset'
+            responses:
+              '200':
+                description: OK
+              '404':
+                $ref: '#/components/responses/NotFund'
+        "})
+        .unwrap();
+
+        assert_eq!(op.responses(&spec).len(), 1);
+        assert_eq!(
+            op.try_responses(&spec).unwrap_err(),
+            RefError::Unresolvable("#/components/responses/NotFund".to_owned()),
+        );
+
+        assert_eq!(op.parameters(&spec).unwrap().len(), 1);
+        assert_eq!(
+            op.try_parameters(&spec).unwrap_err(),
+            RefError::Unresolvable("#/components/parameters/Offset".to_owned()),
+        );
+
+        let op = Operation {
+            parameters: op.parameters[..1].to_vec(),
+            ..Operation::default()
+        };
+        assert_eq!(op.try_parameters(&spec).unwrap()[0].name, "limit");
+        assert!(op.try_responses(&spec).unwrap().is_empty());
+    }
+
+    #[test]
+    fn status_keys() {
+        let op = serde_yml::from_str::<Operation>(indoc::indoc! {"
+            responses:
+              '200':
+                description: OK
+              4XX:
+                description: Client error
+              default:
+                description: Error
+        "})
+        .unwrap();
+
+        assert_eq!(
+            op.status_keys(),
+            [
+                ResponseKey::Status(http::StatusCode::OK),
+                ResponseKey::Range(4),
+                ResponseKey::Default,
+            ],
+        );
+    }
+
+    #[test]
+    fn response_for_status_precedence() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths: {}
+            components:
+              responses:
+                NotFound:
+                  description: Not found
+        "})
+        .unwrap();
+
+        let op = serde_yml::from_str::<Operation>(indoc::indoc! {"
+            responses:
+              '200':
+                description: OK
+              2XX:
+                description: Success
+              '404':
+                $ref: '#/components/responses/NotFound'
+              default:
+                description: Error
+        "})
+        .unwrap();
+
+        let description = |status| {
+            op.response_for_status(status, &spec)
+                .unwrap()
+                .and_then(|res| res.description)
+        };
+
+        assert_eq!(description(http::StatusCode::OK).unwrap(), "OK");
+        assert_eq!(description(http::StatusCode::CREATED).unwrap(), "Success");
+        assert_eq!(
+            description(http::StatusCode::NOT_FOUND).unwrap(),
+            "Not found"
+        );
+        assert_eq!(description(http::StatusCode::CONFLICT).unwrap(), "Error");
+
+        let op = Operation::default();
+        assert!(op
+            .response_for_status(http::StatusCode::OK, &spec)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn unquoted_status_key() {
+        let op = serde_yml::from_str::<Operation>(indoc::indoc! {"
+            responses:
+              200:
+                description: OK
+        "})
+        .unwrap();
+
+        assert!(op.responses.unwrap().contains_key("200"));
+    }
+
+    #[cfg(feature = "lenient")]
+    #[test]
+    fn lenient_integer_status_key() {
+        let value = serde_yml::from_str::<serde_yml::Value>(indoc::indoc! {"
+            responses:
+              200:
+                description: OK
+              default:
+                description: Error
+        "})
+        .unwrap();
+
+        let op = serde_yml::from_value::<Operation>(value).unwrap();
+        let responses = op.responses.unwrap();
+        assert_eq!(responses.keys().collect::<Vec<_>>(), ["200", "default"]);
+
+        let op = serde_yml::from_str::<Operation>("operationId: noResponses").unwrap();
+        assert!(op.responses.is_none());
+    }
 }
@@ -1,15 +1,16 @@
 use std::collections::BTreeMap;
 
+use derive_more::derive::{Display, Error};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    spec_extensions, Example, FromRef, MediaType, ObjectOrReference, ObjectSchema, Ref, RefError,
-    RefType, Spec,
+    resolve_component, spec_extensions, Error as SpecError, Example, FromRef, MediaType,
+    ObjectOrReference, ObjectSchema, RefError, RefType, Spec,
 };
 
 /// Parameter location.
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "lowercase")]
 pub enum ParameterIn {
     /// Used together with [path templating], where the parameter value is actually part of the
     /// operation's URL.
@@ -83,6 +84,35 @@ pub enum ParameterStyle {
     DeepObject,
 }
 
+impl ParameterStyle {
+    /// Returns the default style for parameters in the given location.
+    ///
+    /// This is `form` for `query` and `cookie` parameters, and `simple` for `path` and `header`
+    /// parameters.
+    pub fn default_for(location: ParameterIn) -> ParameterStyle {
+        match location {
+            ParameterIn::Query | ParameterIn::Cookie => ParameterStyle::Form,
+            ParameterIn::Path | ParameterIn::Header => ParameterStyle::Simple,
+        }
+    }
+}
+
+/// Parameter validation errors.
+#[derive(Debug, Clone, PartialEq, Display, Error)]
+pub enum ParameterError {
+    /// Neither `schema` nor `content` are specified.
+    #[display("Parameter must specify one of `schema` or `content`")]
+    MissingSchemaOrContent,
+
+    /// Both `schema` and `content` are specified.
+    #[display("Parameter must not specify both `schema` and `content`")]
+    SchemaAndContent,
+
+    /// The `content` map does not contain exactly one entry.
+    #[display("Parameter `content` must contain exactly one entry; found {}", _0)]
+    ContentEntryCount(#[error(not(source))] usize),
+}
+
 /// Describes a single operation parameter.
 ///
 /// A unique parameter is defined by a combination of a `name` and location (`in`).
@@ -203,24 +233,41 @@ pub struct Parameter {
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
-impl FromRef for Parameter {
-    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError>
-    where
-        Self: Sized,
-    {
-        let refpath = path.parse::<Ref>()?;
-
-        match refpath.kind {
-            RefType::Parameter => spec
-                .components
-                .as_ref()
-                .and_then(|cs| cs.parameters.get(&refpath.name))
-                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
-                .and_then(|oor| oor.resolve(spec)),
-
-            typ => Err(RefError::MismatchedType(typ, RefType::Parameter)),
+impl Parameter {
+    /// Validates that exactly one of the `schema` or `content` fields are specified and, if
+    /// `content` is used, that it contains exactly one media type entry.
+    pub fn validate(&self) -> Result<(), ParameterError> {
+        match (&self.schema, &self.content) {
+            (None, None) => Err(ParameterError::MissingSchemaOrContent),
+            (Some(_), Some(_)) => Err(ParameterError::SchemaAndContent),
+            (None, Some(content)) if content.len() != 1 => {
+                Err(ParameterError::ContentEntryCount(content.len()))
+            }
+            _ => Ok(()),
         }
     }
+
+    /// Resolves and returns the schema describing this parameter's value.
+    ///
+    /// The schema is taken from the `schema` field or, for parameters using the `content` form,
+    /// from the schema of its media type entry. Returns `None` if neither form provides a schema.
+    pub fn effective_schema(&self, spec: &Spec) -> Result<Option<ObjectSchema>, SpecError> {
+        let schema = match (&self.schema, &self.content) {
+            (Some(schema), _) => Some(schema),
+            (None, Some(content)) => content.values().next().and_then(|mt| mt.schema.as_ref()),
+            (None, None) => None,
+        };
+
+        Ok(schema.map(|oor| oor.resolve(spec)).transpose()?)
+    }
+}
+
+impl FromRef for Parameter {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        resolve_component(spec, path, RefType::Parameter, |cs, name| {
+            cs.parameters.get(name)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -242,5 +289,177 @@ mod tests {
 
         let parameter = serde_yml::from_str::<Parameter>(spec).unwrap();
         assert_eq!(parameter.name, "foo");
+        assert_eq!(parameter.location, ParameterIn::Query);
+    }
+
+    #[test]
+    fn content_and_fields_deserialization() {
+        let spec = indoc! {"
+            name: coordinates
+            in: query
+            deprecated: true
+            allowReserved: true
+            content:
+              application/json:
+                schema:
+                  type: object
+            examples:
+              origin:
+                value: { lat: 0, long: 0 }
+        "};
+
+        let parameter = serde_yml::from_str::<Parameter>(spec).unwrap();
+        assert_eq!(parameter.deprecated, Some(true));
+        assert_eq!(parameter.allow_reserved, Some(true));
+        assert!(parameter.content.as_ref().unwrap()["application/json"]
+            .schema
+            .is_some());
+        assert!(parameter.examples.contains_key("origin"));
+        parameter.validate().unwrap();
+    }
+
+    #[test]
+    fn schema_xor_content() {
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: foo
+            in: query
+            schema:
+              type: string
+        "})
+        .unwrap();
+        parameter.validate().unwrap();
+
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: foo
+            in: query
+        "})
+        .unwrap();
+        assert_eq!(
+            parameter.validate().unwrap_err(),
+            ParameterError::MissingSchemaOrContent,
+        );
+
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: foo
+            in: query
+            schema:
+              type: string
+            content:
+              text/plain: {}
+        "})
+        .unwrap();
+        assert_eq!(
+            parameter.validate().unwrap_err(),
+            ParameterError::SchemaAndContent,
+        );
+
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: foo
+            in: query
+            content:
+              text/plain: {}
+              application/json: {}
+        "})
+        .unwrap();
+        assert_eq!(
+            parameter.validate().unwrap_err(),
+            ParameterError::ContentEntryCount(2),
+        );
+    }
+
+    #[test]
+    fn location_round_trip() {
+        for location in ["query", "header", "path", "cookie"] {
+            let spec = format!("name: foo\nin: {location}\n");
+            let parameter = serde_yml::from_str::<Parameter>(&spec).unwrap();
+            assert_eq!(serde_yml::to_string(&parameter).unwrap(), spec);
+        }
+    }
+
+    #[test]
+    fn style_defaults_and_names() {
+        assert_eq!(
+            ParameterStyle::default_for(ParameterIn::Query),
+            ParameterStyle::Form,
+        );
+        assert_eq!(
+            ParameterStyle::default_for(ParameterIn::Cookie),
+            ParameterStyle::Form,
+        );
+        assert_eq!(
+            ParameterStyle::default_for(ParameterIn::Path),
+            ParameterStyle::Simple,
+        );
+        assert_eq!(
+            ParameterStyle::default_for(ParameterIn::Header),
+            ParameterStyle::Simple,
+        );
+
+        let styles = serde_json::from_str::<Vec<ParameterStyle>>(
+            r#"["matrix", "label", "form", "simple", "spaceDelimited", "pipeDelimited", "deepObject"]"#,
+        )
+        .unwrap();
+        assert_eq!(styles.len(), 7);
+        assert_eq!(styles[4], ParameterStyle::SpaceDelimited);
+        assert_eq!(
+            serde_json::to_value(ParameterStyle::PipeDelimited).unwrap(),
+            "pipeDelimited",
+        );
+    }
+
+    #[test]
+    fn unknown_location_rejected() {
+        let spec = indoc! {"
+            name: foo
+            in: querry
+        "};
+
+        serde_yml::from_str::<Parameter>(spec).unwrap_err();
+    }
+
+    #[test]
+    fn effective_schema() {
+        let spec = serde_yml::from_str::<Spec>(indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Id:
+                  type: integer
+        "})
+        .unwrap();
+
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: id
+            in: path
+            schema:
+              $ref: '#/components/schemas/Id'
+        "})
+        .unwrap();
+        let schema = parameter.effective_schema(&spec).unwrap().unwrap();
+        assert_eq!(schema, ObjectSchema::integer());
+
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: filter
+            in: query
+            content:
+              application/json:
+                schema:
+                  type: object
+        "})
+        .unwrap();
+        let schema = parameter.effective_schema(&spec).unwrap().unwrap();
+        assert_eq!(schema, ObjectSchema::object());
+
+        let parameter = serde_yml::from_str::<Parameter>(indoc! {"
+            name: filter
+            in: query
+            content:
+              text/plain: {}
+        "})
+        .unwrap();
+        assert!(parameter.effective_schema(&spec).unwrap().is_none());
     }
 }
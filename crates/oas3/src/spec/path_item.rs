@@ -3,7 +3,10 @@ use std::collections::BTreeMap;
 use http::Method;
 use serde::{Deserialize, Serialize};
 
-use super::{spec_extensions, ObjectOrReference, Operation, Parameter, Server};
+use super::{
+    spec_extensions, FromRef, ObjectOrReference, Operation, Parameter, Ref, RefError, RefType,
+    Server, Spec,
+};
 
 /// Describes the operations available on a single path.
 ///
@@ -114,8 +117,242 @@ impl PathItem {
         push_method!(head, HEAD);
         push_method!(patch, PATCH);
         push_method!(trace, TRACE);
-        push_method!(trace, TRACE);
 
         methods
     }
+
+    /// Returns iterator over mutable references to this path's provided operations, keyed by
+    /// method.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (Method, &mut Operation)> {
+        [
+            (Method::GET, &mut self.get),
+            (Method::PUT, &mut self.put),
+            (Method::POST, &mut self.post),
+            (Method::DELETE, &mut self.delete),
+            (Method::OPTIONS, &mut self.options),
+            (Method::HEAD, &mut self.head),
+            (Method::PATCH, &mut self.patch),
+            (Method::TRACE, &mut self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, op)| op.as_mut().map(|op| (method, op)))
+    }
+
+    /// Resolves this path item's `$ref` (if set) from the given `spec` and returns the result.
+    ///
+    /// Fields set on this path item take precedence over those of the referenced path item. The
+    /// returned path item has no `$ref`.
+    pub fn resolve(&self, spec: &Spec) -> Result<PathItem, RefError> {
+        self.resolve_inner(spec, &mut Vec::new())
+    }
+
+    fn resolve_inner(&self, spec: &Spec, seen: &mut Vec<String>) -> Result<PathItem, RefError> {
+        let Some(ref_path) = &self.reference else {
+            return Ok(self.clone());
+        };
+
+        seen.push(ref_path.clone());
+
+        if seen[..seen.len() - 1].contains(ref_path) {
+            return Err(RefError::Cycle(seen.clone()));
+        }
+
+        let refpath = ref_path.parse::<Ref>()?;
+
+        let target = match refpath.kind {
+            RefType::PathItem => spec
+                .components
+                .as_ref()
+                .and_then(|cs| cs.path_items.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(ref_path.clone()))?,
+
+            typ => return Err(RefError::MismatchedType(typ, RefType::PathItem)),
+        };
+
+        let target = match target {
+            ObjectOrReference::Object(item) => item.resolve_inner(spec, seen),
+            ObjectOrReference::Ref { ref_path } => PathItem {
+                reference: Some(ref_path.clone()),
+                ..PathItem::default()
+            }
+            .resolve_inner(spec, seen),
+        }
+        .map_err(|err| err.via(ref_path))?;
+
+        Ok(self.clone().merged_over(target))
+    }
+
+    /// Fills fields not set on `self` from `base`.
+    fn merged_over(self, base: PathItem) -> PathItem {
+        let mut extensions = base.extensions;
+        extensions.extend(self.extensions);
+
+        PathItem {
+            reference: None,
+            summary: self.summary.or(base.summary),
+            description: self.description.or(base.description),
+            get: self.get.or(base.get),
+            put: self.put.or(base.put),
+            post: self.post.or(base.post),
+            delete: self.delete.or(base.delete),
+            options: self.options.or(base.options),
+            head: self.head.or(base.head),
+            patch: self.patch.or(base.patch),
+            trace: self.trace.or(base.trace),
+            servers: if self.servers.is_empty() {
+                base.servers
+            } else {
+                self.servers
+            },
+            parameters: if self.parameters.is_empty() {
+                base.parameters
+            } else {
+                self.parameters
+            },
+            extensions,
+        }
+    }
+}
+
+impl FromRef for PathItem {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        PathItem {
+            reference: Some(path.to_owned()),
+            ..PathItem::default()
+        }
+        .resolve(spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_ref_with_sibling_fields() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: API
+              version: 1.0.0
+            paths:
+              /pets:
+                $ref: '#/components/pathItems/Pets'
+                summary: Local summary
+                post:
+                  operationId: localCreatePet
+            components:
+              pathItems:
+                Pets:
+                  summary: Shared summary
+                  description: Shared description
+                  get:
+                    operationId: listPets
+                  post:
+                    operationId: createPet
+                Alias:
+                  $ref: '#/components/pathItems/Pets'
+                LoopA:
+                  $ref: '#/components/pathItems/LoopB'
+                LoopB:
+                  $ref: '#/components/pathItems/LoopA'
+        "})
+        .unwrap();
+
+        let item = spec.paths.as_ref().unwrap()["/pets"]
+            .resolve(&spec)
+            .unwrap();
+        assert!(item.reference.is_none());
+        assert_eq!(item.summary.as_deref(), Some("Local summary"));
+        assert_eq!(item.description.as_deref(), Some("Shared description"));
+        assert_eq!(item.get.unwrap().operation_id.as_deref(), Some("listPets"));
+        assert_eq!(
+            item.post.unwrap().operation_id.as_deref(),
+            Some("localCreatePet"),
+        );
+
+        let alias = PathItem::from_ref(&spec, "#/components/pathItems/Alias").unwrap();
+        assert_eq!(alias.get.unwrap().operation_id.as_deref(), Some("listPets"));
+
+        assert_eq!(
+            PathItem::from_ref(&spec, "#/components/pathItems/LoopA").unwrap_err(),
+            RefError::Cycle(vec![
+                "#/components/pathItems/LoopA".to_owned(),
+                "#/components/pathItems/LoopB".to_owned(),
+                "#/components/pathItems/LoopA".to_owned(),
+            ]),
+        );
+        assert_eq!(
+            PathItem::from_ref(&spec, "#/components/schemas/Pet").unwrap_err(),
+            RefError::MismatchedType(RefType::Schema, RefType::PathItem),
+        );
+        assert_eq!(
+            PathItem::from_ref(&spec, "./pets.yaml").unwrap_err(),
+            RefError::Unresolvable("./pets.yaml".to_owned()),
+        );
+    }
+
+    #[test]
+    fn operations_mut() {
+        let mut item = serde_yml::from_str::<PathItem>(indoc::indoc! {"
+            get:
+              operationId: getPet
+            trace:
+              operationId: tracePet
+        "})
+        .unwrap();
+
+        for (method, op) in item.operations_mut() {
+            op.summary = Some(method.to_string());
+        }
+
+        let summaries = item
+            .methods()
+            .into_iter()
+            .map(|(method, op)| (method, op.summary.clone().unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summaries,
+            [
+                (Method::GET, "GET".to_owned()),
+                (Method::TRACE, "TRACE".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn methods_yields_each_operation_once() {
+        let item = serde_yml::from_str::<PathItem>(indoc::indoc! {"
+            get: {}
+            put: {}
+            post: {}
+            delete: {}
+            options: {}
+            head: {}
+            patch: {}
+            trace: {}
+        "})
+        .unwrap();
+
+        let methods = item
+            .methods()
+            .into_iter()
+            .map(|(method, _)| method)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            methods,
+            [
+                Method::GET,
+                Method::PUT,
+                Method::POST,
+                Method::DELETE,
+                Method::OPTIONS,
+                Method::HEAD,
+                Method::PATCH,
+                Method::TRACE,
+            ],
+        );
+    }
 }
@@ -0,0 +1,351 @@
+//! Removal of unreferenced components.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{schema_scope::reference_targets, Components, Spec};
+use crate::json_pointer;
+
+impl Spec {
+    /// Removes components that are not reachable from `paths`, `webhooks`, or `security`,
+    /// returning the number of components removed.
+    ///
+    /// Components are reachable if a local `$ref`, a reference to a schema's `$id` or anchor, or a
+    /// discriminator mapping points to them, either directly or through other reachable components. Security schemes are reachable if they are
+    /// named by the global or an operation's security requirements.
+    ///
+    /// Reachability is computed conservatively: `$ref`-like values inside examples and extensions
+    /// also keep their targets.
+    pub fn prune_unused_components(&mut self) -> usize {
+        let Some(components) = &self.components else {
+            return 0;
+        };
+
+        let components_doc = to_value(components);
+
+        let mut reachable = BTreeSet::new();
+        let mut pending = Vec::new();
+
+        collect_refs(&to_value(&self.paths), &mut pending);
+        collect_refs(&to_value(&self.webhooks), &mut pending);
+
+        for requirement in &self.security {
+            for scheme in requirement.0.keys() {
+                pending.push(("securitySchemes".to_owned(), scheme.clone()));
+            }
+        }
+
+        // references by `$id` or anchor can only be matched to components through the index
+        let mut indexed_refs = BTreeMap::<(String, String), Vec<(String, String)>>::new();
+
+        for (location, target) in reference_targets(self) {
+            let Some(target) = component_at(&target) else {
+                continue;
+            };
+
+            if location.starts_with("/paths/") || location.starts_with("/webhooks/") {
+                pending.push(target);
+            } else if let Some(source) = component_at(&location) {
+                indexed_refs.entry(source).or_default().push(target);
+            }
+        }
+
+        while let Some(component) = pending.pop() {
+            let (kind, name) = &component;
+
+            if let Some(value) = components_doc.get(kind).and_then(|defs| defs.get(name)) {
+                if !reachable.contains(&component) {
+                    collect_refs(value, &mut pending);
+                    pending.extend(indexed_refs.get(&component).into_iter().flatten().cloned());
+                    reachable.insert(component);
+                }
+            }
+        }
+
+        let components = self.components.as_mut().unwrap();
+        let is_used =
+            |kind: &str, name: &String| reachable.contains(&(kind.to_owned(), name.clone()));
+
+        let before = component_count(components);
+
+        components
+            .schemas
+            .retain(|name, _| is_used("schemas", name));
+        components
+            .responses
+            .retain(|name, _| is_used("responses", name));
+        components
+            .parameters
+            .retain(|name, _| is_used("parameters", name));
+        components
+            .examples
+            .retain(|name, _| is_used("examples", name));
+        components
+            .request_bodies
+            .retain(|name, _| is_used("requestBodies", name));
+        components
+            .headers
+            .retain(|name, _| is_used("headers", name));
+        components
+            .security_schemes
+            .retain(|name, _| is_used("securitySchemes", name));
+        components.links.retain(|name, _| is_used("links", name));
+        components
+            .callbacks
+            .retain(|name, _| is_used("callbacks", name));
+        components
+            .path_items
+            .retain(|name, _| is_used("pathItems", name));
+
+        before - component_count(components)
+    }
+}
+
+fn to_value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).expect("spec objects should always serialize to JSON")
+}
+
+fn component_count(components: &Components) -> usize {
+    components.schemas.len()
+        + components.responses.len()
+        + components.parameters.len()
+        + components.examples.len()
+        + components.request_bodies.len()
+        + components.headers.len()
+        + components.security_schemes.len()
+        + components.links.len()
+        + components.callbacks.len()
+        + components.path_items.len()
+}
+
+/// Collects the type and name of components referenced from within `value`.
+fn collect_refs(value: &Value, refs: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, value) in obj {
+                match (key.as_str(), value) {
+                    ("$ref", Value::String(ref_path)) => refs.extend(component_ref(ref_path)),
+
+                    ("mapping", Value::Object(mapping)) => {
+                        for target in mapping.values().filter_map(Value::as_str) {
+                            // bare names in discriminator mappings refer to schemas
+                            match component_ref(target) {
+                                Some(component) => refs.push(component),
+                                None => refs.push(("schemas".to_owned(), target.to_owned())),
+                            }
+                        }
+                    }
+
+                    ("security", Value::Array(requirements)) => {
+                        for requirement in requirements.iter().filter_map(Value::as_object) {
+                            refs.extend(
+                                requirement
+                                    .keys()
+                                    .map(|scheme| ("securitySchemes".to_owned(), scheme.clone())),
+                            );
+                        }
+                    }
+
+                    _ => {}
+                }
+
+                collect_refs(value, refs);
+            }
+        }
+
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, refs);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Splits a local reference like `#/components/schemas/Pet` into component type and name.
+fn component_ref(ref_path: &str) -> Option<(String, String)> {
+    component_at(ref_path.strip_prefix('#')?)
+}
+
+/// Returns the type and name of the component at or containing JSON pointer `ptr`.
+fn component_at(ptr: &str) -> Option<(String, String)> {
+    let mut tokens = ptr.strip_prefix("/components/")?.split('/');
+    let kind = tokens.next()?;
+    let name = tokens.next()?;
+
+    Some((kind.to_owned(), json_pointer::unescape(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_unused_components() {
+        let mut spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            security:
+              - apiKey: []
+            paths:
+              /pets:
+                get:
+                  security:
+                    - oauth: [read]
+                  parameters:
+                    - $ref: '#/components/parameters/Limit'
+                  responses:
+                    '200':
+                      $ref: '#/components/responses/Pets'
+            webhooks:
+              newPet:
+                post:
+                  requestBody:
+                    $ref: '#/components/requestBodies/NewPet'
+            components:
+              schemas:
+                Pet:
+                  oneOf:
+                    - $ref: '#/components/schemas/Cat'
+                  discriminator:
+                    propertyName: kind
+                    mapping:
+                      dog: Dog
+                Cat:
+                  type: object
+                  properties:
+                    owner:
+                      $ref: '#/components/schemas/Person/properties/name'
+                Dog:
+                  type: object
+                Person:
+                  type: object
+                  properties:
+                    name: { type: string }
+                Orphan:
+                  type: object
+                OrphanChild:
+                  $ref: '#/components/schemas/Orphan'
+              parameters:
+                Limit:
+                  name: limit
+                  in: query
+                  schema: { type: integer }
+                Unused:
+                  name: unused
+                  in: query
+              responses:
+                Pets:
+                  description: OK
+                  content:
+                    application/json:
+                      schema:
+                        type: array
+                        items:
+                          $ref: '#/components/schemas/Pet'
+              requestBodies:
+                NewPet:
+                  content:
+                    application/json:
+                      schema:
+                        $ref: '#/components/schemas/Dog'
+              securitySchemes:
+                apiKey:
+                  type: apiKey
+                  name: key
+                  in: header
+                oauth:
+                  type: http
+                  scheme: bearer
+                basic:
+                  type: http
+                  scheme: basic
+        "})
+        .unwrap();
+
+        assert_eq!(spec.prune_unused_components(), 4);
+
+        let components = spec.components.as_ref().unwrap();
+
+        let mut schemas = components.schemas.keys().collect::<Vec<_>>();
+        schemas.sort_unstable();
+        assert_eq!(schemas, ["Cat", "Dog", "Person", "Pet"]);
+
+        assert_eq!(components.parameters.keys().collect::<Vec<_>>(), ["Limit"]);
+        assert_eq!(components.responses.keys().collect::<Vec<_>>(), ["Pets"]);
+        assert_eq!(
+            components.request_bodies.keys().collect::<Vec<_>>(),
+            ["NewPet"],
+        );
+        assert_eq!(
+            components.security_schemes.keys().collect::<Vec<_>>(),
+            ["apiKey", "oauth"],
+        );
+
+        assert_eq!(spec.prune_unused_components(), 0);
+    }
+
+    #[test]
+    fn prune_keeps_id_and_anchor_targets() {
+        let mut spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  parameters:
+                    - name: tag
+                      in: query
+                      schema:
+                        $ref: '#tag'
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            $ref: 'https://example.com/pet'
+            components:
+              schemas:
+                Pet:
+                  $id: https://example.com/pet
+                  type: object
+                  properties:
+                    owner:
+                      $ref: owner
+                Owner:
+                  $id: https://example.com/owner
+                  type: object
+                Tag:
+                  $anchor: tag
+                  type: string
+                Unused:
+                  $id: https://example.com/unused
+                  type: object
+        "})
+        .unwrap();
+
+        assert_eq!(spec.prune_unused_components(), 1);
+
+        let mut schemas = spec
+            .components
+            .as_ref()
+            .unwrap()
+            .schemas
+            .keys()
+            .collect::<Vec<_>>();
+        schemas.sort_unstable();
+        assert_eq!(schemas, ["Owner", "Pet", "Tag"]);
+
+        let op = spec.operation(&http::Method::GET, "/pets").unwrap();
+        let response = &op.responses(&spec)["200"];
+        response.content["application/json"].schema(&spec).unwrap();
+    }
+}
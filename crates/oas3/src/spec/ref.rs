@@ -6,7 +6,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use super::Spec;
+use super::{Components, Spec};
 
 static RE_REF: Lazy<Regex> = Lazy::new(|| {
     Regex::new("^(?P<source>[^#]*)#/components/(?P<type>[^/]+)/(?P<name>.+)$").unwrap()
@@ -40,6 +40,12 @@ where
     }
 }
 
+impl<T> From<T> for ObjectOrReference<T> {
+    fn from(obj: T) -> Self {
+        Self::Object(obj)
+    }
+}
+
 /// Object reference error.
 #[derive(Clone, Debug, PartialEq, Display, Error)]
 pub enum RefError {
@@ -54,6 +60,58 @@ pub enum RefError {
     /// Reference path points outside the given spec file.
     #[display("Unresolvable path: {}", _0)]
     Unresolvable(#[error(not(source))] String), // TODO: use some kind of path structure
+
+    /// Reference chain leads back to a reference already followed.
+    #[display("Reference cycle: {}", _0.join(" -> "))]
+    Cycle(#[error(not(source))] Vec<String>),
+
+    /// Reference could not be resolved after following other references.
+    #[display("{}", display_chain(chain, source))]
+    Chain {
+        /// Each reference followed, in order, before the error occurred.
+        chain: Vec<String>,
+
+        /// Error resolving the final reference.
+        source: Box<RefError>,
+    },
+}
+
+impl RefError {
+    /// Returns the underlying error, skipping any chain of followed references.
+    pub fn root(&self) -> &RefError {
+        match self {
+            RefError::Chain { source, .. } => source,
+            err => err,
+        }
+    }
+
+    /// Records that this error occurred while resolving the target of `ref_path`.
+    ///
+    /// Cycle errors already hold every reference followed and are returned unchanged.
+    pub(crate) fn via(self, ref_path: &str) -> Self {
+        match self {
+            RefError::Cycle(_) => self,
+
+            RefError::Chain { mut chain, source } => {
+                chain.insert(0, ref_path.to_owned());
+                RefError::Chain { chain, source }
+            }
+
+            err => RefError::Chain {
+                chain: vec![ref_path.to_owned()],
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
+fn display_chain(chain: &[String], source: &RefError) -> String {
+    let chain = chain.join(" -> ");
+
+    match source {
+        RefError::Unresolvable(path) => format!("Unresolvable path: {chain} -> {path}"),
+        err => format!("{err} (via {chain})"),
+    }
 }
 
 /// Component type of a reference.
@@ -83,6 +141,9 @@ pub enum RefType {
     /// Link component type.
     Link,
 
+    /// Path item component type.
+    PathItem,
+
     /// Callback component type.
     Callback,
 }
@@ -100,6 +161,7 @@ impl FromStr for RefType {
             "headers" => Self::Header,
             "securitySchemes" => Self::SecurityScheme,
             "links" => Self::Link,
+            "pathItems" => Self::PathItem,
             "callbacks" => Self::Callback,
             typ => return Err(RefError::UnknownType(typ.to_owned())),
         })
@@ -123,7 +185,9 @@ impl FromStr for Ref {
     type Err = RefError;
 
     fn from_str(path: &str) -> Result<Self, Self::Err> {
-        let parts = RE_REF.captures(path).unwrap();
+        let parts = RE_REF
+            .captures(path)
+            .ok_or_else(|| RefError::Unresolvable(path.to_owned()))?;
 
         trace!("creating Ref: {}/{}", &parts["type"], &parts["name"]);
 
@@ -135,6 +199,56 @@ impl FromStr for Ref {
     }
 }
 
+/// Resolves a reference to a component of type `kind`, found in `spec` using `get`.
+///
+/// Any chain of references between components is followed until reaching an inline object,
+/// returning [`RefError::Cycle`] if a reference repeats.
+pub(crate) fn resolve_component<'a, T: Clone + 'a>(
+    spec: &'a Spec,
+    path: &str,
+    kind: RefType,
+    get: impl Fn(&'a Components, &str) -> Option<&'a ObjectOrReference<T>>,
+) -> Result<T, RefError> {
+    let mut followed = Vec::<String>::new();
+    let mut current = path.to_owned();
+
+    loop {
+        if followed.contains(&current) {
+            followed.push(current);
+            return Err(RefError::Cycle(followed));
+        }
+
+        let target = current.parse::<Ref>().and_then(|refpath| {
+            if refpath.kind != kind {
+                return Err(RefError::MismatchedType(refpath.kind, kind));
+            }
+
+            spec.components
+                .as_ref()
+                .and_then(|cs| get(cs, &refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(current.clone()))
+        });
+
+        match target {
+            Ok(ObjectOrReference::Object(obj)) => return Ok(obj.clone()),
+
+            Ok(ObjectOrReference::Ref { ref_path }) => {
+                followed.push(current);
+                current = ref_path.clone();
+            }
+
+            Err(err) if followed.is_empty() => return Err(err),
+
+            Err(err) => {
+                return Err(RefError::Chain {
+                    chain: followed,
+                    source: Box::new(err),
+                })
+            }
+        }
+    }
+}
+
 /// Find an object from a reference path (`$ref`).
 ///
 /// Implemented for object types which can be shared via a spec's `components` object.
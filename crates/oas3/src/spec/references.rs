@@ -0,0 +1,201 @@
+//! Reverse lookup of references.
+
+use serde_json::Value;
+
+use super::{schema_scope::reference_targets, Spec};
+use crate::json_pointer;
+
+impl Spec {
+    /// Returns JSON pointers to every reference object in the document whose `$ref` points to
+    /// `pointer` or to a location within it.
+    ///
+    /// For example, references to `#/components/schemas/User/properties/name` are included when
+    /// looking up `#/components/schemas/User`. Only direct references are returned; places that use
+    /// the target through another component can be found by looking up that component in turn.
+    ///
+    /// References to a schema by its `$id` or to an anchor are included when the schema they resolve
+    /// to is at or within `pointer`.
+    pub fn references_to(&self, pointer: &str) -> Vec<String> {
+        let doc = serde_json::to_value(self).expect("specs should always serialize to JSON");
+
+        let mut refs = vec![];
+        find_refs(&doc, "#", pointer, &mut refs);
+
+        let target = pointer.strip_prefix('#').unwrap_or(pointer);
+
+        for (location, resolved) in reference_targets(self) {
+            let location = format!("#{location}");
+
+            if is_within(&resolved, target) && !refs.contains(&location) {
+                refs.push(location);
+            }
+        }
+
+        refs
+    }
+}
+
+/// Returns `true` if `path` is `target` or a location within it.
+fn is_within(path: &str, target: &str) -> bool {
+    path.strip_prefix(target)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+fn find_refs(value: &Value, ptr: &str, target: &str, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::String(ref_path)) = obj.get("$ref") {
+                if is_within(ref_path, target) {
+                    refs.push(ptr.to_owned());
+                }
+            }
+
+            for (key, value) in obj {
+                find_refs(value, &json_pointer::append(ptr, key), target, refs);
+            }
+        }
+
+        Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                find_refs(
+                    item,
+                    &json_pointer::append(ptr, &idx.to_string()),
+                    target,
+                    refs,
+                );
+            }
+        }
+
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_to() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /users:
+                post:
+                  requestBody:
+                    content:
+                      application/json:
+                        schema:
+                          $ref: '#/components/schemas/User'
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            type: array
+                            items:
+                              $ref: '#/components/schemas/User'
+            components:
+              schemas:
+                User:
+                  type: object
+                  properties:
+                    name: { type: string }
+                Team:
+                  type: object
+                  properties:
+                    lead:
+                      $ref: '#/components/schemas/User/properties/name'
+                    members:
+                      $ref: '#/components/schemas/Users'
+                Users:
+                  type: array
+        "})
+        .unwrap();
+
+        let mut refs = spec.references_to("#/components/schemas/User");
+        refs.sort_unstable();
+
+        assert_eq!(
+            refs,
+            [
+                "#/components/schemas/Team/properties/lead",
+                "#/paths/~1users/post/requestBody/content/application~1json/schema",
+                "#/paths/~1users/post/responses/200/content/application~1json/schema/items",
+            ],
+        );
+
+        assert_eq!(
+            spec.references_to("#/components/schemas/Users"),
+            ["#/components/schemas/Team/properties/members"],
+        );
+        assert!(spec.references_to("#/components/schemas/Team").is_empty());
+    }
+
+    #[test]
+    fn references_by_id_and_anchor() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                get:
+                  parameters:
+                    - name: tag
+                      in: query
+                      schema:
+                        $ref: '#tag'
+                  responses:
+                    '200':
+                      description: OK
+                      content:
+                        application/json:
+                          schema:
+                            $ref: 'https://example.com/pet'
+            components:
+              schemas:
+                Pet:
+                  $id: https://example.com/pet
+                  type: object
+                  properties:
+                    owner:
+                      $ref: owner#/properties/name
+                    self:
+                      $ref: '#'
+                Owner:
+                  $id: https://example.com/owner
+                  type: object
+                  properties:
+                    name: { type: string }
+                Tag:
+                  $anchor: tag
+                  type: string
+        "})
+        .unwrap();
+
+        let mut refs = spec.references_to("#/components/schemas/Pet");
+        refs.sort_unstable();
+
+        assert_eq!(
+            refs,
+            [
+                "#/components/schemas/Pet/properties/self",
+                "#/paths/~1pets/get/responses/200/content/application~1json/schema",
+            ],
+        );
+
+        assert_eq!(
+            spec.references_to("#/components/schemas/Owner"),
+            ["#/components/schemas/Pet/properties/owner"],
+        );
+        assert_eq!(
+            spec.references_to("#/components/schemas/Tag"),
+            ["#/paths/~1pets/get/parameters/0/schema"],
+        );
+    }
+}
@@ -2,7 +2,10 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::{FromRef, MediaType, Ref, RefError, RefType, Spec};
+use super::{
+    find_media_type, resolve_component, Error, Example, FromRef, MediaType, ObjectSchema, RefError,
+    RefType, Spec,
+};
 
 /// Describes a single request body.
 ///
@@ -33,22 +36,128 @@ pub struct RequestBody {
     pub required: Option<bool>,
 }
 
+impl RequestBody {
+    /// Resolves and returns the schema for the given `media_type`.
+    ///
+    /// Returns `None` if the media type is not present or declares no schema.
+    pub fn schema(&self, media_type: &str, spec: &Spec) -> Result<Option<ObjectSchema>, Error> {
+        self.content
+            .get(media_type)
+            .and_then(|mt| mt.schema.as_ref())
+            .map(|schema| schema.resolve(spec).map_err(Error::Ref))
+            .transpose()
+    }
+
+    /// Resolves and returns the schema for the entry of `content` that best matches `media_type`.
+    ///
+    /// Unlike [`schema()`](Self::schema), media type parameters and case are ignored and media type
+    /// ranges are considered; see [`find_media_type()`].
+    ///
+    /// Returns `None` if no entry matches or the matching entry declares no schema.
+    pub fn schema_for(&self, media_type: &str, spec: &Spec) -> Result<Option<ObjectSchema>, Error> {
+        find_media_type(&self.content, media_type)
+            .and_then(|mt| mt.schema.as_ref())
+            .map(|schema| schema.resolve(spec).map_err(Error::Ref))
+            .transpose()
+    }
+
+    /// Returns true if the request body is required, defaulting to false.
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(false)
+    }
+
+    /// Resolves and returns the examples for the given `media_type`, keyed by name.
+    ///
+    /// Returns an empty map if the media type is not present. Also see [`MediaType::examples()`].
+    pub fn examples(&self, media_type: &str, spec: &Spec) -> BTreeMap<String, Example> {
+        self.content
+            .get(media_type)
+            .map(|mt| mt.examples(spec))
+            .unwrap_or_default()
+    }
+}
+
 impl FromRef for RequestBody {
-    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError>
-    where
-        Self: Sized,
-    {
-        let refpath = path.parse::<Ref>()?;
-
-        match refpath.kind {
-            RefType::RequestBody => spec
-                .components
-                .as_ref()
-                .and_then(|cs| cs.request_bodies.get(&refpath.name))
-                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
-                .and_then(|oor| oor.resolve(spec)),
-
-            typ => Err(RefError::MismatchedType(typ, RefType::RequestBody)),
-        }
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        resolve_component(spec, path, RefType::RequestBody, |cs, name| {
+            cs.request_bodies.get(name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_and_examples() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  type: object
+              examples:
+                Ferris:
+                  value: { name: ferris }
+        "})
+        .unwrap();
+
+        let body = serde_yml::from_str::<RequestBody>(indoc::indoc! {"
+            content:
+              application/json:
+                schema:
+                  $ref: '#/components/schemas/Pet'
+                examples:
+                  ferris:
+                    $ref: '#/components/examples/Ferris'
+              text/plain: {}
+        "})
+        .unwrap();
+
+        let schema = body.schema("application/json", &spec).unwrap();
+        assert_eq!(schema, Some(ObjectSchema::object()));
+        assert!(body.schema("text/plain", &spec).unwrap().is_none());
+        assert!(body.schema("application/xml", &spec).unwrap().is_none());
+
+        let examples = body.examples("application/json", &spec);
+        assert_eq!(
+            examples["ferris"].value,
+            Some(serde_json::json!({ "name": "ferris" })),
+        );
+        assert!(body.examples("application/xml", &spec).is_empty());
+    }
+
+    #[test]
+    fn schema_for_and_required() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+        "})
+        .unwrap();
+
+        let body = serde_yml::from_str::<RequestBody>(indoc::indoc! {"
+            required: true
+            content:
+              application/json:
+                schema: { type: object }
+              text/*:
+                schema: { type: string }
+        "})
+        .unwrap();
+
+        assert!(body.is_required());
+        assert!(!RequestBody::default().is_required());
+
+        let schema = body.schema_for("application/json; charset=utf-8", &spec);
+        assert_eq!(schema.unwrap(), Some(ObjectSchema::object()));
+        assert!(body.schema("text/plain", &spec).unwrap().is_none());
+        assert!(body.schema_for("text/plain", &spec).unwrap().is_some());
+        assert!(body.schema_for("image/png", &spec).unwrap().is_none());
     }
 }
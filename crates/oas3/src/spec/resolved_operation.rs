@@ -0,0 +1,166 @@
+use http::Method;
+
+use super::{Operation, Parameter, RefError, SecurityRequirement, Server, Spec};
+
+/// An operation bundled with the settings it inherits from its path item and the spec root.
+///
+/// Returned by [`Spec::resolved_operation()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedOperation {
+    /// Path of the operation.
+    pub path: String,
+
+    /// Method of the operation.
+    pub method: Method,
+
+    /// The operation itself.
+    pub operation: Operation,
+
+    /// Resolved path-level and operation-level parameters.
+    ///
+    /// See [`Operation::merged_parameters()`].
+    pub parameters: Vec<Parameter>,
+
+    /// Security requirements that apply to the operation.
+    ///
+    /// See [`Spec::effective_security()`].
+    pub security: Vec<SecurityRequirement>,
+
+    /// Servers that service the operation.
+    ///
+    /// See [`Operation::effective_servers()`].
+    pub servers: Vec<Server>,
+}
+
+impl Spec {
+    /// Returns the operation at `method` and `path` along with its merged parameters, effective
+    /// security, and effective servers.
+    ///
+    /// The path item's `$ref` is followed, if set. Returns `None` if the operation does not exist,
+    /// or an error if the path item or any parameter fails to resolve.
+    pub fn resolved_operation(
+        &self,
+        method: &Method,
+        path: &str,
+    ) -> Result<Option<ResolvedOperation>, RefError> {
+        let Some(item) = self.paths.as_ref().and_then(|paths| paths.get(path)) else {
+            return Ok(None);
+        };
+
+        let item = item.resolve(self)?;
+
+        let Some((_, operation)) = item.methods().into_iter().find(|(m, _)| m == method) else {
+            return Ok(None);
+        };
+
+        Ok(Some(ResolvedOperation {
+            path: path.to_owned(),
+            method: method.clone(),
+            operation: operation.clone(),
+            parameters: operation.merged_parameters(&item, self)?,
+            security: operation
+                .security
+                .clone()
+                .unwrap_or_else(|| self.security.clone()),
+            servers: operation.effective_servers(&item, self),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::ParameterIn;
+
+    #[test]
+    fn resolved_operation() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            servers:
+              - url: https://api.example.com
+            security:
+              - apiKey: []
+            paths:
+              /pets/{id}:
+                servers:
+                  - url: https://pets.example.com
+                parameters:
+                  - name: id
+                    in: path
+                    required: true
+                    description: path-level
+                  - $ref: '#/components/parameters/Trace'
+                get:
+                  security: []
+                  parameters:
+                    - name: id
+                      in: path
+                      required: true
+                      description: operation-level
+                    - name: id
+                      in: query
+              /shared:
+                $ref: '#/components/pathItems/Shared'
+              /broken:
+                get:
+                  parameters:
+                    - $ref: '#/components/parameters/Missing'
+            components:
+              parameters:
+                Trace:
+                  name: X-Trace
+                  in: header
+              pathItems:
+                Shared:
+                  post: {}
+        "})
+        .unwrap();
+
+        let resolved = spec
+            .resolved_operation(&Method::GET, "/pets/{id}")
+            .unwrap()
+            .unwrap();
+
+        let params = resolved
+            .parameters
+            .iter()
+            .map(|param| (param.name.as_str(), param.location))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            [
+                ("id", ParameterIn::Path),
+                ("X-Trace", ParameterIn::Header),
+                ("id", ParameterIn::Query),
+            ],
+        );
+        assert_eq!(
+            resolved.parameters[0].description.as_deref(),
+            Some("operation-level"),
+        );
+        assert!(resolved.security.is_empty());
+        assert_eq!(resolved.servers[0].url, "https://pets.example.com");
+
+        let resolved = spec
+            .resolved_operation(&Method::POST, "/shared")
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.path, "/shared");
+        assert_eq!(resolved.security, spec.security);
+        assert_eq!(resolved.servers[0].url, "https://api.example.com");
+
+        assert!(spec
+            .resolved_operation(&Method::POST, "/pets/{id}")
+            .unwrap()
+            .is_none());
+        assert!(spec
+            .resolved_operation(&Method::GET, "/missing")
+            .unwrap()
+            .is_none());
+        spec.resolved_operation(&Method::GET, "/broken")
+            .unwrap_err();
+    }
+}
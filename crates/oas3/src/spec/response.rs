@@ -3,8 +3,8 @@ use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    spec_extensions, FromRef, Header, Link, MediaType, ObjectOrReference, Ref, RefError, RefType,
-    Spec,
+    resolve_component, spec_extensions, FromRef, Header, Link, MediaType, ObjectOrReference,
+    RefError, RefType, Spec,
 };
 
 /// Describes a single response from an API Operation, including design-time, static `links`
@@ -50,19 +50,140 @@ pub struct Response {
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
+impl Response {
+    /// Resolves and returns map of this response's headers, keyed by header name.
+    pub fn headers_resolved(&self, spec: &Spec) -> Result<BTreeMap<String, Header>, RefError> {
+        self.headers
+            .iter()
+            .map(|(name, oor)| oor.resolve(spec).map(|header| (name.clone(), header)))
+            .collect()
+    }
+
+    /// Resolves and returns map of this response's links, keyed by link name.
+    pub fn links_resolved(&self, spec: &Spec) -> Result<BTreeMap<String, Link>, RefError> {
+        self.links
+            .iter()
+            .map(|(name, oor)| oor.resolve(spec).map(|link| (name.clone(), link)))
+            .collect()
+    }
+}
+
 impl FromRef for Response {
     fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
-        let refpath = path.parse::<Ref>()?;
-
-        match refpath.kind {
-            RefType::Response => spec
-                .components
-                .as_ref()
-                .and_then(|cs| cs.responses.get(&refpath.name))
-                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
-                .and_then(|oor| oor.resolve(spec)),
-
-            typ => Err(RefError::MismatchedType(typ, RefType::Response)),
-        }
+        resolve_component(spec, path, RefType::Response, |cs, name| {
+            cs.responses.get(name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_resolved() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Users
+              version: 0.0.0
+            paths:
+              /users:
+                post:
+                  responses:
+                    '201':
+                      description: Created
+                      links:
+                        GetUser:
+                          $ref: '#/components/links/GetUserById'
+                        DeleteUser:
+                          operationId: deleteUser
+            components:
+              links:
+                GetUserById:
+                  operationId: getUser
+                  parameters:
+                    userId: $response.body#/id
+        "})
+        .unwrap();
+
+        let op = spec.operation(&http::Method::POST, "/users").unwrap();
+        let res = &op.responses(&spec)["201"];
+        let links = res.links_resolved(&spec).unwrap();
+
+        assert_eq!(links.len(), 2);
+        assert!(matches!(
+            &links["GetUser"],
+            Link::Id { operation_id, parameters, .. }
+                if operation_id == "getUser" && parameters["userId"] == "$response.body#/id"
+        ));
+        assert!(matches!(
+            &links["DeleteUser"],
+            Link::Id { operation_id, .. } if operation_id == "deleteUser"
+        ));
+    }
+
+    #[test]
+    fn headers_resolved() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Users
+              version: 0.0.0
+            paths:
+              /users:
+                get:
+                  responses:
+                    '200':
+                      description: OK
+                      headers:
+                        X-Rate-Limit:
+                          $ref: '#/components/headers/RateLimit'
+                        X-Request-Id:
+                          required: true
+                          schema:
+                            type: string
+            components:
+              headers:
+                RateLimit:
+                  schema:
+                    type: integer
+        "})
+        .unwrap();
+
+        let op = spec.operation(&http::Method::GET, "/users").unwrap();
+        let res = &op.responses(&spec)["200"];
+        let headers = res.headers_resolved(&spec).unwrap();
+
+        assert_eq!(headers.len(), 2);
+        assert!(headers["X-Rate-Limit"].schema.is_some());
+        assert_eq!(headers["X-Request-Id"].required, Some(true));
+    }
+
+    #[test]
+    fn links_resolved_unresolvable() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Users
+              version: 0.0.0
+            paths: {}
+        "})
+        .unwrap();
+
+        let res = Response {
+            links: BTreeMap::from([(
+                "Missing".to_owned(),
+                ObjectOrReference::Ref {
+                    ref_path: "#/components/links/Missing".to_owned(),
+                },
+            )]),
+            ..Response::default()
+        };
+
+        assert_eq!(
+            res.links_resolved(&spec).unwrap_err(),
+            RefError::Unresolvable("#/components/links/Missing".to_owned()),
+        );
     }
 }
@@ -0,0 +1,101 @@
+use std::{fmt, str::FromStr};
+
+use derive_more::derive::{Display, Error};
+use http::StatusCode;
+
+/// Response key parse error.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+#[display("Invalid response key: {}", _0)]
+pub struct ResponseKeyError(#[error(not(source))] String);
+
+/// Key of an operation's responses map.
+///
+/// Keys order by precedence: exact status codes, then ranges, then `default`.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#responses-object>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ResponseKey {
+    /// A single status code, e.g. `200`.
+    Status(StatusCode),
+
+    /// A range of status codes, e.g. `4XX`, holding the leading digit.
+    Range(u8),
+
+    /// Fallback for status codes not covered by other keys.
+    Default,
+}
+
+impl ResponseKey {
+    /// Returns true if a response with `status` is described by this key.
+    pub fn matches(&self, status: StatusCode) -> bool {
+        match self {
+            Self::Status(code) => *code == status,
+            Self::Range(class) => status.as_u16() / 100 == u16::from(*class),
+            Self::Default => true,
+        }
+    }
+}
+
+impl FromStr for ResponseKey {
+    type Err = ResponseKeyError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        if key == "default" {
+            return Ok(Self::Default);
+        }
+
+        match key.as_bytes() {
+            [class @ b'1'..=b'5', b'X' | b'x', b'X' | b'x'] => Ok(Self::Range(class - b'0')),
+
+            [b'0'..=b'9', b'0'..=b'9', b'0'..=b'9'] => StatusCode::from_bytes(key.as_bytes())
+                .map(Self::Status)
+                .map_err(|_| ResponseKeyError(key.to_owned())),
+
+            _ => Err(ResponseKeyError(key.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ResponseKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status(code) => write!(f, "{}", code.as_u16()),
+            Self::Range(class) => write!(f, "{class}XX"),
+            Self::Default => f.write_str("default"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        for (key, parsed) in [
+            ("200", ResponseKey::Status(StatusCode::OK)),
+            ("4XX", ResponseKey::Range(4)),
+            ("default", ResponseKey::Default),
+        ] {
+            assert_eq!(key.parse::<ResponseKey>().unwrap(), parsed);
+            assert_eq!(parsed.to_string(), key);
+        }
+
+        assert_eq!("5xx".parse::<ResponseKey>().unwrap(), ResponseKey::Range(5));
+
+        for key in ["6XX", "20", "2000", "abc", "Default"] {
+            assert!(
+                key.parse::<ResponseKey>().is_err(),
+                "{key} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn matches() {
+        assert!(ResponseKey::Range(2).matches(StatusCode::CREATED));
+        assert!(!ResponseKey::Range(2).matches(StatusCode::NOT_FOUND));
+        assert!(!ResponseKey::Status(StatusCode::OK).matches(StatusCode::CREATED));
+        assert!(ResponseKey::Default.matches(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+}
@@ -6,8 +6,9 @@ use derive_more::derive::{Display, Error};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use super::{
-    discriminator::Discriminator, spec_extensions, FromRef, ObjectOrReference, Ref, RefError,
-    RefType, Spec,
+    discriminator::Discriminator,
+    schema_scope::{absolute_ref, keep_base, keep_subschema_base, SchemaResolver},
+    spec_extensions, FromRef, ObjectOrReference, OrderedMap, RefError, Spec, Xml,
 };
 
 /// Schema errors.
@@ -24,6 +25,30 @@ pub enum Error {
     /// Required property list specified for a non-object schema.
     #[display("Required property list specified for a non-object schema")]
     RequiredSpecifiedOnNonObject,
+
+    /// Pattern is not a valid regular expression.
+    #[display("Invalid pattern: {}", _0)]
+    InvalidPattern(#[error(not(source))] String),
+
+    /// Enum value does not conform to the schema's type or constraints.
+    #[display("Enum value {} does not conform to schema", _0)]
+    InvalidEnumValue(#[error(not(source))] serde_json::Value),
+
+    /// Referenced schema could not be resolved.
+    #[display("Reference error: {}", _0)]
+    Ref(RefError),
+
+    /// Members of an `allOf` composition declare types with no overlap.
+    #[display("Conflicting types in allOf composition: {:?} and {:?}", _0, _1)]
+    ConflictingTypes(TypeSet, TypeSet),
+
+    /// Members of an `allOf` composition declare values for a keyword that cannot both hold.
+    #[display("Conflicting {} in allOf composition: {} and {}", _0, _1, _2)]
+    ConflictingKeyword(&'static str, String, String),
+
+    /// Schema references itself in a way that cannot be instantiated.
+    #[display("Circular schema reference: {}", _0)]
+    CircularReference(#[error(not(source))] String),
 }
 
 /// Single schema type.
@@ -63,7 +88,35 @@ pub enum TypeSet {
     Multiple(Vec<Type>),
 }
 
+impl Type {
+    /// Returns `true` if `value` is an instance of this type.
+    pub fn matches(self, value: &serde_json::Value) -> bool {
+        use serde_json::Value;
+
+        match (self, value) {
+            (Type::Boolean, Value::Bool(_))
+            | (Type::Number, Value::Number(_))
+            | (Type::String, Value::String(_))
+            | (Type::Array, Value::Array(_))
+            | (Type::Object, Value::Object(_))
+            | (Type::Null, Value::Null) => true,
+            (Type::Integer, Value::Number(num)) => {
+                num.is_i64() || num.is_u64() || num.as_f64().is_some_and(|num| num.fract() == 0.0)
+            }
+            _ => false,
+        }
+    }
+}
+
 impl TypeSet {
+    /// Returns `true` if `value` is an instance of any type in this type-set.
+    pub fn matches(&self, value: &serde_json::Value) -> bool {
+        match self {
+            TypeSet::Single(type_) => type_.matches(value),
+            TypeSet::Multiple(type_set) => type_set.iter().any(|type_| type_.matches(value)),
+        }
+    }
+
     /// Returns `true` if this type-set contains the given type.
     pub fn contains(&self, type_: Type) -> bool {
         match self {
@@ -72,6 +125,68 @@ impl TypeSet {
         }
     }
 
+    /// Returns the types present in both this type-set and `other`.
+    ///
+    /// Returns an error if no types overlap.
+    fn intersect(&self, other: &TypeSet) -> Result<TypeSet, Error> {
+        let types = match self {
+            TypeSet::Single(type_) => vec![*type_],
+            TypeSet::Multiple(types) => types.clone(),
+        };
+
+        let mut common = types
+            .into_iter()
+            .filter(|type_| other.contains(*type_))
+            .collect::<Vec<_>>();
+
+        match common.len() {
+            0 => Err(Error::ConflictingTypes(self.clone(), other.clone())),
+            1 => Ok(TypeSet::Single(common.remove(0))),
+            _ => Ok(TypeSet::Multiple(common)),
+        }
+    }
+
+    /// Returns `true` if this type-set contains the `null` type.
+    pub fn is_nullable(&self) -> bool {
+        self.contains(Type::Null)
+    }
+
+    /// Returns this type-set with the `null` type added.
+    ///
+    /// A single type `T` becomes `[T, "null"]`. Type-sets already containing `null` are returned
+    /// unchanged.
+    pub fn with_null(self) -> TypeSet {
+        match self {
+            TypeSet::Single(Type::Null) => TypeSet::Single(Type::Null),
+            TypeSet::Single(type_) => TypeSet::Multiple(vec![type_, Type::Null]),
+            TypeSet::Multiple(mut types) => {
+                if !types.contains(&Type::Null) {
+                    types.push(Type::Null);
+                }
+                TypeSet::Multiple(types)
+            }
+        }
+    }
+
+    /// Returns this type-set with the `null` type removed.
+    ///
+    /// If a single type remains, it is returned as [`TypeSet::Single`]. Removing `null` from a
+    /// type-set containing only `null` results in an empty (unsatisfiable) type-set.
+    pub fn without_null(self) -> TypeSet {
+        let mut types = match self {
+            TypeSet::Single(Type::Null) => vec![],
+            TypeSet::Single(type_) => return TypeSet::Single(type_),
+            TypeSet::Multiple(types) => types,
+        };
+
+        types.retain(|type_| *type_ != Type::Null);
+
+        match types.len() {
+            1 => TypeSet::Single(types.remove(0)),
+            _ => TypeSet::Multiple(types),
+        }
+    }
+
     /// Returns `true` if this type-set is `object` or `[object, 'null']`.
     pub fn is_object_or_nullable_object(&self) -> bool {
         match self {
@@ -110,6 +225,46 @@ impl TypeSet {
 /// [JSON Schema Validation]: https://tools.ietf.org/html/draft-wright-json-schema-validation-00
 #[derive(Clone, Debug, PartialEq, Default, Deserialize, Serialize)]
 pub struct ObjectSchema {
+    // #########################################################################
+    // The JSON Schema Core Vocabulary
+    // https://json-schema.org/draft/2020-12/json-schema-core#section-8
+    // #########################################################################
+
+    //
+    /// Identifier of the dialect this schema is written in, as a URI.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-the-schema-keyword>.
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema_dialect: Option<String>,
+
+    /// Canonical URI of this schema, which also serves as the base URI for relative references
+    /// within it.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-the-id-keyword>.
+    #[serde(rename = "$id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Plain name fragment identifying this schema, for use in references.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#section-8.2.2>.
+    #[serde(rename = "$anchor", skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+
+    /// Plain name fragment identifying this schema, which `$dynamicRef` resolves against at
+    /// evaluation time.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#section-8.2.3.2>.
+    #[serde(rename = "$dynamicAnchor", skip_serializing_if = "Option::is_none")]
+    pub dynamic_anchor: Option<String>,
+
+    /// Reference to a schema, resolved dynamically against `$dynamicAnchor`s at evaluation time.
+    ///
+    /// These references are not followed by this library.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#section-8.2.3.2>.
+    #[serde(rename = "$dynamicRef", skip_serializing_if = "Option::is_none")]
+    pub dynamic_ref: Option<String>,
+
     // #########################################################################
     // Keywords for Applying Subschemas With Logic
     // https://json-schema.org/draft/2020-12/json-schema-core#name-keywords-for-applying-subsch
@@ -124,7 +279,7 @@ pub struct ObjectSchema {
     ///
     /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-allof>.
     #[serde(rename = "allOf", default, skip_serializing_if = "Vec::is_empty")]
-    pub all_of: Vec<ObjectOrReference<ObjectSchema>>,
+    pub all_of: Vec<Schema>,
 
     /// An instance validates successfully against this keyword if it validates successfully against
     /// at least one schema defined by this keyword's value.
@@ -134,7 +289,7 @@ pub struct ObjectSchema {
     ///
     /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-anyof>.
     #[serde(rename = "anyOf", default, skip_serializing_if = "Vec::is_empty")]
-    pub any_of: Vec<ObjectOrReference<ObjectSchema>>,
+    pub any_of: Vec<Schema>,
 
     /// An instance validates successfully against this keyword if it validates successfully against
     /// exactly one schema defined by this keyword's value.
@@ -144,10 +299,14 @@ pub struct ObjectSchema {
     ///
     /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-oneof>.
     #[serde(rename = "oneOf", default, skip_serializing_if = "Vec::is_empty")]
-    pub one_of: Vec<ObjectOrReference<ObjectSchema>>,
+    pub one_of: Vec<Schema>,
 
-    // TODO: missing fields
-    // - not
+    /// An instance is valid against this keyword if it fails to validate successfully against the
+    /// schema defined by this keyword.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-not>.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Schema>,
 
     // #########################################################################
     // TODO: missing concept
@@ -163,11 +322,14 @@ pub struct ObjectSchema {
     //
     /// This keyword applies its subschema to all instance array elements.
     ///
-    /// Omitting this keyword has the same assertion behavior as an empty schema.
+    /// Omitting this keyword has the same assertion behavior as an empty schema. Inline or
+    /// referenced item MUST be of a [Schema Object] or a boolean.
     ///
     /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-items>.
+    ///
+    /// [Schema Object]: https://spec.openapis.org/oas/v3.1.0#schema-object
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub items: Option<Box<ObjectOrReference<ObjectSchema>>>,
+    pub items: Option<Schema>,
 
     // TODO: missing fields
     // - prefixItems
@@ -186,8 +348,20 @@ pub struct ObjectSchema {
     /// Omitting this keyword has the same assertion behavior as an empty object.
     ///
     /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-properties>.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub properties: BTreeMap<String, ObjectOrReference<ObjectSchema>>,
+    #[serde(default, skip_serializing_if = "OrderedMap::is_empty")]
+    pub properties: OrderedMap<String, Schema>,
+
+    /// Schemas for object properties whose names match a regular expression, keyed by pattern.
+    ///
+    /// A property matching a pattern is not considered additional.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-core#name-patternproperties>.
+    #[serde(
+        rename = "patternProperties",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub pattern_properties: BTreeMap<String, Schema>,
 
     /// Schema for additional object properties.
     ///
@@ -422,6 +596,24 @@ pub struct ObjectSchema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
 
+    // #########################################################################
+    // A Vocabulary for the Contents of String-Encoded Data
+    // https://json-schema.org/draft/2020-12/json-schema-validation#name-a-vocabulary-for-the-conten
+    // #########################################################################
+
+    //
+    /// Encoding used to store binary data within the string, e.g. `base64`.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-validation#name-contentencoding>.
+    #[serde(rename = "contentEncoding", skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+
+    /// Media type of the string's contents, e.g. `image/png`.
+    ///
+    /// See <https://json-schema.org/draft/2020-12/json-schema-validation#name-contentmediatype>.
+    #[serde(rename = "contentMediaType", skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
+
     // #########################################################################
     // A Vocabulary for Basic Meta-Data Annotations
     // https://json-schema.org/draft/2020-12/json-schema-validation#name-a-vocabulary-for-basic-meta
@@ -507,6 +699,14 @@ pub struct ObjectSchema {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub discriminator: Option<Discriminator>,
 
+    /// Additional metadata describing the XML representation of this property.
+    ///
+    /// May only be used on property schemas. It has no effect on root schemas.
+    ///
+    /// See <https://spec.openapis.org/oas/v3.1.0#xml-object>.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
+
     /// A free-form property to include an example of an instance for this schema.
     ///
     /// To represent examples that cannot be naturally represented in JSON or YAML, a string value
@@ -538,30 +738,930 @@ pub struct ObjectSchema {
 }
 
 impl ObjectSchema {
+    fn of_type(type_: Type) -> Self {
+        Self {
+            schema_type: Some(TypeSet::Single(type_)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a schema of type `object`.
+    pub fn object() -> Self {
+        Self::of_type(Type::Object)
+    }
+
+    /// Returns a schema of type `string`.
+    pub fn string() -> Self {
+        Self::of_type(Type::String)
+    }
+
+    /// Returns a schema of type `integer`.
+    pub fn integer() -> Self {
+        Self::of_type(Type::Integer)
+    }
+
+    /// Returns a schema of type `number`.
+    pub fn number() -> Self {
+        Self::of_type(Type::Number)
+    }
+
+    /// Returns a schema of type `boolean`.
+    pub fn boolean() -> Self {
+        Self::of_type(Type::Boolean)
+    }
+
+    /// Returns a schema of type `array` whose elements match `items`.
+    pub fn array(items: impl Into<Schema>) -> Self {
+        Self {
+            items: Some(items.into()),
+            ..Self::of_type(Type::Array)
+        }
+    }
+
+    /// Adds a property to this schema, replacing any existing property with the same `name`.
+    pub fn with_property(mut self, name: impl Into<String>, schema: impl Into<Schema>) -> Self {
+        self.properties.insert(name.into(), schema.into());
+        self
+    }
+
+    /// Marks the given properties as required, in addition to any already required.
+    pub fn required(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        for name in names {
+            let name = name.into();
+
+            if !self.required.contains(&name) {
+                self.required.push(name);
+            }
+        }
+
+        self
+    }
+
+    /// Adds `null` to this schema's set of types.
+    ///
+    /// A schema with type `T` becomes `[T, "null"]`. Schemas without a type already admit `null`
+    /// and are left unchanged.
+    pub fn nullable(mut self) -> Self {
+        self.schema_type = self.schema_type.map(TypeSet::with_null);
+        self
+    }
+
     /// Returns true if [`Null`](Type::Null) appears in set of schema types, or None if unspecified.
     pub fn is_nullable(&self) -> Option<bool> {
-        Some(match self.schema_type.as_ref()? {
-            TypeSet::Single(type_) => *type_ == Type::Null,
-            TypeSet::Multiple(set) => set.contains(&Type::Null),
+        self.schema_type.as_ref().map(TypeSet::is_nullable)
+    }
+
+    /// Returns true if `contentEncoding` indicates the string holds encoded binary data.
+    ///
+    /// Recognized encodings are `binary` and the [RFC 4648] `base16`, `base32`, `base64` and
+    /// `base64url` encodings, compared case-insensitively.
+    ///
+    /// [RFC 4648]: https://www.rfc-editor.org/rfc/rfc4648
+    pub fn is_binary(&self) -> bool {
+        self.content_encoding.as_deref().is_some_and(|encoding| {
+            ["binary", "base16", "base32", "base64", "base64url"]
+                .iter()
+                .any(|binary| encoding.eq_ignore_ascii_case(binary))
+        })
+    }
+
+    /// Returns a suggested Rust type for values of this schema, based on its `type` and `format`.
+    ///
+    /// Strings with a binary [`contentEncoding`](Self::is_binary) are also mapped to `Vec<u8>`.
+    ///
+    /// Only the schema's first non-null type is considered, and nullability is not reflected in the
+    /// suggestion. Arrays and objects map to generic containers since their contents depend on
+    /// subschemas. Schemas without a type, and unknown formats, fall back to `serde_json::Value`
+    /// and the type's default mapping respectively.
+    pub fn suggested_rust_type(&self) -> &'static str {
+        let type_ = match &self.schema_type {
+            Some(TypeSet::Single(type_)) => Some(*type_),
+            Some(TypeSet::Multiple(types)) => {
+                types.iter().find(|type_| **type_ != Type::Null).copied()
+            }
+            None => None,
+        };
+
+        match (type_, self.format.as_deref()) {
+            (Some(Type::String), Some("date-time")) => "chrono::DateTime<chrono::Utc>",
+            (Some(Type::String), Some("date")) => "chrono::NaiveDate",
+            (Some(Type::String), Some("time")) => "chrono::NaiveTime",
+            (Some(Type::String), Some("uuid")) => "uuid::Uuid",
+            (Some(Type::String), Some("uri")) => "url::Url",
+            (Some(Type::String), Some("byte" | "binary")) => "Vec<u8>",
+            (Some(Type::String), _) if self.is_binary() => "Vec<u8>",
+            (Some(Type::String), _) => "String",
+
+            (Some(Type::Integer), Some("int8")) => "i8",
+            (Some(Type::Integer), Some("int16")) => "i16",
+            (Some(Type::Integer), Some("int32")) => "i32",
+            (Some(Type::Integer), Some("uint8")) => "u8",
+            (Some(Type::Integer), Some("uint16")) => "u16",
+            (Some(Type::Integer), Some("uint32")) => "u32",
+            (Some(Type::Integer), Some("uint64")) => "u64",
+            (Some(Type::Integer), _) => "i64",
+
+            (Some(Type::Number), Some("float")) => "f32",
+            (Some(Type::Number), _) => "f64",
+
+            (Some(Type::Boolean), _) => "bool",
+            (Some(Type::Array), _) => "Vec<serde_json::Value>",
+            (Some(Type::Object), _) => "serde_json::Map<String, serde_json::Value>",
+            (Some(Type::Null), _) => "()",
+            (None, _) => "serde_json::Value",
+        }
+    }
+
+    /// Checks this schema for internal consistency.
+    ///
+    /// Currently checks that each `enum` value is an instance of the schema's `type` and satisfies
+    /// its length, item count, and numeric bounds and `multipleOf`, and that `patternProperties`
+    /// keys are valid regular expressions.
+    pub fn validate(&self) -> Result<(), Error> {
+        for pattern in self.pattern_properties.keys() {
+            if regex::Regex::new(pattern).is_err() {
+                return Err(Error::InvalidPattern(pattern.clone()));
+            }
+        }
+
+        for value in &self.enum_values {
+            if !self.admits(value) {
+                return Err(Error::InvalidEnumValue(value.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if `value` satisfies this schema's type, length, item count, numeric bounds,
+    /// and `multipleOf`.
+    fn admits(&self, value: &serde_json::Value) -> bool {
+        if let Some(type_set) = &self.schema_type {
+            if !type_set.matches(value) {
+                return false;
+            }
+        }
+
+        if let Some(val) = value.as_str() {
+            let len = val.chars().count() as u64;
+
+            if self.min_length.is_some_and(|min| len < min)
+                || self.max_length.is_some_and(|max| len > max)
+            {
+                return false;
+            }
+        }
+
+        if let Some(val) = value.as_f64() {
+            let bound = |num: &Option<serde_json::Number>| num.as_ref().and_then(|n| n.as_f64());
+
+            if bound(&self.minimum).is_some_and(|min| val < min)
+                || bound(&self.maximum).is_some_and(|max| val > max)
+                || bound(&self.exclusive_minimum).is_some_and(|min| val <= min)
+                || bound(&self.exclusive_maximum).is_some_and(|max| val >= max)
+            {
+                return false;
+            }
+
+            if let Some(step) = bound(&self.multiple_of) {
+                let quotient = val / step;
+
+                if (quotient - quotient.round()).abs() > 1e-9 {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(items) = value.as_array() {
+            let len = items.len() as u64;
+
+            if self.min_items.is_some_and(|min| len < min)
+                || self.max_items.is_some_and(|max| len > max)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns an example value that conforms to this schema.
+    ///
+    /// The schema's `example`, first `examples` entry, `default`, `const`, or first `enum` value is
+    /// returned if present. Otherwise, a minimal value is synthesized: the first branch of `oneOf`
+    /// or `anyOf`, or a value of the schema's (first non-null) type. Strings are empty unless
+    /// padded to `minLength` (capped at `maxLength`), numbers are the value closest to `0` within
+    /// the numeric bounds that is a multiple of `multipleOf`, arrays contain `minItems` items (at
+    /// least one unless `maxItems` is `0`, and at most one if `uniqueItems` is set), and objects contain only required properties merged
+    /// with any `allOf` members.
+    ///
+    /// References are resolved relative to the `$id` base URIs in effect; a reference cycle
+    /// encountered while synthesizing returns an error.
+    pub fn example_value(&self, spec: &Spec) -> Result<serde_json::Value, Error> {
+        let walk = Walk::new(spec);
+        self.example_value_inner(&walk, self.base_uri(None).as_deref(), &mut vec![])
+    }
+
+    fn example_value_inner(
+        &self,
+        walk: &Walk<'_>,
+        base: Option<&str>,
+        seen: &mut Vec<String>,
+    ) -> Result<serde_json::Value, Error> {
+        use serde_json::{Map, Value};
+
+        if let Some(value) = self
+            .example
+            .as_ref()
+            .or(self.examples.first())
+            .or(self.default.as_ref())
+            .or(self.const_value.as_ref())
+            .or(self.enum_values.first())
+        {
+            return Ok(value.clone());
+        }
+
+        if let Some(branch) = self.one_of.first().or(self.any_of.first()) {
+            return example_of(branch, walk, base, seen);
+        }
+
+        let type_ = match &self.schema_type {
+            Some(TypeSet::Single(type_)) => Some(*type_),
+            Some(TypeSet::Multiple(types)) => types
+                .iter()
+                .find(|type_| **type_ != Type::Null)
+                .or(types.first())
+                .copied(),
+            None if !self.properties.is_empty() || !self.all_of.is_empty() => Some(Type::Object),
+            None if self.items.is_some() => Some(Type::Array),
+            None => None,
+        };
+
+        Ok(match type_ {
+            Some(Type::Boolean) => Value::Bool(false),
+
+            Some(Type::String) => {
+                let len = self
+                    .min_length
+                    .unwrap_or(0)
+                    .min(self.max_length.unwrap_or(u64::MAX));
+                Value::String("a".repeat(len as usize))
+            }
+
+            Some(Type::Integer) => Value::from(self.example_number(true).ceil() as i64),
+
+            Some(Type::Number) => serde_json::Number::from_f64(self.example_number(false))
+                .map_or(Value::Null, Value::Number),
+
+            Some(Type::Array) => {
+                let mut len = self
+                    .min_items
+                    .unwrap_or(1)
+                    .max(1)
+                    .min(self.max_items.unwrap_or(u64::MAX));
+
+                // the synthesized items are all equal, so only one may be used when they must be
+                // unique; this favours a valid `uniqueItems` over reaching `minItems`
+                if self.unique_items == Some(true) {
+                    len = len.min(1);
+                }
+
+                match &self.items {
+                    Some(items @ Schema::Object(_)) if len > 0 => {
+                        let item = example_of(items, walk, base, seen)?;
+                        Value::Array(vec![item; len as usize])
+                    }
+                    _ => Value::Array(vec![]),
+                }
+            }
+
+            Some(Type::Object) => {
+                let mut obj = Map::new();
+
+                for member in &self.all_of {
+                    if let Value::Object(member) = example_of(member, walk, base, seen)? {
+                        obj.extend(member);
+                    }
+                }
+
+                for name in &self.required {
+                    let value = match self.properties.get(name) {
+                        Some(prop) => example_of(prop, walk, base, seen)?,
+                        None => Value::Null,
+                    };
+
+                    obj.insert(name.clone(), value);
+                }
+
+                Value::Object(obj)
+            }
+
+            Some(Type::Null) | None => Value::Null,
+        })
+    }
+
+    /// Returns the number closest to zero that satisfies this schema's numeric bounds and
+    /// `multipleOf`, or an integer if `integer` is set.
+    ///
+    /// Unsatisfiable constraints yield a best-effort value.
+    fn example_number(&self, integer: bool) -> f64 {
+        let bound = |num: &Option<serde_json::Number>| num.as_ref().and_then(|n| n.as_f64());
+
+        // the tightest bound on each side, and whether it is exclusive
+        let tightest = |inclusive: Option<f64>, exclusive: Option<f64>, lower: bool| match (
+            inclusive, exclusive,
+        ) {
+            (Some(inc), Some(exc)) if (inc > exc) == lower && inc != exc => Some((inc, false)),
+            (_, Some(exc)) => Some((exc, true)),
+            (inc, None) => inc.map(|inc| (inc, false)),
+        };
+
+        let lower = tightest(bound(&self.minimum), bound(&self.exclusive_minimum), true);
+        let upper = tightest(bound(&self.maximum), bound(&self.exclusive_maximum), false);
+
+        let above_lower = |num: f64| match lower {
+            Some((min, exclusive)) => num > min || (!exclusive && num == min),
+            None => true,
+        };
+        let below_upper = |num: f64| match upper {
+            Some((max, exclusive)) => num < max || (!exclusive && num == max),
+            None => true,
+        };
+
+        if above_lower(0.0) && below_upper(0.0) {
+            return 0.0;
+        }
+
+        let step = bound(&self.multiple_of).or(integer.then_some(1.0));
+
+        match (step, above_lower(0.0)) {
+            // closest multiple above the lower bound
+            (Some(step), false) => {
+                let min = lower.map_or(0.0, |(min, _)| min);
+                let num = (min / step).ceil() * step;
+
+                if above_lower(num) {
+                    num
+                } else {
+                    num + step
+                }
+            }
+
+            // closest multiple below the upper bound
+            (Some(step), true) => {
+                let max = upper.map_or(0.0, |(max, _)| max);
+                let num = (max / step).floor() * step;
+
+                if below_upper(num) {
+                    num
+                } else {
+                    num - step
+                }
+            }
+
+            (None, false) => match lower {
+                Some((min, false)) => min,
+                Some((min, true)) if below_upper(min + 1.0) => min + 1.0,
+                Some((min, true)) => (min + upper.map_or(min, |(max, _)| max)) / 2.0,
+                None => 0.0,
+            },
+
+            (None, true) => match upper {
+                Some((max, false)) => max,
+                Some((max, true)) if above_lower(max - 1.0) => max - 1.0,
+                Some((max, true)) => (max + lower.map_or(max, |(min, _)| min)) / 2.0,
+                None => 0.0,
+            },
+        }
+    }
+
+    /// Fills in `default` values for properties absent from `value`, recursively.
+    ///
+    /// Defaults are applied to nested objects, to array elements through `items`, and from each
+    /// member of an `allOf` composition. Present values are never overwritten.
+    ///
+    /// Returns an error if a subschema reference fails to resolve, or if a reference cycle would
+    /// apply defaults without end, e.g. when a schema is an `allOf` member of itself or a property
+    /// defaults to a value whose own defaults recurse through the same schema.
+    pub fn apply_defaults(&self, value: &mut serde_json::Value, spec: &Spec) -> Result<(), Error> {
+        let walk = Walk::new(spec);
+        self.apply_defaults_inner(value, &walk, self.base_uri(None).as_deref(), &mut vec![])
+    }
+
+    /// Applies defaults to `value`, guarding against reference cycles.
+    ///
+    /// `seen` holds the references followed since last descending into a value that was already
+    /// present. Recursion only ends once it runs out of such values, so revisiting a reference
+    /// before then would never end.
+    fn apply_defaults_inner(
+        &self,
+        value: &mut serde_json::Value,
+        walk: &Walk<'_>,
+        base: Option<&str>,
+        seen: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        use serde_json::Value;
+
+        for member in &self.all_of {
+            defaults_of(member, value, walk, base, seen)?;
+        }
+
+        match value {
+            Value::Object(obj) => {
+                for (name, prop) in &self.properties {
+                    let (resolved, _) = walk.resolve(prop, base)?;
+
+                    if obj.contains_key(name) {
+                        defaults_of(prop, obj.get_mut(name).unwrap(), walk, base, &mut vec![])?;
+                        continue;
+                    }
+
+                    let Some(default) = &resolved.default else {
+                        continue;
+                    };
+
+                    obj.insert(name.clone(), default.clone());
+                    defaults_of(prop, obj.get_mut(name).unwrap(), walk, base, seen)?;
+                }
+            }
+
+            Value::Array(items) => {
+                if let Some(schema) = &self.items {
+                    for item in items {
+                        defaults_of(schema, item, walk, base, &mut vec![])?;
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Merges the members of this schema's `allOf` composition into a single effective schema.
+    ///
+    /// Members are resolved and flattened recursively. The result has the union of all `properties`
+    /// and `required` lists, the intersection of all `type`s, and the most restrictive numeric,
+    /// length, item count, and property count constraints. Different schemas for the same property
+    /// or `patternProperties` pattern, and object `additionalProperties` schemas, are combined with
+    /// `allOf`; a `false` `additionalProperties` wins over any other. `multipleOf` values are combined into their least common multiple.
+    /// Other keywords are taken from this schema. The returned schema has no `allOf`.
+    ///
+    /// Members are resolved relative to the `$id` base URIs in effect. Subschemas taken from members
+    /// with a different base URI are given an `$id`, or have their references made absolute, so
+    /// that they keep their meaning.
+    ///
+    /// Returns an error if a member fails to resolve, the members' types do not overlap, or members
+    /// declare different `pattern`s, `format`s, or `multipleOf` values with no common multiple.
+    pub fn flatten_all_of(&self, spec: &Spec) -> Result<ObjectSchema, Error> {
+        let walk = Walk::new(spec);
+        self.flatten_all_of_inner(&walk, self.base_uri(None).as_deref())
+    }
+
+    fn flatten_all_of_inner(
+        &self,
+        walk: &Walk<'_>,
+        base: Option<&str>,
+    ) -> Result<ObjectSchema, Error> {
+        let mut flat = self.clone();
+        let members = std::mem::take(&mut flat.all_of);
+
+        for member in members {
+            let (member, member_base) = walk.resolve(&member, base)?;
+            let mut member = member.flatten_all_of_inner(walk, member_base.as_deref())?;
+
+            for prop in member
+                .properties
+                .values_mut()
+                .chain(member.pattern_properties.values_mut())
+                .chain(&mut member.additional_properties)
+                .chain(&mut member.not)
+            {
+                keep_subschema_base(prop, member_base.as_deref(), base);
+            }
+
+            flat.merge_constraints(member)?;
+        }
+
+        Ok(flat)
+    }
+
+    /// Merges the properties and constraints of `other` into this schema.
+    fn merge_constraints(&mut self, other: ObjectSchema) -> Result<(), Error> {
+        use std::cmp::Ordering;
+
+        self.schema_type = match (self.schema_type.take(), other.schema_type) {
+            (Some(ours), Some(theirs)) => Some(ours.intersect(&theirs)?),
+            (ours, theirs) => ours.or(theirs),
+        };
+
+        // both schemas apply to a property declared by each
+        for (name, theirs) in other.properties {
+            match self.properties.get_mut(&name) {
+                Some(ours) if *ours != theirs => {
+                    *ours = all_of_schemas(std::mem::replace(ours, true.into()), theirs);
+                }
+                Some(_) => {}
+                None => {
+                    self.properties.insert(name, theirs);
+                }
+            }
+        }
+
+        for name in other.required {
+            if !self.required.contains(&name) {
+                self.required.push(name);
+            }
+        }
+
+        // both schemas apply to properties matching a pattern declared by each
+        for (pattern, theirs) in other.pattern_properties {
+            match self.pattern_properties.remove(&pattern) {
+                Some(ours) if ours != theirs => {
+                    self.pattern_properties
+                        .insert(pattern, all_of_schemas(ours, theirs));
+                }
+                Some(ours) => {
+                    self.pattern_properties.insert(pattern, ours);
+                }
+                None => {
+                    self.pattern_properties.insert(pattern, theirs);
+                }
+            }
+        }
+
+        self.additional_properties = match (
+            self.additional_properties.take(),
+            other.additional_properties,
+        ) {
+            (Some(Schema::Boolean(BooleanSchema(false))), _)
+            | (_, Some(Schema::Boolean(BooleanSchema(false)))) => {
+                Some(Schema::Boolean(BooleanSchema(false)))
+            }
+            (None | Some(Schema::Boolean(BooleanSchema(true))), theirs) => theirs,
+            (ours, None | Some(Schema::Boolean(BooleanSchema(true)))) => ours,
+            (Some(ours), Some(theirs)) if ours == theirs => Some(ours),
+            (Some(ours), Some(theirs)) => Some(all_of_schemas(ours, theirs)),
+        };
+
+        // neither schema may hold
+        self.not = match (self.not.take(), other.not) {
+            (Some(ours), Some(theirs)) if ours != theirs => Some(
+                ObjectSchema {
+                    any_of: vec![ours, theirs],
+                    ..ObjectSchema::default()
+                }
+                .into(),
+            ),
+            (ours, theirs) => ours.or(theirs),
+        };
+
+        fn conflict(
+            keyword: &'static str,
+            ours: &Option<String>,
+            theirs: Option<String>,
+        ) -> Result<Option<String>, Error> {
+            match (ours, theirs) {
+                (Some(a), Some(b)) if *a != b => {
+                    Err(Error::ConflictingKeyword(keyword, a.clone(), b))
+                }
+                (Some(a), _) => Ok(Some(a.clone())),
+                (None, theirs) => Ok(theirs),
+            }
+        }
+
+        self.pattern = conflict("pattern", &self.pattern, other.pattern)?;
+        self.format = conflict("format", &self.format, other.format)?;
+
+        self.multiple_of = match (self.multiple_of.take(), other.multiple_of) {
+            (Some(ours), Some(theirs)) => {
+                Some(common_multiple(&ours, &theirs).ok_or_else(|| {
+                    Error::ConflictingKeyword("multipleOf", ours.to_string(), theirs.to_string())
+                })?)
+            }
+            (ours, theirs) => ours.or(theirs),
+        };
+
+        fn restrict<T>(ours: &mut Option<T>, theirs: Option<T>, keep: impl Fn(&T, &T) -> bool) {
+            match (ours.as_ref(), theirs) {
+                (Some(a), Some(b)) if !keep(a, &b) => *ours = Some(b),
+                (None, theirs) => *ours = theirs,
+                _ => {}
+            }
+        }
+
+        let cmp = |a: &serde_json::Number, b: &serde_json::Number| {
+            a.as_f64()
+                .partial_cmp(&b.as_f64())
+                .unwrap_or(Ordering::Equal)
+        };
+        let larger = |a: &serde_json::Number, b: &serde_json::Number| cmp(a, b).is_ge();
+        let smaller = |a: &serde_json::Number, b: &serde_json::Number| cmp(a, b).is_le();
+
+        restrict(&mut self.minimum, other.minimum, larger);
+        restrict(&mut self.exclusive_minimum, other.exclusive_minimum, larger);
+        restrict(&mut self.maximum, other.maximum, smaller);
+        restrict(
+            &mut self.exclusive_maximum,
+            other.exclusive_maximum,
+            smaller,
+        );
+
+        restrict(&mut self.min_length, other.min_length, |a, b| a >= b);
+        restrict(&mut self.max_length, other.max_length, |a, b| a <= b);
+        restrict(
+            &mut self.content_encoding,
+            other.content_encoding,
+            |_, _| true,
+        );
+        restrict(
+            &mut self.content_media_type,
+            other.content_media_type,
+            |_, _| true,
+        );
+
+        restrict(&mut self.min_items, other.min_items, |a, b| a >= b);
+        restrict(&mut self.max_items, other.max_items, |a, b| a <= b);
+        restrict(&mut self.min_properties, other.min_properties, |a, b| {
+            a >= b
+        });
+        restrict(&mut self.max_properties, other.max_properties, |a, b| {
+            a <= b
+        });
+
+        Ok(())
+    }
+
+    /// Resolves and returns the immediate members of this schema's `allOf`, `anyOf`, and `oneOf`
+    /// keywords.
+    ///
+    /// Members are resolved one level deep; references within the member schemas are left as-is.
+    pub fn composition_members(&self, spec: &Spec) -> Result<Composition, RefError> {
+        let resolve_all = |members: &[Schema]| {
+            members
+                .iter()
+                .map(|member| member.resolve(spec))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Composition {
+            all_of: resolve_all(&self.all_of)?,
+            any_of: resolve_all(&self.any_of)?,
+            one_of: resolve_all(&self.one_of)?,
         })
     }
 }
 
-impl FromRef for ObjectSchema {
-    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
-        let refpath = path.parse::<Ref>()?;
+/// Returns a schema requiring both `ours` and `theirs` to hold.
+fn all_of_schemas(ours: Schema, theirs: Schema) -> Schema {
+    ObjectSchema {
+        all_of: vec![ours, theirs],
+        ..ObjectSchema::default()
+    }
+    .into()
+}
 
-        match refpath.kind {
-            RefType::Schema => spec
-                .components
-                .as_ref()
-                .and_then(|cs| cs.schemas.get(&refpath.name))
-                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
-                .and_then(|oor| oor.resolve(spec)),
+/// Returns the least common multiple of two `multipleOf` values.
+///
+/// Returns `None` if the values are not integers and neither is a multiple of the other.
+fn common_multiple(a: &serde_json::Number, b: &serde_json::Number) -> Option<serde_json::Number> {
+    if let (Some(a), Some(b)) = (a.as_u64(), b.as_u64()) {
+        let gcd = |mut a: u64, mut b: u64| {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        };
+
+        return (a / gcd(a, b)).checked_mul(b).map(Into::into);
+    }
+
+    let (x, y) = (a.as_f64()?, b.as_f64()?);
+
+    if (x / y).fract() == 0.0 {
+        Some(a.clone())
+    } else if (y / x).fract() == 0.0 {
+        Some(b.clone())
+    } else {
+        None
+    }
+}
+
+impl ObjectOrReference<ObjectSchema> {
+    /// Resolves this schema, also inlining references found in its subschemas.
+    ///
+    /// Subschemas are inlined up to `max_depth` levels below this schema. References nested deeper
+    /// than that, or that would introduce a reference cycle, are left in place.
+    ///
+    /// References are resolved relative to the `$id` base URIs in effect where they appear. Inlined
+    /// schemas are given an `$id` if needed to keep the base URI in effect within them.
+    pub fn resolve_deep(&self, spec: &Spec, max_depth: usize) -> Result<ObjectSchema, RefError> {
+        let walk = Walk::new(spec);
+        let mut seen = vec![];
+
+        if let ObjectOrReference::Ref { ref_path } = self {
+            seen.push(ref_path.clone());
+        }
+
+        let (schema, base) = walk.resolver.resolve(spec, self, None)?;
+        inline_refs(schema, &walk, base.as_deref(), max_depth, &mut seen)
+    }
+}
 
-            typ => Err(RefError::MismatchedType(typ, RefType::Schema)),
+/// Inlines references in the subschemas of `schema`, within which `base` is in effect, up to
+/// `depth` levels deep.
+fn inline_refs(
+    mut schema: ObjectSchema,
+    walk: &Walk<'_>,
+    base: Option<&str>,
+    depth: usize,
+    seen: &mut Vec<String>,
+) -> Result<ObjectSchema, RefError> {
+    let Some(depth) = depth.checked_sub(1) else {
+        return Ok(schema);
+    };
+
+    let subschemas = schema
+        .all_of
+        .iter_mut()
+        .chain(&mut schema.any_of)
+        .chain(&mut schema.one_of)
+        .chain(schema.properties.values_mut())
+        .chain(schema.pattern_properties.values_mut())
+        .chain(&mut schema.items)
+        .chain(&mut schema.additional_properties)
+        .chain(&mut schema.not)
+        .filter_map(Schema::as_object_mut);
+
+    for subschema in subschemas {
+        match subschema {
+            ObjectOrReference::Object(inner) => {
+                let inner_base = inner.base_uri(base);
+                *inner = inline_refs(
+                    std::mem::take(inner),
+                    walk,
+                    inner_base.as_deref(),
+                    depth,
+                    seen,
+                )?;
+            }
+
+            ObjectOrReference::Ref { ref_path } => {
+                let key = absolute_ref(base, ref_path);
+
+                if seen.contains(&key) {
+                    continue;
+                }
+
+                let (resolved, resolved_base) =
+                    walk.resolver.resolve_ref(walk.spec, base, ref_path)?;
+
+                seen.push(key);
+                let resolved = inline_refs(resolved, walk, resolved_base.as_deref(), depth, seen);
+                seen.pop();
+
+                let mut resolved = resolved?;
+                keep_base(&mut resolved, resolved_base.as_deref(), base);
+
+                *subschema = ObjectOrReference::Object(resolved);
+            }
         }
     }
+
+    Ok(schema)
+}
+
+/// State shared while walking a schema and its subschemas.
+struct Walk<'a> {
+    spec: &'a Spec,
+    resolver: SchemaResolver,
+}
+
+impl<'a> Walk<'a> {
+    fn new(spec: &'a Spec) -> Self {
+        Self {
+            spec,
+            resolver: SchemaResolver::new(),
+        }
+    }
+
+    /// Resolves a subschema appearing where `base` is in effect, borrowing it if inline.
+    ///
+    /// Boolean schemas resolve to their object equivalents, as with [`Schema::resolve()`]. Returns
+    /// the schema along with the base URI in effect within it.
+    fn resolve<'s>(
+        &self,
+        schema: &'s Schema,
+        base: Option<&str>,
+    ) -> Result<(std::borrow::Cow<'s, ObjectSchema>, Option<String>), Error> {
+        use std::borrow::Cow;
+
+        match schema.as_object() {
+            None => Ok((
+                Cow::Owned(schema.resolve(self.spec).map_err(Error::Ref)?),
+                base.map(str::to_owned),
+            )),
+            Some(ObjectOrReference::Object(schema)) => {
+                Ok((Cow::Borrowed(schema), schema.base_uri(base)))
+            }
+            Some(ObjectOrReference::Ref { ref_path }) => self
+                .resolver
+                .resolve_ref(self.spec, base, ref_path)
+                .map(|(schema, base)| (Cow::Owned(schema), base))
+                .map_err(Error::Ref),
+        }
+    }
+}
+
+/// Returns an example value for a schema or reference appearing where `base` is in effect,
+/// guarding against reference cycles.
+fn example_of(
+    schema: &Schema,
+    walk: &Walk<'_>,
+    base: Option<&str>,
+    seen: &mut Vec<String>,
+) -> Result<serde_json::Value, Error> {
+    let key = match schema.as_object() {
+        Some(ObjectOrReference::Ref { ref_path }) => Some(absolute_ref(base, ref_path)),
+        _ => None,
+    };
+
+    if let Some(key) = &key {
+        if seen.contains(key) {
+            return Err(Error::CircularReference(key.clone()));
+        }
+
+        seen.push(key.clone());
+    }
+
+    let value = walk
+        .resolve(schema, base)
+        .and_then(|(schema, base)| schema.example_value_inner(walk, base.as_deref(), seen));
+
+    if key.is_some() {
+        seen.pop();
+    }
+
+    value
+}
+
+/// Applies defaults to `value` from a schema or reference appearing where `base` is in effect,
+/// guarding against reference cycles.
+///
+/// See [`ObjectSchema::apply_defaults()`].
+fn defaults_of(
+    schema: &Schema,
+    value: &mut serde_json::Value,
+    walk: &Walk<'_>,
+    base: Option<&str>,
+    seen: &mut Vec<String>,
+) -> Result<(), Error> {
+    let key = match schema.as_object() {
+        Some(ObjectOrReference::Ref { ref_path }) => Some(absolute_ref(base, ref_path)),
+        _ => None,
+    };
+
+    if let Some(key) = &key {
+        if seen.contains(key) {
+            return Err(Error::CircularReference(key.clone()));
+        }
+
+        seen.push(key.clone());
+    }
+
+    let result = walk
+        .resolve(schema, base)
+        .and_then(|(schema, base)| schema.apply_defaults_inner(value, walk, base.as_deref(), seen));
+
+    if key.is_some() {
+        seen.pop();
+    }
+
+    result
+}
+
+/// Resolved members of a schema's composition keywords.
+///
+/// See [`ObjectSchema::composition_members()`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Composition {
+    /// Resolved `allOf` member schemas.
+    pub all_of: Vec<ObjectSchema>,
+
+    /// Resolved `anyOf` member schemas.
+    pub any_of: Vec<ObjectSchema>,
+
+    /// Resolved `oneOf` member schemas.
+    pub one_of: Vec<ObjectSchema>,
+}
+
+impl FromRef for ObjectSchema {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        SchemaResolver::new()
+            .resolve_ref(spec, None, path)
+            .map(|(schema, _)| schema)
+    }
 }
 
 /// A boolean JSON schema.
@@ -584,6 +1684,57 @@ pub enum Schema {
     Object(Box<ObjectOrReference<ObjectSchema>>),
 }
 
+impl Schema {
+    /// Returns the object schema or reference, if this is not a boolean schema.
+    pub fn as_object(&self) -> Option<&ObjectOrReference<ObjectSchema>> {
+        match self {
+            Schema::Boolean(_) => None,
+            Schema::Object(schema) => Some(schema),
+        }
+    }
+
+    /// Returns the object schema or reference mutably, if this is not a boolean schema.
+    pub fn as_object_mut(&mut self) -> Option<&mut ObjectOrReference<ObjectSchema>> {
+        match self {
+            Schema::Boolean(_) => None,
+            Schema::Object(schema) => Some(schema),
+        }
+    }
+
+    /// Resolves this schema to an object schema.
+    ///
+    /// Boolean schemas resolve to their object equivalents: `true` to an empty schema and `false`
+    /// to `{ "not": true }`.
+    pub fn resolve(&self, spec: &Spec) -> Result<ObjectSchema, RefError> {
+        match self {
+            Schema::Boolean(BooleanSchema(true)) => Ok(ObjectSchema::default()),
+            Schema::Boolean(BooleanSchema(false)) => Ok(ObjectSchema {
+                not: Some(Schema::Boolean(BooleanSchema(true))),
+                ..ObjectSchema::default()
+            }),
+            Schema::Object(schema) => schema.resolve(spec),
+        }
+    }
+}
+
+impl From<bool> for Schema {
+    fn from(value: bool) -> Self {
+        Schema::Boolean(BooleanSchema(value))
+    }
+}
+
+impl From<ObjectSchema> for Schema {
+    fn from(schema: ObjectSchema) -> Self {
+        Schema::Object(Box::new(ObjectOrReference::Object(schema)))
+    }
+}
+
+impl From<ObjectOrReference<ObjectSchema>> for Schema {
+    fn from(schema: ObjectOrReference<ObjectSchema>) -> Self {
+        Schema::Object(Box::new(schema))
+    }
+}
+
 /// Considers any value that is present as `Some`, including `null`.
 fn distinguish_missing_and_null<'de, T, D>(de: D) -> Result<Option<T>, D::Error>
 where
@@ -597,6 +1748,28 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn type_set_null() {
+        let string = TypeSet::Single(Type::String);
+        assert!(!string.is_nullable());
+
+        let nullable = string.clone().with_null();
+        assert!(nullable.is_nullable());
+        assert_eq!(nullable, TypeSet::Multiple(vec![Type::String, Type::Null]));
+        assert_eq!(nullable.clone().with_null(), nullable);
+        assert_eq!(nullable.without_null(), string);
+
+        let multiple = TypeSet::Multiple(vec![Type::Null, Type::String, Type::Integer]);
+        assert_eq!(
+            multiple.without_null(),
+            TypeSet::Multiple(vec![Type::String, Type::Integer]),
+        );
+
+        let null = TypeSet::Single(Type::Null);
+        assert_eq!(null.clone().with_null(), null);
+        assert_eq!(null.without_null(), TypeSet::Multiple(vec![]));
+    }
+
     #[test]
     fn type_set_contains() {
         let spec = "type: integer";
@@ -638,6 +1811,135 @@ mod tests {
         assert_eq!(schema.example, Some(serde_json::Value::Null));
     }
 
+    #[test]
+    fn validate_enum_values() {
+        let schema = serde_yml::from_str::<ObjectSchema>(indoc::indoc! {"
+            type: integer
+            enum: [1, 2.0, a]
+        "})
+        .unwrap();
+        assert_eq!(
+            schema.validate().unwrap_err(),
+            Error::InvalidEnumValue(serde_json::json!("a")),
+        );
+
+        let schema = serde_yml::from_str::<ObjectSchema>(indoc::indoc! {"
+            type: [string, 'null']
+            maxLength: 2
+            enum: [ab, null]
+        "})
+        .unwrap();
+        schema.validate().unwrap();
+
+        let schema = serde_yml::from_str::<ObjectSchema>(indoc::indoc! {"
+            type: number
+            minimum: 0
+            enum: [-1, 0.5]
+        "})
+        .unwrap();
+        assert_eq!(
+            schema.validate().unwrap_err(),
+            Error::InvalidEnumValue(serde_json::json!(-1)),
+        );
+
+        let schema = serde_yml::from_str::<ObjectSchema>(indoc::indoc! {"
+            type: object
+            patternProperties:
+              '^x-(':
+                type: string
+        "})
+        .unwrap();
+        assert_eq!(
+            schema.validate().unwrap_err(),
+            Error::InvalidPattern("^x-(".to_owned()),
+        );
+    }
+
+    #[test]
+    fn constructors() {
+        let schema = ObjectSchema::object()
+            .with_property("id", ObjectSchema::integer())
+            .with_property("name", ObjectSchema::string().nullable())
+            .with_property(
+                "tags",
+                ObjectSchema::array(ObjectOrReference::Ref {
+                    ref_path: "#/components/schemas/Tag".to_owned(),
+                }),
+            )
+            .required(["id", "name"])
+            .required(["id"]);
+
+        assert_eq!(
+            serde_yml::to_string(&schema).unwrap(),
+            indoc::indoc! {"
+                properties:
+                  id:
+                    type: integer
+                  name:
+                    type:
+                    - string
+                    - 'null'
+                  tags:
+                    items:
+                      $ref: '#/components/schemas/Tag'
+                    type: array
+                type: object
+                required:
+                - id
+                - name
+            "},
+        );
+
+        assert_eq!(
+            ObjectSchema::string().nullable().nullable().schema_type,
+            Some(TypeSet::Multiple(vec![Type::String, Type::Null])),
+        );
+        assert_eq!(ObjectSchema::default().nullable().schema_type, None);
+    }
+
+    #[test]
+    fn composition_members_all_of() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Composition
+              version: 0.0.0
+            components:
+              schemas:
+                Pet:
+                  allOf:
+                    - $ref: '#/components/schemas/Named'
+                    - type: object
+                      properties:
+                        age: { type: integer }
+                Named:
+                  type: object
+                  properties:
+                    name: { type: string }
+        "})
+        .unwrap();
+
+        let schema = spec.components.as_ref().unwrap().schemas["Pet"]
+            .resolve(&spec)
+            .unwrap();
+
+        let composition = schema.composition_members(&spec).unwrap();
+        assert!(composition.any_of.is_empty());
+        assert!(composition.one_of.is_empty());
+        assert_eq!(composition.all_of.len(), 2);
+        assert!(composition.all_of[0].properties.contains_key("name"));
+        assert!(composition.all_of[1].properties.contains_key("age"));
+
+        let schema = ObjectSchema {
+            one_of: vec![ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Missing".to_owned(),
+            }
+            .into()],
+            ..ObjectSchema::default()
+        };
+        schema.composition_members(&spec).unwrap_err();
+    }
+
     #[test]
     fn discriminator_example_is_parsed_correctly() {
         let spec = indoc::indoc! {"
@@ -657,4 +1959,750 @@ mod tests {
         assert!(schema.discriminator.is_some());
         assert_eq!(2, schema.discriminator.unwrap().mapping.unwrap().len());
     }
+
+    #[test]
+    fn example_value() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  allOf:
+                    - $ref: '#/components/schemas/Named'
+                  type: object
+                  required: [id, tags, kind, status, count, alive, owner]
+                  properties:
+                    id:
+                      type: integer
+                      minimum: 1
+                    tags:
+                      type: array
+                      items:
+                        type: string
+                        minLength: 2
+                    kind:
+                      oneOf:
+                        - const: cat
+                        - const: dog
+                    status:
+                      type: string
+                      enum: [available, sold]
+                    count:
+                      type: [number, 'null']
+                    alive:
+                      type: boolean
+                    owner:
+                      type: [object, 'null']
+                    nickname:
+                      type: string
+                Named:
+                  type: object
+                  required: [name]
+                  properties:
+                    name:
+                      type: string
+                      example: Rex
+                Node:
+                  type: object
+                  required: [next]
+                  properties:
+                    next:
+                      $ref: '#/components/schemas/Node'
+        "})
+        .unwrap();
+
+        let pet = ObjectSchema::from_ref(&spec, "#/components/schemas/Pet").unwrap();
+        assert_eq!(
+            pet.example_value(&spec).unwrap(),
+            serde_json::json!({
+                "name": "Rex",
+                "id": 1,
+                "tags": ["aa"],
+                "kind": "cat",
+                "status": "available",
+                "count": 0.0,
+                "alive": false,
+                "owner": {},
+            }),
+        );
+
+        let node = ObjectSchema::from_ref(&spec, "#/components/schemas/Node").unwrap();
+        assert_eq!(
+            node.example_value(&spec).unwrap_err(),
+            Error::CircularReference("#/components/schemas/Node".to_owned()),
+        );
+
+        let missing = ObjectSchema::array(ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Missing".to_owned(),
+        });
+        assert!(matches!(missing.example_value(&spec), Err(Error::Ref(_))));
+    }
+
+    #[test]
+    fn example_value_within_bounds() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths: {}
+        "})
+        .unwrap();
+
+        let cases = [
+            ("type: integer, maximum: -3", serde_json::json!(-3)),
+            ("type: integer, exclusiveMaximum: -3", serde_json::json!(-4)),
+            ("type: integer, exclusiveMinimum: 5", serde_json::json!(6)),
+            (
+                "type: integer, minimum: 1, multipleOf: 4",
+                serde_json::json!(4),
+            ),
+            (
+                "type: integer, exclusiveMaximum: -8, multipleOf: 4",
+                serde_json::json!(-12),
+            ),
+            ("type: number, maximum: -0.5", serde_json::json!(-0.5)),
+            (
+                "type: number, exclusiveMinimum: 1, exclusiveMaximum: 1.5",
+                serde_json::json!(1.25),
+            ),
+            (
+                "type: number, minimum: 0.7, multipleOf: 0.5",
+                serde_json::json!(1.0),
+            ),
+            (
+                "type: string, minLength: 2, maxLength: 3",
+                serde_json::json!("aa"),
+            ),
+            ("type: array, maxItems: 0, items: {}", serde_json::json!([])),
+            (
+                "type: array, minItems: 2, items: { type: integer, minimum: 1 }",
+                serde_json::json!([1, 1]),
+            ),
+        ];
+
+        for (yaml, expected) in cases {
+            let schema = serde_yml::from_str::<ObjectSchema>(&format!("{{ {yaml} }}")).unwrap();
+            let example = schema.example_value(&spec).unwrap();
+
+            assert_eq!(example, expected, "{yaml}");
+            assert!(schema.admits(&example), "{yaml}");
+        }
+
+        // repeated items would not be unique
+        let schema = serde_yml::from_str::<ObjectSchema>(
+            "{ type: array, minItems: 3, uniqueItems: true, items: { type: integer } }",
+        )
+        .unwrap();
+        assert_eq!(schema.example_value(&spec).unwrap(), serde_json::json!([0]));
+    }
+
+    #[test]
+    fn unresolvable_ref_chain() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                A:
+                  $ref: '#/components/schemas/B'
+                B:
+                  $ref: '#/components/schemas/Missing'
+        "})
+        .unwrap();
+
+        let err = ObjectSchema::from_ref(&spec, "#/components/schemas/A").unwrap_err();
+        assert_eq!(
+            err,
+            RefError::Chain {
+                chain: vec![
+                    "#/components/schemas/A".to_owned(),
+                    "#/components/schemas/B".to_owned(),
+                ],
+                source: Box::new(RefError::Unresolvable(
+                    "#/components/schemas/Missing".to_owned()
+                )),
+            },
+        );
+        assert_eq!(
+            err.to_string(),
+            "Unresolvable path: #/components/schemas/A -> #/components/schemas/B -> \
+             #/components/schemas/Missing",
+        );
+
+        let err = ObjectSchema::from_ref(&spec, "#/components/schemas/Missing").unwrap_err();
+        assert_eq!(
+            err,
+            RefError::Unresolvable("#/components/schemas/Missing".to_owned()),
+        );
+    }
+
+    #[test]
+    fn ref_cycle() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                A:
+                  $ref: '#/components/schemas/B'
+                B:
+                  $ref: '#/components/schemas/A'
+                C:
+                  $ref: '#/components/schemas/C'
+        "})
+        .unwrap();
+
+        let schema = ObjectOrReference::<ObjectSchema>::Ref {
+            ref_path: "#/components/schemas/A".to_owned(),
+        };
+        let err = schema.resolve(&spec).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Reference cycle: #/components/schemas/A -> #/components/schemas/B -> \
+             #/components/schemas/A",
+        );
+
+        assert_eq!(
+            ObjectSchema::from_ref(&spec, "#/components/schemas/C").unwrap_err(),
+            RefError::Cycle(vec![
+                "#/components/schemas/C".to_owned(),
+                "#/components/schemas/C".to_owned(),
+            ]),
+        );
+    }
+
+    #[test]
+    fn resolve_deep() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  properties:
+                    owner:
+                      $ref: '#/components/schemas/Person'
+                Person:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                    pets:
+                      type: array
+                      items:
+                        $ref: '#/components/schemas/Pet'
+        "})
+        .unwrap();
+
+        let pet = ObjectOrReference::<ObjectSchema>::Ref {
+            ref_path: "#/components/schemas/Pet".to_owned(),
+        };
+
+        let schema = pet.resolve_deep(&spec, 10).unwrap();
+        let Some(ObjectOrReference::Object(owner)) = schema.properties["owner"].as_object() else {
+            panic!("owner should be inlined");
+        };
+        assert_eq!(owner.properties["name"], ObjectSchema::string().into());
+
+        // cyclic reference back to `Pet` is left in place
+        let Some(ObjectOrReference::Object(pets)) = owner.properties["pets"].as_object() else {
+            panic!("pets should be inline");
+        };
+        assert_eq!(pets.items, Some(Schema::Object(Box::new(pet.clone()))),);
+
+        let schema = pet.resolve_deep(&spec, 0).unwrap();
+        assert_eq!(
+            schema.properties["owner"],
+            Schema::from(ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Person".to_owned(),
+            }),
+        );
+    }
+
+    #[test]
+    fn suggested_rust_type() {
+        let suggest = |yaml: &str| {
+            serde_yml::from_str::<ObjectSchema>(yaml)
+                .unwrap()
+                .suggested_rust_type()
+        };
+
+        assert_eq!(
+            suggest("{ type: string, format: date-time }"),
+            "chrono::DateTime<chrono::Utc>",
+        );
+        assert_eq!(suggest("{ type: string, format: uuid }"), "uuid::Uuid");
+        assert_eq!(suggest("{ type: string, format: byte }"), "Vec<u8>");
+        assert_eq!(suggest("{ type: string, format: hostname }"), "String");
+        assert_eq!(suggest("{ type: integer, format: int32 }"), "i32");
+        assert_eq!(suggest("{ type: integer, format: int64 }"), "i64");
+        assert_eq!(suggest("{ type: [number, 'null'], format: float }"), "f32");
+        assert_eq!(suggest("{ type: boolean }"), "bool");
+        assert_eq!(suggest("{ format: uuid }"), "serde_json::Value");
+        assert_eq!(
+            suggest("{ type: string, contentEncoding: base64 }"),
+            "Vec<u8>"
+        );
+    }
+
+    #[test]
+    fn core_keywords() {
+        let schema = serde_yml::from_str::<ObjectSchema>(indoc::indoc! {"
+            $schema: https://json-schema.org/draft/2020-12/schema
+            $id: https://example.com/schemas/tree
+            $dynamicAnchor: node
+            type: object
+            properties:
+              children:
+                type: array
+                items:
+                  $dynamicRef: '#node'
+              root:
+                $anchor: root
+                type: boolean
+        "})
+        .unwrap();
+
+        assert_eq!(
+            schema.schema_dialect.as_deref(),
+            Some("https://json-schema.org/draft/2020-12/schema"),
+        );
+        assert_eq!(
+            schema.id.as_deref(),
+            Some("https://example.com/schemas/tree"),
+        );
+        assert_eq!(schema.dynamic_anchor.as_deref(), Some("node"));
+
+        let Some(ObjectOrReference::Object(root)) = schema.properties["root"].as_object() else {
+            panic!("expected inline schema");
+        };
+        assert_eq!(root.anchor.as_deref(), Some("root"));
+
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            value["$schema"],
+            "https://json-schema.org/draft/2020-12/schema",
+        );
+        assert_eq!(value["$id"], "https://example.com/schemas/tree");
+        assert_eq!(value["$dynamicAnchor"], "node");
+        assert_eq!(
+            value["properties"]["children"]["items"]["$dynamicRef"],
+            "#node",
+        );
+        assert_eq!(value["properties"]["root"]["$anchor"], "root");
+        assert_eq!(
+            serde_json::from_value::<ObjectSchema>(value).unwrap(),
+            schema
+        );
+    }
+
+    #[test]
+    fn content_keywords() {
+        let schema = serde_yml::from_str::<ObjectSchema>(indoc::indoc! {"
+            type: string
+            contentEncoding: base64
+            contentMediaType: image/png
+        "})
+        .unwrap();
+
+        assert_eq!(schema.content_encoding.as_deref(), Some("base64"));
+        assert_eq!(schema.content_media_type.as_deref(), Some("image/png"));
+        assert!(schema.is_binary());
+
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "type": "string",
+                "contentEncoding": "base64",
+                "contentMediaType": "image/png",
+            }),
+        );
+        assert_eq!(
+            serde_json::from_value::<ObjectSchema>(value).unwrap(),
+            schema
+        );
+
+        let is_binary = |encoding: Option<&str>| {
+            ObjectSchema {
+                content_encoding: encoding.map(str::to_owned),
+                ..ObjectSchema::default()
+            }
+            .is_binary()
+        };
+
+        assert!(is_binary(Some("Base64URL")));
+        assert!(is_binary(Some("binary")));
+        assert!(!is_binary(Some("quoted-printable")));
+        assert!(!is_binary(None));
+    }
+
+    #[test]
+    fn flatten_all_of() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Named:
+                  type: object
+                  required: [name]
+                  properties:
+                    name:
+                      type: string
+                Sized:
+                  allOf:
+                    - type: [object, 'null']
+                      required: [size]
+                      properties:
+                        size:
+                          type: integer
+                      minProperties: 1
+                Pet:
+                  description: A pet.
+                  allOf:
+                    - $ref: '#/components/schemas/Named'
+                    - $ref: '#/components/schemas/Sized'
+                    - minProperties: 2
+                      maxProperties: 5
+                Broken:
+                  allOf:
+                    - type: string
+                    - type: integer
+                Code:
+                  allOf:
+                    - type: object
+                      properties:
+                        id:
+                          type: string
+                        kind:
+                          type: string
+                      patternProperties:
+                        '^x-':
+                          type: string
+                      additionalProperties:
+                        type: integer
+                      multipleOf: 4
+                      pattern: '^[a-z]+$'
+                    - properties:
+                        id:
+                          maxLength: 36
+                        kind:
+                          type: string
+                      patternProperties:
+                        '^x-':
+                          maxLength: 8
+                        '^y-':
+                          type: integer
+                      additionalProperties:
+                        minimum: 0
+                      multipleOf: 6
+                      pattern: '^[a-z]+$'
+                      format: hostname
+                    - additionalProperties: true
+                Closed:
+                  allOf:
+                    - additionalProperties:
+                        type: string
+                    - additionalProperties: false
+                Patterns:
+                  allOf:
+                    - pattern: '^a'
+                    - pattern: '^b'
+                Formats:
+                  allOf:
+                    - format: email
+                    - format: uri
+                Multiples:
+                  allOf:
+                    - multipleOf: 0.5
+                    - multipleOf: 0.3
+        "})
+        .unwrap();
+
+        let pet = ObjectSchema::from_ref(&spec, "#/components/schemas/Pet").unwrap();
+        let flat = pet.flatten_all_of(&spec).unwrap();
+
+        assert!(flat.all_of.is_empty());
+        assert_eq!(flat.description.as_deref(), Some("A pet."));
+        assert_eq!(flat.schema_type, Some(TypeSet::Single(Type::Object)));
+        assert_eq!(flat.required, ["name", "size"]);
+        assert_eq!(flat.properties.keys().collect::<Vec<_>>(), ["name", "size"],);
+        assert_eq!(flat.min_properties, Some(2));
+        assert_eq!(flat.max_properties, Some(5));
+
+        let broken = ObjectSchema::from_ref(&spec, "#/components/schemas/Broken").unwrap();
+        assert_eq!(
+            broken.flatten_all_of(&spec).unwrap_err(),
+            Error::ConflictingTypes(
+                TypeSet::Single(Type::String),
+                TypeSet::Single(Type::Integer),
+            ),
+        );
+
+        let code = ObjectSchema::from_ref(&spec, "#/components/schemas/Code").unwrap();
+        let flat = code.flatten_all_of(&spec).unwrap();
+
+        let schema = |yaml: &str| Schema::from(serde_yml::from_str::<ObjectSchema>(yaml).unwrap());
+        assert_eq!(
+            flat.properties["id"],
+            schema("allOf: [{ type: string }, { maxLength: 36 }]"),
+        );
+        assert_eq!(flat.properties["kind"], schema("type: string"));
+        assert_eq!(
+            flat.pattern_properties,
+            BTreeMap::from([
+                (
+                    "^x-".to_owned(),
+                    schema("allOf: [{ type: string }, { maxLength: 8 }]"),
+                ),
+                ("^y-".to_owned(), schema("type: integer")),
+            ]),
+        );
+        assert_eq!(
+            flat.additional_properties,
+            Some(schema("allOf: [{ type: integer }, { minimum: 0 }]")),
+        );
+        assert_eq!(flat.multiple_of, Some(12.into()));
+        assert_eq!(flat.pattern.as_deref(), Some("^[a-z]+$"));
+        assert_eq!(flat.format.as_deref(), Some("hostname"));
+
+        let closed = ObjectSchema::from_ref(&spec, "#/components/schemas/Closed").unwrap();
+        assert_eq!(
+            closed.flatten_all_of(&spec).unwrap().additional_properties,
+            Some(Schema::Boolean(BooleanSchema(false))),
+        );
+
+        let conflict = |name: &str| {
+            ObjectSchema::from_ref(&spec, &format!("#/components/schemas/{name}"))
+                .unwrap()
+                .flatten_all_of(&spec)
+                .unwrap_err()
+        };
+        assert_eq!(
+            conflict("Patterns"),
+            Error::ConflictingKeyword("pattern", "^a".to_owned(), "^b".to_owned()),
+        );
+        assert_eq!(
+            conflict("Formats"),
+            Error::ConflictingKeyword("format", "email".to_owned(), "uri".to_owned()),
+        );
+        assert_eq!(
+            conflict("Multiples"),
+            Error::ConflictingKeyword("multipleOf", "0.5".to_owned(), "0.3".to_owned()),
+        );
+    }
+
+    #[test]
+    fn boolean_subschemas_round_trip() {
+        let yaml = indoc::indoc! {"
+            type: object
+            properties:
+              tags:
+                type: array
+                items: false
+              extra:
+                type: array
+                items:
+                  type: string
+              gone: false
+            patternProperties:
+              '^x-': true
+            additionalProperties: true
+            allOf: [true]
+            anyOf:
+              - false
+              - type: object
+            oneOf: [true]
+            not: false
+        "};
+
+        let schema = serde_yml::from_str::<ObjectSchema>(yaml).unwrap();
+        assert_eq!(
+            schema.additional_properties,
+            Some(Schema::Boolean(BooleanSchema(true))),
+        );
+
+        let Some(ObjectOrReference::Object(tags)) = schema.properties["tags"].as_object() else {
+            panic!("tags should be inline");
+        };
+        assert_eq!(tags.items, Some(Schema::Boolean(BooleanSchema(false))));
+
+        let Some(ObjectOrReference::Object(extra)) = schema.properties["extra"].as_object() else {
+            panic!("extra should be inline");
+        };
+        assert!(matches!(extra.items, Some(Schema::Object(_))));
+
+        assert_eq!(schema.properties["gone"], false.into());
+        assert_eq!(schema.pattern_properties["^x-"], true.into());
+        assert_eq!(schema.all_of, [true.into()]);
+        assert_eq!(schema.any_of[0], false.into());
+        assert!(matches!(schema.any_of[1], Schema::Object(_)));
+        assert_eq!(schema.one_of, [true.into()]);
+        assert_eq!(schema.not, Some(false.into()));
+
+        let round_trip = serde_yml::to_string(&schema).unwrap();
+        assert_eq!(
+            serde_yml::from_str::<ObjectSchema>(&round_trip).unwrap(),
+            schema,
+        );
+        assert!(round_trip.contains("items: false"));
+        assert!(round_trip.contains("gone: false"));
+        assert!(round_trip.contains("additionalProperties: true"));
+        assert!(round_trip.contains("not: false"));
+
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths: {}
+        "})
+        .unwrap();
+        assert_eq!(
+            Schema::from(true).resolve(&spec).unwrap(),
+            ObjectSchema::default()
+        );
+        assert_eq!(
+            Schema::from(false).resolve(&spec).unwrap().not,
+            Some(true.into()),
+        );
+    }
+
+    #[test]
+    fn apply_defaults() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Paging:
+                  type: object
+                  properties:
+                    limit:
+                      type: integer
+                      default: 20
+                Query:
+                  allOf:
+                    - $ref: '#/components/schemas/Paging'
+                  type: object
+                  properties:
+                    sort:
+                      type: string
+                      default: asc
+                    filters:
+                      type: array
+                      items:
+                        type: object
+                        properties:
+                          op:
+                            type: string
+                            default: eq
+                    options:
+                      type: object
+                      default: {}
+                      properties:
+                        verbose:
+                          type: boolean
+                          default: false
+        "})
+        .unwrap();
+
+        let schema = ObjectSchema::from_ref(&spec, "#/components/schemas/Query").unwrap();
+
+        let mut value = serde_json::json!({
+            "sort": "desc",
+            "filters": [{ "field": "name" }, { "op": "ne" }],
+        });
+        schema.apply_defaults(&mut value, &spec).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "limit": 20,
+                "sort": "desc",
+                "filters": [{ "field": "name", "op": "eq" }, { "op": "ne" }],
+                "options": { "verbose": false },
+            }),
+        );
+    }
+
+    #[test]
+    fn apply_defaults_cycles() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Loop:
+                  allOf:
+                    - $ref: '#/components/schemas/Loop'
+                Node:
+                  type: object
+                  properties:
+                    child:
+                      allOf:
+                        - $ref: '#/components/schemas/Node'
+                      default: {}
+                Tree:
+                  type: object
+                  properties:
+                    size:
+                      type: integer
+                      default: 1
+                    children:
+                      type: array
+                      items:
+                        $ref: '#/components/schemas/Tree'
+        "})
+        .unwrap();
+
+        let schema = |name: &str| {
+            ObjectSchema::from_ref(&spec, &format!("#/components/schemas/{name}")).unwrap()
+        };
+
+        let mut value = serde_json::json!({});
+        assert_eq!(
+            schema("Loop")
+                .apply_defaults(&mut value, &spec)
+                .unwrap_err(),
+            Error::CircularReference("#/components/schemas/Loop".to_owned()),
+        );
+
+        let mut value = serde_json::json!({});
+        assert_eq!(
+            schema("Node")
+                .apply_defaults(&mut value, &spec)
+                .unwrap_err(),
+            Error::CircularReference("#/components/schemas/Node".to_owned()),
+        );
+
+        // recursive schemas are fine while they follow existing values
+        let mut value = serde_json::json!({ "children": [{ "children": [{}] }] });
+        schema("Tree").apply_defaults(&mut value, &spec).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "size": 1,
+                "children": [{ "size": 1, "children": [{ "size": 1 }] }],
+            }),
+        );
+    }
 }
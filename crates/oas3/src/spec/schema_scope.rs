@@ -0,0 +1,753 @@
+//! Resolution of schema references relative to `$id` base URIs.
+
+use std::{cell::OnceCell, collections::BTreeMap};
+
+use serde_json::Value;
+use url::Url;
+
+use super::{ObjectOrReference, ObjectSchema, Ref, RefError, RefType, Schema, Spec};
+use crate::json_pointer;
+
+/// Base URI standing in for the document's own location, which is not known.
+const DOCUMENT_BASE: &str = "oas3:///";
+
+/// Resolves schema references relative to the `$id` base URIs in effect where they appear.
+///
+/// References may point to a schema by its `$id`, to an `$anchor` or `$dynamicAnchor` within a
+/// schema resource (e.g. `#node`), or to a location within a schema resource by JSON pointer.
+///
+/// Resolving references that do not point directly to a component requires an index of the
+/// document's schema resources. The index is built on first use and reused for all later
+/// references, so a resolver should be shared for the duration of a walk over a spec's schemas and
+/// must only be used with a single spec.
+#[derive(Debug, Default)]
+pub struct SchemaResolver {
+    index: OnceCell<SchemaIndex>,
+}
+
+impl SchemaResolver {
+    /// Creates a new resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves `schema` (if needed) from the given `spec`, with its reference taken as relative to
+    /// `base`, returning it along with the base URI in effect within it.
+    ///
+    /// `base` is the base URI in effect where `schema` appears, i.e. as returned for the enclosing
+    /// schema; `None` stands for the document itself. The returned base URI should be passed when
+    /// resolving the subschemas of the returned schema.
+    pub fn resolve(
+        &self,
+        spec: &Spec,
+        schema: &ObjectOrReference<ObjectSchema>,
+        base: Option<&str>,
+    ) -> Result<(ObjectSchema, Option<String>), RefError> {
+        match schema {
+            ObjectOrReference::Object(schema) => Ok((schema.clone(), schema.base_uri(base))),
+            ObjectOrReference::Ref { ref_path } => self.resolve_ref(spec, base, ref_path),
+        }
+    }
+
+    /// Resolves a schema reference `path`, relative to `base` or the document if `None`.
+    ///
+    /// Chains of references are followed until reaching an inline schema, returning
+    /// [`RefError::Cycle`] if a reference repeats.
+    pub(crate) fn resolve_ref(
+        &self,
+        spec: &Spec,
+        base: Option<&str>,
+        path: &str,
+    ) -> Result<(ObjectSchema, Option<String>), RefError> {
+        let document = Url::parse(DOCUMENT_BASE).expect("document base should be a valid URL");
+
+        let mut followed = Vec::<String>::new();
+        let mut seen = Vec::<String>::new();
+        let mut current = path.to_owned();
+        let mut current_base = document
+            .join(base.unwrap_or_default())
+            .map_err(|_| RefError::Unresolvable(path.to_owned()))?;
+
+        loop {
+            let step = split_ref(&current_base, &current)
+                .ok_or_else(|| RefError::Unresolvable(current.clone()))
+                .and_then(|(resource, fragment)| {
+                    let key = format!("{resource}#{fragment}");
+
+                    if seen.contains(&key) {
+                        let mut chain = followed.clone();
+                        chain.push(current.clone());
+                        return Err(RefError::Cycle(chain));
+                    }
+
+                    seen.push(key);
+
+                    if resource == document && is_component_pointer(fragment) {
+                        return component_step(spec, &current, &document);
+                    }
+
+                    // only built when following references other than to components
+                    let index = self.index.get_or_init(|| SchemaIndex::new(spec, &document));
+
+                    let node = index
+                        .lookup(&resource, fragment)
+                        .ok_or_else(|| RefError::Unresolvable(current.clone()))?;
+
+                    match node.value.get("$ref") {
+                        Some(Value::String(ref_path)) => {
+                            Ok(Step::Follow(ref_path.clone(), node.base))
+                        }
+                        _ => serde_json::from_value(node.value.clone())
+                            .map(|schema| Step::Done(Box::new(schema), node.base))
+                            .map_err(|_| RefError::Unresolvable(current.clone())),
+                    }
+                });
+
+            match step {
+                Ok(Step::Done(schema, base)) => return Ok((*schema, scope_base(&base))),
+
+                Ok(Step::Follow(ref_path, base)) => {
+                    followed.push(current);
+                    current = ref_path;
+                    current_base = base;
+                }
+
+                Err(err @ RefError::Cycle(_)) => return Err(err),
+                Err(err) if followed.is_empty() => return Err(err),
+
+                Err(err) => {
+                    return Err(RefError::Chain {
+                        chain: followed,
+                        source: Box::new(err),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl ObjectOrReference<ObjectSchema> {
+    /// Resolves the schema (if needed) from the given `spec`, with its reference taken as relative
+    /// to `base`.
+    ///
+    /// `base` is the base URI in effect where this schema appears, i.e. the `$id` of the nearest
+    /// enclosing schema that declares one; see [`ObjectSchema::base_uri()`]. If `None`, the
+    /// reference is relative to the document, as in [`resolve()`](Self::resolve).
+    ///
+    /// Use a [`SchemaResolver`] when resolving many references, e.g. while walking subschemas.
+    pub fn resolve_with_base(
+        &self,
+        spec: &Spec,
+        base: Option<&str>,
+    ) -> Result<ObjectSchema, RefError> {
+        SchemaResolver::new()
+            .resolve(spec, self, base)
+            .map(|(schema, _)| schema)
+    }
+}
+
+impl Schema {
+    /// Resolves this schema to an object schema, with any reference taken as relative to `base`.
+    ///
+    /// See [`ObjectOrReference::resolve_with_base()`] and [`Schema::resolve()`].
+    pub fn resolve_with_base(
+        &self,
+        spec: &Spec,
+        base: Option<&str>,
+    ) -> Result<ObjectSchema, RefError> {
+        match self.as_object() {
+            Some(schema) => schema.resolve_with_base(spec, base),
+            None => self.resolve(spec),
+        }
+    }
+}
+
+impl ObjectSchema {
+    /// Returns the base URI in effect within this schema.
+    ///
+    /// That is this schema's `$id`, resolved against `parent_base` if relative, or `parent_base`
+    /// itself if this schema has no `$id`.
+    pub fn base_uri(&self, parent_base: Option<&str>) -> Option<String> {
+        let Some(id) = &self.id else {
+            return parent_base.map(str::to_owned);
+        };
+
+        let joined = parent_base
+            .and_then(|base| Url::parse(base).ok())
+            .and_then(|base| base.join(id).ok());
+
+        match joined {
+            Some(mut url) => {
+                url.set_fragment(None);
+                Some(url.into())
+            }
+            None => Some(id.clone()),
+        }
+    }
+}
+
+/// Returns `ref_path`, taken as relative to `base`, as an absolute URI.
+///
+/// References relative to the document are returned as-is.
+pub(crate) fn absolute_ref(base: Option<&str>, ref_path: &str) -> String {
+    let document = Url::parse(DOCUMENT_BASE).expect("document base should be a valid URL");
+
+    match base.and_then(|base| document.join(base).ok()) {
+        Some(base) if base != document => base
+            .join(ref_path)
+            .map_or_else(|_| ref_path.to_owned(), |url| url.into()),
+        _ => ref_path.to_owned(),
+    }
+}
+
+/// Ensures `schema`, within which `base` is in effect, keeps that base URI when placed where
+/// `parent_base` is in effect, by setting its `$id`.
+pub(crate) fn keep_base(schema: &mut ObjectSchema, base: Option<&str>, parent_base: Option<&str>) {
+    if let Some(base) = base {
+        if schema.base_uri(parent_base).as_deref() != Some(base) {
+            schema.id = Some(base.to_owned());
+        }
+    }
+}
+
+/// Ensures `schema`, appearing where `base` is in effect, keeps its meaning when placed where
+/// `parent_base` is in effect.
+///
+/// Inline schemas are given an `$id` and reference paths are made absolute, as needed. Boolean
+/// schemas are left as-is.
+pub(crate) fn keep_subschema_base(
+    schema: &mut Schema,
+    base: Option<&str>,
+    parent_base: Option<&str>,
+) {
+    match schema.as_object_mut() {
+        Some(ObjectOrReference::Object(schema)) => {
+            let base = schema.base_uri(base);
+            keep_base(schema, base.as_deref(), parent_base);
+        }
+
+        Some(ObjectOrReference::Ref { ref_path }) if base != parent_base => {
+            *ref_path = absolute_ref(base, ref_path);
+        }
+
+        _ => {}
+    }
+}
+
+/// Returns the location of each reference in `spec`, paired with the location of its target, both
+/// as JSON pointers into the document.
+///
+/// References are taken as relative to the `$id` base URIs in effect where they appear, so those
+/// pointing to a schema by its `$id` or to an anchor are included. Chains of references are not
+/// followed and unresolvable references are omitted.
+pub(crate) fn reference_targets(spec: &Spec) -> Vec<(String, String)> {
+    let document = Url::parse(DOCUMENT_BASE).expect("document base should be a valid URL");
+    let index = SchemaIndex::new(spec, &document);
+
+    index
+        .refs
+        .iter()
+        .filter_map(|(ptr, base, ref_path)| {
+            let (resource, fragment) = split_ref(base, ref_path)?;
+            Some((ptr.clone(), index.locate(&resource, fragment)?))
+        })
+        .collect()
+}
+
+/// Converts a base URI to the form returned by [`SchemaResolver::resolve()`].
+///
+/// The document is represented by `None` and URIs relative to it are returned as relative.
+fn scope_base(base: &Url) -> Option<String> {
+    match base.as_str().strip_prefix(DOCUMENT_BASE) {
+        Some("") => None,
+        Some(relative) => Some(relative.to_owned()),
+        None => Some(base.to_string()),
+    }
+}
+
+/// Outcome of resolving a single reference.
+enum Step {
+    /// Reference points to an inline schema, given with the base URI in effect within it.
+    Done(Box<ObjectSchema>, Url),
+
+    /// Reference points to another reference, given with the base URI it is relative to.
+    Follow(String, Url),
+}
+
+/// Resolves a reference to a schema component directly, without indexing the document.
+fn component_step(spec: &Spec, ref_path: &str, document: &Url) -> Result<Step, RefError> {
+    let refpath = ref_path.parse::<Ref>()?;
+
+    if refpath.kind != RefType::Schema {
+        return Err(RefError::MismatchedType(refpath.kind, RefType::Schema));
+    }
+
+    match spec
+        .components
+        .as_ref()
+        .and_then(|cs| cs.schemas.get(&refpath.name))
+    {
+        Some(ObjectOrReference::Object(schema)) => {
+            let base = match &schema.id {
+                Some(id) => document
+                    .join(id)
+                    .map_err(|_| RefError::Unresolvable(id.clone()))?,
+                None => document.clone(),
+            };
+
+            Ok(Step::Done(Box::new(schema.clone()), base))
+        }
+        Some(ObjectOrReference::Ref { ref_path }) => {
+            Ok(Step::Follow(ref_path.clone(), document.clone()))
+        }
+        None => Err(RefError::Unresolvable(ref_path.to_owned())),
+    }
+}
+
+/// Returns true if `fragment` is a JSON pointer to a component, like `/components/schemas/Pet`.
+fn is_component_pointer(fragment: &str) -> bool {
+    fragment
+        .strip_prefix("/components/")
+        .is_some_and(|rest| rest.split('/').count() == 2)
+}
+
+/// Splits `ref_path`, resolved against `base`, into the URI of its resource and its fragment.
+fn split_ref<'a>(base: &Url, ref_path: &'a str) -> Option<(Url, &'a str)> {
+    let (resource, fragment) = ref_path.split_once('#').unwrap_or((ref_path, ""));
+
+    let mut resource = base.join(resource).ok()?;
+    resource.set_fragment(None);
+
+    Some((resource, fragment))
+}
+
+/// A schema found in the document, along with the base URI in effect within it.
+#[derive(Debug)]
+struct Node<'a> {
+    value: &'a Value,
+    base: Url,
+}
+
+/// Schema resources and anchors of a document.
+#[derive(Debug)]
+struct SchemaIndex {
+    /// Serialized document, itself the root resource.
+    document: Value,
+
+    /// Base URI of the document.
+    document_base: Url,
+
+    /// Pointers to schemas declaring an `$id`, keyed by their absolute URI.
+    resources: BTreeMap<String, String>,
+
+    /// Pointers to schemas declaring an `$anchor` or `$dynamicAnchor`, keyed by the URI of their
+    /// resource and the anchor name.
+    anchors: BTreeMap<(String, String), String>,
+
+    /// Pointers to reference objects, along with the base URI their `$ref` is relative to.
+    refs: Vec<(String, Url, String)>,
+}
+
+impl SchemaIndex {
+    fn new(spec: &Spec, document_base: &Url) -> Self {
+        let document = serde_json::to_value(spec).expect("specs should always serialize to JSON");
+
+        let mut index = Self {
+            document: Value::Null,
+            document_base: document_base.clone(),
+            resources: BTreeMap::new(),
+            anchors: BTreeMap::new(),
+            refs: Vec::new(),
+        };
+
+        index.collect(&document, "", document_base, Position::Other);
+        index.document = document;
+        index
+    }
+
+    /// Records resources and anchors within `value`, found at JSON pointer `ptr`.
+    fn collect(&mut self, value: &Value, ptr: &str, base: &Url, position: Position) {
+        match value {
+            Value::Object(obj) => {
+                let mut base = base.clone();
+
+                if position == Position::Schema {
+                    if let Some(Value::String(id)) = obj.get("$id") {
+                        if let Ok(mut url) = base.join(id) {
+                            url.set_fragment(None);
+                            self.resources.insert(url.to_string(), ptr.to_owned());
+                            base = url;
+                        }
+                    }
+
+                    for key in ["$anchor", "$dynamicAnchor"] {
+                        if let Some(Value::String(anchor)) = obj.get(key) {
+                            self.anchors
+                                .insert((base.to_string(), anchor.clone()), ptr.to_owned());
+                        }
+                    }
+                }
+
+                if let Some(Value::String(ref_path)) = obj.get("$ref") {
+                    self.refs
+                        .push((ptr.to_owned(), base.clone(), ref_path.clone()));
+                }
+
+                for (key, value) in obj {
+                    let Some(position) = position.child(key) else {
+                        continue;
+                    };
+
+                    self.collect(
+                        value,
+                        &format!("{ptr}/{}", json_pointer::escape(key)),
+                        &base,
+                        position,
+                    );
+                }
+            }
+
+            Value::Array(items) => {
+                for (idx, item) in items.iter().enumerate() {
+                    self.collect(item, &format!("{ptr}/{idx}"), base, position);
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Returns a JSON pointer to `fragment` within the schema resource identified by `resource`.
+    fn locate(&self, resource: &Url, fragment: &str) -> Option<String> {
+        let resource_ptr = if *resource == self.document_base {
+            ""
+        } else {
+            self.resources.get(resource.as_str())?
+        };
+
+        if fragment.is_empty() {
+            // the document itself is not a schema
+            if *resource == self.document_base {
+                return None;
+            }
+
+            Some(resource_ptr.to_owned())
+        } else if fragment.starts_with('/') {
+            Some(format!("{resource_ptr}{fragment}"))
+        } else {
+            self.anchors
+                .get(&(resource.to_string(), fragment.to_owned()))
+                .cloned()
+        }
+    }
+
+    /// Finds the schema at `fragment` within the schema resource identified by `resource`.
+    fn lookup(&self, resource: &Url, fragment: &str) -> Option<Node<'_>> {
+        let ptr = self.locate(resource, fragment)?;
+        let value = self.document.pointer(&ptr)?;
+
+        // resources and anchors are keyed by the base in effect within their schema
+        let base = match value.get("$id").and_then(Value::as_str) {
+            Some(id) if fragment.starts_with('/') => {
+                let mut url = resource.join(id).ok()?;
+                url.set_fragment(None);
+                url
+            }
+            _ => resource.clone(),
+        };
+
+        Some(Node { value, base })
+    }
+}
+
+/// Kind of value found at a location in the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// A schema, whose keys are keywords.
+    Schema,
+
+    /// A map of schemas, whose keys are names, like `properties`.
+    SchemaMap,
+
+    /// Any other part of the document.
+    Other,
+}
+
+impl Position {
+    /// Returns the position of the value under `key`, or `None` if it holds literal data.
+    fn child(self, key: &str) -> Option<Position> {
+        match self {
+            Position::Schema => match key {
+                "example" | "examples" | "default" | "enum" | "const" => None,
+
+                "properties" | "patternProperties" | "dependentSchemas" | "$defs"
+                | "definitions" => Some(Position::SchemaMap),
+
+                "items"
+                | "prefixItems"
+                | "additionalProperties"
+                | "allOf"
+                | "anyOf"
+                | "oneOf"
+                | "not"
+                | "if"
+                | "then"
+                | "else"
+                | "contains"
+                | "propertyNames"
+                | "unevaluatedItems"
+                | "unevaluatedProperties"
+                | "contentSchema" => Some(Position::Schema),
+
+                _ => Some(Position::Other),
+            },
+
+            Position::SchemaMap => Some(Position::Schema),
+
+            Position::Other => match key {
+                "example" | "examples" => None,
+                "schema" => Some(Position::Schema),
+                "schemas" => Some(Position::SchemaMap),
+                _ => Some(Position::Other),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{FromRef, SchemaType, SchemaTypeSet};
+
+    fn spec() -> Spec {
+        serde_yml::from_str(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  $id: https://example.com/schemas/pet
+                  type: object
+                  properties:
+                    owner:
+                      $ref: owner
+                    tag:
+                      $anchor: tag
+                      type: string
+                    alias:
+                      $ref: '#tag'
+                    legacy:
+                      $ref: '#/components/schemas/Node'
+                    default:
+                      $id: https://example.com/schemas/pet-default
+                      type: integer
+                Owner:
+                  $id: https://example.com/schemas/owner
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                      default: ferris
+                    pet:
+                      $ref: pet#/properties/tag
+                  required: [name]
+                Cat:
+                  $id: https://example.com/schemas/cat
+                  type: object
+                  properties:
+                    owner:
+                      $ref: owner
+                  required: [owner]
+                Dog:
+                  $id: https://example.com/schemas/dog
+                  allOf:
+                    - $ref: owner
+                Node:
+                  $anchor: node
+                  type: boolean
+                Alias:
+                  $ref: '#node'
+        "})
+        .unwrap()
+    }
+
+    fn single(type_: SchemaType) -> Option<SchemaTypeSet> {
+        Some(SchemaTypeSet::Single(type_))
+    }
+
+    #[test]
+    fn base_uri() {
+        let spec = spec();
+        let pet = ObjectSchema::from_ref(&spec, "#/components/schemas/Pet").unwrap();
+
+        assert_eq!(
+            pet.base_uri(None).as_deref(),
+            Some("https://example.com/schemas/pet"),
+        );
+        assert_eq!(
+            pet.base_uri(Some("https://other.com/")).as_deref(),
+            Some("https://example.com/schemas/pet"),
+        );
+
+        let relative = ObjectSchema {
+            id: Some("child".to_owned()),
+            ..ObjectSchema::default()
+        };
+        assert_eq!(
+            relative
+                .base_uri(Some("https://example.com/schemas/pet"))
+                .as_deref(),
+            Some("https://example.com/schemas/child"),
+        );
+        assert_eq!(relative.base_uri(None).as_deref(), Some("child"));
+        assert_eq!(
+            ObjectSchema::default()
+                .base_uri(Some("https://example.com/"))
+                .as_deref(),
+            Some("https://example.com/"),
+        );
+    }
+
+    #[test]
+    fn resolve_relative_to_id() {
+        let spec = spec();
+        let pet = ObjectSchema::from_ref(&spec, "#/components/schemas/Pet").unwrap();
+        let base = pet.base_uri(None);
+        let resolve = |prop: &str| pet.properties[prop].resolve_with_base(&spec, base.as_deref());
+
+        let owner = resolve("owner").unwrap();
+        assert_eq!(
+            owner.id.as_deref(),
+            Some("https://example.com/schemas/owner")
+        );
+
+        let alias = resolve("alias").unwrap();
+        assert_eq!(alias.anchor.as_deref(), Some("tag"));
+        assert_eq!(alias.schema_type, single(SchemaType::String));
+
+        // JSON pointers are relative to the schema resource
+        resolve("legacy").unwrap_err();
+
+        // without a base, references are relative to the document
+        pet.properties["alias"].resolve(&spec).unwrap_err();
+
+        let tag = owner.properties["pet"]
+            .resolve_with_base(&spec, owner.base_uri(None).as_deref())
+            .unwrap();
+        assert_eq!(tag.anchor.as_deref(), Some("tag"));
+    }
+
+    #[test]
+    fn resolver_returns_base() {
+        let spec = spec();
+        let resolver = SchemaResolver::new();
+
+        let pet = ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Pet".to_owned(),
+        };
+        let (pet, base) = resolver.resolve(&spec, &pet, None).unwrap();
+        assert_eq!(base.as_deref(), Some("https://example.com/schemas/pet"));
+
+        let (owner, base) = resolver
+            .resolve(
+                &spec,
+                pet.properties["owner"].as_object().unwrap(),
+                base.as_deref(),
+            )
+            .unwrap();
+        assert_eq!(base.as_deref(), Some("https://example.com/schemas/owner"));
+
+        let (tag, base) = resolver
+            .resolve(
+                &spec,
+                owner.properties["pet"].as_object().unwrap(),
+                base.as_deref(),
+            )
+            .unwrap();
+        assert_eq!(tag.anchor.as_deref(), Some("tag"));
+        assert_eq!(base.as_deref(), Some("https://example.com/schemas/pet"));
+
+        let node = ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Alias".to_owned(),
+        };
+        let (_, base) = resolver.resolve(&spec, &node, None).unwrap();
+        assert_eq!(base, None);
+    }
+
+    #[test]
+    fn walkers_track_base() {
+        let spec = spec();
+        let cat = ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Cat".to_owned(),
+        };
+
+        let deep = cat.resolve_deep(&spec, 2).unwrap();
+        let Some(ObjectOrReference::Object(owner)) = deep.properties["owner"].as_object() else {
+            panic!("owner should be inlined");
+        };
+        assert_eq!(
+            owner.id.as_deref(),
+            Some("https://example.com/schemas/owner")
+        );
+        let Some(ObjectOrReference::Object(tag)) = owner.properties["pet"].as_object() else {
+            panic!("owner's pet should be inlined");
+        };
+        assert_eq!(tag.anchor.as_deref(), Some("tag"));
+        // inlined schema keeps the base of the resource it came from
+        assert_eq!(tag.id.as_deref(), Some("https://example.com/schemas/pet"));
+
+        let cat = cat.resolve(&spec).unwrap();
+        assert_eq!(
+            cat.example_value(&spec).unwrap(),
+            serde_json::json!({ "owner": { "name": "ferris" } }),
+        );
+
+        let mut value = serde_json::json!({ "owner": {} });
+        cat.apply_defaults(&mut value, &spec).unwrap();
+        assert_eq!(value, serde_json::json!({ "owner": { "name": "ferris" } }));
+
+        let dog = ObjectSchema::from_ref(&spec, "#/components/schemas/Dog").unwrap();
+        let flat = dog.flatten_all_of(&spec).unwrap();
+        assert_eq!(flat.required, ["name"]);
+        assert_eq!(
+            flat.properties["pet"],
+            Schema::from(ObjectOrReference::Ref {
+                ref_path: "https://example.com/schemas/pet#/properties/tag".to_owned(),
+            }),
+        );
+    }
+
+    #[test]
+    fn literal_keywords_only_skipped_in_keyword_position() {
+        let spec = spec();
+
+        let default =
+            ObjectSchema::from_ref(&spec, "https://example.com/schemas/pet-default").unwrap();
+        assert_eq!(default.schema_type, single(SchemaType::Integer));
+    }
+
+    #[test]
+    fn resolve_ids_and_anchors_from_document() {
+        let spec = spec();
+
+        let node = ObjectSchema::from_ref(&spec, "#node").unwrap();
+        assert_eq!(node.schema_type, single(SchemaType::Boolean));
+
+        let alias = ObjectSchema::from_ref(&spec, "#/components/schemas/Alias").unwrap();
+        assert_eq!(alias, node);
+
+        let owner = ObjectSchema::from_ref(&spec, "https://example.com/schemas/owner").unwrap();
+        assert_eq!(owner.schema_type, single(SchemaType::Object));
+
+        let tag = ObjectSchema::from_ref(&spec, "https://example.com/schemas/pet#tag").unwrap();
+        assert_eq!(tag.schema_type, single(SchemaType::String));
+
+        let err = ObjectSchema::from_ref(&spec, "https://example.com/schemas/missing").unwrap_err();
+        assert_eq!(
+            err,
+            RefError::Unresolvable("https://example.com/schemas/missing".to_owned()),
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! Schema specification for [OpenAPI 3.1](https://spec.openapis.org/oas/v3.1.0)
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Lists the required security schemes to execute an operation.
+///
+/// The name used for each property MUST correspond to a security scheme declared in the
+/// [Security Schemes](super::Components::security_schemes) under the Components Object. Each value
+/// is a list of scope names required for execution, which is empty for scheme types that do not
+/// use scopes.
+///
+/// When a list of Security Requirement Objects is defined on the OpenAPI Object or Operation
+/// Object, only one of them needs to be satisfied to authorize the request.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#security-requirement-object>.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SecurityRequirement(pub BTreeMap<String, Vec<String>>);
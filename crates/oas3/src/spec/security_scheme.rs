@@ -1,6 +1,7 @@
+use derive_more::derive::Display;
 use serde::{Deserialize, Serialize};
 
-use super::Flows;
+use super::{resolve_component, Flows, FromRef, RefError, RefType, Spec};
 
 /// Defines a security scheme that can be used by the operations.
 ///
@@ -101,6 +102,53 @@ pub enum SecurityScheme {
     },
 }
 
+impl SecurityScheme {
+    /// Returns the type of this security scheme.
+    pub fn scheme_type(&self) -> SecuritySchemeType {
+        match self {
+            Self::ApiKey { .. } => SecuritySchemeType::ApiKey,
+            Self::Http { .. } => SecuritySchemeType::Http,
+            Self::OAuth2 { .. } => SecuritySchemeType::OAuth2,
+            Self::OpenIdConnect { .. } => SecuritySchemeType::OpenIdConnect,
+            Self::MutualTls { .. } => SecuritySchemeType::MutualTls,
+        }
+    }
+}
+
+impl FromRef for SecurityScheme {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        resolve_component(spec, path, RefType::SecurityScheme, |cs, name| {
+            cs.security_schemes.get(name)
+        })
+    }
+}
+
+/// Type of a [`SecurityScheme`].
+///
+/// Displays as the value of the scheme's `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum SecuritySchemeType {
+    /// API key authentication.
+    #[display("apiKey")]
+    ApiKey,
+
+    /// HTTP authentication.
+    #[display("http")]
+    Http,
+
+    /// OAuth2 authentication.
+    #[display("oauth2")]
+    OAuth2,
+
+    /// OpenID Connect authentication.
+    #[display("openIdConnect")]
+    OpenIdConnect,
+
+    /// Mutual TLS authentication.
+    #[display("mutualTLS")]
+    MutualTls,
+}
+
 #[cfg(test)]
 mod tests {
     use url::Url;
@@ -176,4 +224,40 @@ mod tests {
             _ => panic!("wrong security scheme type"),
         }
     }
+
+    #[test]
+    fn scheme_type_and_lookup() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: Auth
+              version: 0.0.0
+            components:
+              securitySchemes:
+                key:
+                  type: apiKey
+                  name: X-API-Key
+                  in: header
+                bearer:
+                  type: http
+                  scheme: bearer
+                auth:
+                  $ref: '#/components/securitySchemes/bearer'
+                mtls:
+                  type: mutualTLS
+        "})
+        .unwrap();
+
+        let key = spec.security_scheme("key").unwrap().unwrap();
+        assert_eq!(key.scheme_type(), SecuritySchemeType::ApiKey);
+        assert_eq!(key.scheme_type().to_string(), "apiKey");
+
+        let auth = spec.security_scheme("auth").unwrap().unwrap();
+        assert_eq!(auth.scheme_type(), SecuritySchemeType::Http);
+
+        let mtls = spec.security_scheme("mtls").unwrap().unwrap();
+        assert_eq!(mtls.scheme_type().to_string(), "mutualTLS");
+
+        assert!(spec.security_scheme("missing").unwrap().is_none());
+    }
 }
@@ -1,7 +1,35 @@
 use std::collections::BTreeMap;
 
+use derive_more::derive::{Display, Error};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use super::spec_extensions;
+
+static RE_VARIABLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{(?P<name>[^{}]+)\}").unwrap());
+
+/// Server URL expansion errors.
+#[derive(Debug, Clone, PartialEq, Display, Error)]
+pub enum ServerError {
+    /// Variable is referenced or overridden but not declared by the server.
+    #[display("Server variable not declared: {}", _0)]
+    UndeclaredVariable(#[error(not(source))] String),
+
+    /// Variable value is not one of the variable's allowed values.
+    #[display("Value {value:?} for server variable `{name}` is not one of {allowed:?}")]
+    InvalidVariableValue {
+        /// Variable name.
+        name: String,
+
+        /// Provided value.
+        value: String,
+
+        /// Allowed values.
+        allowed: Vec<String>,
+    },
+}
+
 /// An object representing a Server.
 ///
 /// See <https://spec.openapis.org/oas/v3.1.0#server-object>.
@@ -25,6 +53,72 @@ pub struct Server {
     /// The value is used for substitution in the server's URL template.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub variables: BTreeMap<String, ServerVariable>,
+
+    /// Specification extensions.
+    ///
+    /// Only "x-" prefixed keys are collected, and the prefix is stripped.
+    ///
+    /// See <https://spec.openapis.org/oas/v3.1.0#specification-extensions>.
+    #[serde(flatten, with = "spec_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl Server {
+    /// Returns this server's URL with variable placeholders substituted.
+    ///
+    /// Each `{name}` placeholder is replaced by the value in `overrides`, if present, otherwise by
+    /// the variable's default. Overridden values must be a member of the variable's `enum` list
+    /// when it is non-empty. Placeholders without a matching variable are an error.
+    pub fn resolve_url(&self, overrides: &BTreeMap<String, String>) -> Result<String, ServerError> {
+        if let Some(name) = overrides
+            .keys()
+            .find(|name| !self.variables.contains_key(*name))
+        {
+            return Err(ServerError::UndeclaredVariable(name.clone()));
+        }
+
+        let mut url = String::with_capacity(self.url.len());
+        let mut last_end = 0;
+
+        for caps in RE_VARIABLE.captures_iter(&self.url) {
+            let placeholder = caps.get(0).unwrap();
+            let name = &caps["name"];
+
+            url.push_str(&self.url[last_end..placeholder.start()]);
+            last_end = placeholder.end();
+
+            let variable = self
+                .variables
+                .get(name)
+                .ok_or_else(|| ServerError::UndeclaredVariable(name.to_owned()))?;
+
+            let value = match overrides.get(name) {
+                Some(value) => {
+                    variable.validate_value(name, value)?;
+                    value
+                }
+                None => &variable.default,
+            };
+
+            url.push_str(value);
+        }
+
+        url.push_str(&self.url[last_end..]);
+
+        Ok(url)
+    }
+}
+
+impl Default for Server {
+    /// Returns the server implied when none are declared, which has a URL of `/`.
+    fn default() -> Self {
+        Self {
+            url: "/".to_owned(),
+            description: None,
+            variables: BTreeMap::new(),
+            extensions: BTreeMap::new(),
+        }
+    }
 }
 
 /// An object representing a Server Variable for server URL template substitution.
@@ -49,4 +143,106 @@ pub struct ServerVariable {
     /// [CommonMark]: https://spec.commonmark.org/
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Specification extensions.
+    ///
+    /// Only "x-" prefixed keys are collected, and the prefix is stripped.
+    ///
+    /// See <https://spec.openapis.org/oas/v3.1.0#specification-extensions>.
+    #[serde(flatten, with = "spec_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+impl ServerVariable {
+    fn validate_value(&self, name: &str, value: &str) -> Result<(), ServerError> {
+        if self.substitutions_enum.is_empty()
+            || self
+                .substitutions_enum
+                .iter()
+                .any(|allowed| allowed == value)
+        {
+            Ok(())
+        } else {
+            Err(ServerError::InvalidVariableValue {
+                name: name.to_owned(),
+                value: value.to_owned(),
+                allowed: self.substitutions_enum.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templated_server() -> Server {
+        serde_yml::from_str(indoc::indoc! {"
+            url: https://{env}.example.com:{port}/v1
+            variables:
+              env:
+                default: api
+                enum: [api, staging]
+              port:
+                default: '443'
+        "})
+        .unwrap()
+    }
+
+    #[test]
+    fn resolve_url_defaults() {
+        let server = templated_server();
+        assert_eq!(
+            server.resolve_url(&BTreeMap::new()).unwrap(),
+            "https://api.example.com:443/v1",
+        );
+    }
+
+    #[test]
+    fn resolve_url_overrides() {
+        let server = templated_server();
+
+        let overrides = BTreeMap::from([
+            ("env".to_owned(), "staging".to_owned()),
+            ("port".to_owned(), "8443".to_owned()),
+        ]);
+        assert_eq!(
+            server.resolve_url(&overrides).unwrap(),
+            "https://staging.example.com:8443/v1",
+        );
+
+        let overrides = BTreeMap::from([("env".to_owned(), "prod".to_owned())]);
+        assert_eq!(
+            server.resolve_url(&overrides).unwrap_err(),
+            ServerError::InvalidVariableValue {
+                name: "env".to_owned(),
+                value: "prod".to_owned(),
+                allowed: vec!["api".to_owned(), "staging".to_owned()],
+            },
+        );
+
+        let overrides = BTreeMap::from([("region".to_owned(), "eu".to_owned())]);
+        assert_eq!(
+            server.resolve_url(&overrides).unwrap_err(),
+            ServerError::UndeclaredVariable("region".to_owned()),
+        );
+    }
+
+    #[test]
+    fn resolve_url_undeclared_placeholder() {
+        let server = Server {
+            url: "https://{tenant}.example.com".to_owned(),
+            ..Server::default()
+        };
+
+        assert_eq!(
+            server.resolve_url(&BTreeMap::new()).unwrap_err(),
+            ServerError::UndeclaredVariable("tenant".to_owned()),
+        );
+
+        assert_eq!(
+            Server::default().resolve_url(&BTreeMap::new()).unwrap(),
+            "/",
+        );
+    }
 }
@@ -0,0 +1,57 @@
+//! Lenient deserialization of response maps keyed by status code.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Deserializes an optional map keyed by status code, accepting integer keys.
+///
+/// Integer keys (e.g. from an unquoted `200:` in YAML) are normalized to strings.
+pub(crate) fn deserialize<'de, D, V>(
+    deserializer: D,
+) -> Result<Option<BTreeMap<String, V>>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    let map = Option::<BTreeMap<StatusKey, V>>::deserialize(deserializer)?;
+    Ok(map.map(|map| map.into_iter().map(|(key, val)| (key.0, val)).collect()))
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct StatusKey(String);
+
+impl<'de> Deserialize<'de> for StatusKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StatusKeyVisitor;
+
+        impl de::Visitor<'_> for StatusKeyVisitor {
+            type Value = StatusKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a status code string or integer")
+            }
+
+            fn visit_str<E: de::Error>(self, val: &str) -> Result<Self::Value, E> {
+                Ok(StatusKey(val.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, val: String) -> Result<Self::Value, E> {
+                Ok(StatusKey(val))
+            }
+
+            fn visit_u64<E: de::Error>(self, val: u64) -> Result<Self::Value, E> {
+                Ok(StatusKey(val.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, val: i64) -> Result<Self::Value, E> {
+                Ok(StatusKey(val.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(StatusKeyVisitor)
+    }
+}
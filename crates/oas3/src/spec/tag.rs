@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::spec_extensions;
+use super::{spec_extensions, ExternalDoc};
 
 /// Adds metadata to a single tag that is used by the [Operation Object].
 ///
@@ -21,12 +21,10 @@ pub struct Tag {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
-    //
-    // /// Additional external documentation for this tag.
-    // #[serde(default)]
-    // #[serde(skip_serializing_if = "Vec::is_empty")]
-    // pub external_docs: Vec<ExternalDoc>,
-    //
+    /// Additional external documentation for this tag.
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+
     /// Specification extensions.
     ///
     /// Only "x-" prefixed keys are collected, and the prefix is stripped.
@@ -35,3 +33,28 @@ pub struct Tag {
     #[serde(flatten, with = "spec_extensions")]
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let yaml = indoc::indoc! {"
+            name: pets
+            description: Pet operations.
+            externalDocs:
+              url: https://example.com/docs/pets
+            x-display-name: Pets
+        "};
+
+        let tag = serde_yml::from_str::<Tag>(yaml).unwrap();
+        assert_eq!(
+            tag.external_docs.as_ref().unwrap().url.as_str(),
+            "https://example.com/docs/pets",
+        );
+        assert_eq!(tag.extensions["display-name"], "Pets");
+
+        assert_eq!(serde_yml::to_string(&tag).unwrap(), yaml);
+    }
+}
@@ -0,0 +1,584 @@
+//! Traversal of the schemas and media types in a spec.
+
+use std::collections::BTreeMap;
+
+use http::Method;
+use log::error;
+
+use super::{
+    Callback, Header, MediaType, ObjectOrReference, ObjectSchema, Operation, Parameter, PathItem,
+    RequestBody, Response, Schema, SchemaType, SchemaTypeSet, Spec,
+};
+use crate::json_pointer;
+
+/// Location of a media type within a spec's operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaTypeLocation {
+    /// Path of the operation.
+    pub path: String,
+
+    /// HTTP method of the operation.
+    pub method: Method,
+
+    /// Whether the media type describes the request body or a response.
+    pub direction: MediaTypeDirection,
+
+    /// Content type key of the media type, e.g. `application/json`.
+    pub content_type: String,
+}
+
+/// An enumerated schema, as returned by [`Spec::enums()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDefinition {
+    /// JSON pointer to the schema.
+    pub pointer: String,
+
+    /// Allowed values of the schema.
+    pub values: Vec<serde_json::Value>,
+
+    /// Type of the values, if they share one.
+    ///
+    /// Taken from the schema's non-null `type` if present, otherwise inferred from the values.
+    /// Integer and non-integer numbers are inferred as [`SchemaType::Number`].
+    pub value_type: Option<SchemaType>,
+}
+
+/// Part of an operation that a media type describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MediaTypeDirection {
+    /// Request body content.
+    Request,
+
+    /// Response content.
+    Response {
+        /// Response status code, range, or `default`.
+        status: String,
+    },
+}
+
+impl Spec {
+    /// Invokes `f` for every media type in the request bodies and responses of this spec's
+    /// operations, along with its location.
+    ///
+    /// Referenced request bodies and responses are resolved; those that fail to resolve are
+    /// skipped.
+    pub fn for_each_media_type(&self, mut f: impl FnMut(MediaTypeLocation, &MediaType)) {
+        for (path, method, op) in self.operations() {
+            let mut visit = |direction: &MediaTypeDirection,
+                             content: &BTreeMap<String, MediaType>| {
+                for (content_type, media_type) in content {
+                    let location = MediaTypeLocation {
+                        path: path.clone(),
+                        method: method.clone(),
+                        direction: direction.clone(),
+                        content_type: content_type.clone(),
+                    };
+
+                    f(location, media_type);
+                }
+            };
+
+            if let Some(body) = &op.request_body {
+                match body.resolve(self) {
+                    Ok(body) => visit(&MediaTypeDirection::Request, &body.content),
+                    Err(err) => error!("{}", err),
+                }
+            }
+
+            for (status, res) in op.responses(self) {
+                visit(&MediaTypeDirection::Response { status }, &res.content);
+            }
+        }
+    }
+
+    /// Returns iterator over every schema in this spec, keyed by its JSON pointer.
+    ///
+    /// Schemas are collected from components, paths, and webhooks, including those nested in
+    /// parameters, headers, request or response content, and the operations of callbacks. Schemas
+    /// of referenced components, such as callbacks, are collected once, from the component. Inline schemas are descended into
+    /// through `properties`, `patternProperties`, `items`, `allOf`, `anyOf`, `oneOf`,
+    /// `additionalProperties`, and `not`. Boolean schemas are skipped. References are yielded but
+    /// not followed.
+    pub fn schemas(&self) -> impl Iterator<Item = (String, &ObjectOrReference<ObjectSchema>)> {
+        let mut walker = SchemaWalker::default();
+
+        if let Some(components) = &self.components {
+            for (name, schema) in &components.schemas {
+                walker.schema(json_pointer::append("#/components/schemas", name), schema);
+            }
+
+            for (name, param) in &components.parameters {
+                if let ObjectOrReference::Object(param) = param {
+                    walker.parameter(json_pointer::append("#/components/parameters", name), param);
+                }
+            }
+
+            for (name, res) in &components.responses {
+                if let ObjectOrReference::Object(res) = res {
+                    walker.response(json_pointer::append("#/components/responses", name), res);
+                }
+            }
+
+            for (name, body) in &components.request_bodies {
+                if let ObjectOrReference::Object(body) = body {
+                    walker.request_body(
+                        json_pointer::append("#/components/requestBodies", name),
+                        body,
+                    );
+                }
+            }
+
+            walker.headers("#/components/headers", &components.headers);
+
+            for (name, item) in &components.path_items {
+                if let ObjectOrReference::Object(item) = item {
+                    walker.path_item(json_pointer::append("#/components/pathItems", name), item);
+                }
+            }
+
+            for (name, callback) in &components.callbacks {
+                if let ObjectOrReference::Object(callback) = callback {
+                    walker.callback(
+                        json_pointer::append("#/components/callbacks", name),
+                        callback,
+                    );
+                }
+            }
+        }
+
+        for (path, item) in self.paths.iter().flatten() {
+            walker.path_item(json_pointer::append("#/paths", path), item);
+        }
+
+        for (name, item) in &self.webhooks {
+            walker.path_item(json_pointer::append("#/webhooks", name), item);
+        }
+
+        walker.schemas.into_iter()
+    }
+
+    /// Returns every inline schema that declares a non-empty `enum`.
+    ///
+    /// See [`schemas()`](Self::schemas) for which schemas are considered.
+    pub fn enums(&self) -> Vec<EnumDefinition> {
+        self.schemas()
+            .filter_map(|(pointer, schema)| match schema {
+                ObjectOrReference::Object(schema) if !schema.enum_values.is_empty() => {
+                    Some(EnumDefinition {
+                        pointer,
+                        value_type: enum_value_type(schema),
+                        values: schema.enum_values.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns the declared or inferred type of an enumerated schema's values.
+fn enum_value_type(schema: &ObjectSchema) -> Option<SchemaType> {
+    let declared = match &schema.schema_type {
+        Some(SchemaTypeSet::Single(type_)) => Some(*type_),
+        Some(SchemaTypeSet::Multiple(types)) => {
+            let mut types = types.iter().filter(|type_| **type_ != SchemaType::Null);
+
+            match (types.next(), types.next()) {
+                (Some(type_), None) => Some(*type_),
+                _ => None,
+            }
+        }
+        None => None,
+    };
+
+    if declared.is_some() {
+        return declared;
+    }
+
+    let mut inferred = None;
+
+    for value in &schema.enum_values {
+        let type_ = match value {
+            serde_json::Value::Null => continue,
+            serde_json::Value::Bool(_) => SchemaType::Boolean,
+            serde_json::Value::Number(num) if num.is_f64() => SchemaType::Number,
+            serde_json::Value::Number(_) => SchemaType::Integer,
+            serde_json::Value::String(_) => SchemaType::String,
+            serde_json::Value::Array(_) => SchemaType::Array,
+            serde_json::Value::Object(_) => SchemaType::Object,
+        };
+
+        inferred = match (inferred, type_) {
+            (None, type_) => Some(type_),
+            (Some(prev), type_) if prev == type_ => Some(type_),
+            (
+                Some(SchemaType::Integer | SchemaType::Number),
+                SchemaType::Integer | SchemaType::Number,
+            ) => Some(SchemaType::Number),
+            _ => return None,
+        };
+    }
+
+    inferred
+}
+
+/// Appends an escaped reference token to a JSON pointer.
+#[derive(Default)]
+struct SchemaWalker<'a> {
+    schemas: Vec<(String, &'a ObjectOrReference<ObjectSchema>)>,
+}
+
+impl<'a> SchemaWalker<'a> {
+    fn schema(&mut self, ptr: String, schema: &'a ObjectOrReference<ObjectSchema>) {
+        self.schemas.push((ptr.clone(), schema));
+
+        let ObjectOrReference::Object(schema) = schema else {
+            return;
+        };
+
+        for (name, prop) in &schema.properties {
+            self.subschema(
+                json_pointer::append(&format!("{ptr}/properties"), name),
+                prop,
+            );
+        }
+
+        for (pattern, prop) in &schema.pattern_properties {
+            self.subschema(
+                json_pointer::append(&format!("{ptr}/patternProperties"), pattern),
+                prop,
+            );
+        }
+
+        if let Some(items) = &schema.items {
+            self.subschema(format!("{ptr}/items"), items);
+        }
+
+        for (keyword, members) in [
+            ("allOf", &schema.all_of),
+            ("anyOf", &schema.any_of),
+            ("oneOf", &schema.one_of),
+        ] {
+            for (idx, member) in members.iter().enumerate() {
+                self.subschema(format!("{ptr}/{keyword}/{idx}"), member);
+            }
+        }
+
+        if let Some(additional) = &schema.additional_properties {
+            self.subschema(format!("{ptr}/additionalProperties"), additional);
+        }
+
+        if let Some(not) = &schema.not {
+            self.subschema(format!("{ptr}/not"), not);
+        }
+    }
+
+    /// Collects a subschema, skipping boolean schemas.
+    fn subschema(&mut self, ptr: String, schema: &'a Schema) {
+        if let Some(schema) = schema.as_object() {
+            self.schema(ptr, schema);
+        }
+    }
+
+    fn path_item(&mut self, ptr: String, item: &'a PathItem) {
+        self.parameters(&ptr, &item.parameters);
+
+        for (method, op) in item.methods() {
+            let method = method.as_str().to_lowercase();
+            self.operation(format!("{ptr}/{method}"), op);
+        }
+    }
+
+    fn operation(&mut self, ptr: String, op: &'a Operation) {
+        self.parameters(&ptr, &op.parameters);
+
+        if let Some(ObjectOrReference::Object(body)) = &op.request_body {
+            self.request_body(format!("{ptr}/requestBody"), body);
+        }
+
+        for (status, res) in op.responses.iter().flatten() {
+            if let ObjectOrReference::Object(res) = res {
+                self.response(
+                    json_pointer::append(&format!("{ptr}/responses"), status),
+                    res,
+                );
+            }
+        }
+
+        for (name, callback) in &op.callbacks {
+            if let ObjectOrReference::Object(callback) = callback {
+                self.callback(
+                    json_pointer::append(&format!("{ptr}/callbacks"), name),
+                    callback,
+                );
+            }
+        }
+    }
+
+    fn callback(&mut self, ptr: String, callback: &'a Callback) {
+        for (expression, item) in &callback.paths {
+            self.path_item(json_pointer::append(&ptr, expression), item);
+        }
+    }
+
+    fn parameters(&mut self, ptr: &str, params: &'a [ObjectOrReference<Parameter>]) {
+        for (idx, param) in params.iter().enumerate() {
+            if let ObjectOrReference::Object(param) = param {
+                self.parameter(format!("{ptr}/parameters/{idx}"), param);
+            }
+        }
+    }
+
+    fn parameter(&mut self, ptr: String, param: &'a Parameter) {
+        if let Some(schema) = &param.schema {
+            self.schema(format!("{ptr}/schema"), schema);
+        }
+
+        if let Some(content) = &param.content {
+            self.content(&ptr, content);
+        }
+    }
+
+    fn request_body(&mut self, ptr: String, body: &'a RequestBody) {
+        self.content(&ptr, &body.content);
+    }
+
+    fn response(&mut self, ptr: String, res: &'a Response) {
+        self.headers(&format!("{ptr}/headers"), &res.headers);
+        self.content(&ptr, &res.content);
+    }
+
+    fn headers(&mut self, ptr: &str, headers: &'a BTreeMap<String, ObjectOrReference<Header>>) {
+        for (name, header) in headers {
+            if let ObjectOrReference::Object(header) = header {
+                self.header(json_pointer::append(ptr, name), header);
+            }
+        }
+    }
+
+    fn header(&mut self, ptr: String, header: &'a Header) {
+        if let Some(schema) = &header.schema {
+            self.schema(format!("{ptr}/schema"), schema);
+        }
+
+        if let Some(content) = &header.content {
+            self.content(&ptr, content);
+        }
+    }
+
+    fn content(&mut self, ptr: &str, content: &'a BTreeMap<String, MediaType>) {
+        for (media_type, media) in content {
+            let ptr = json_pointer::append(&format!("{ptr}/content"), media_type);
+
+            if let Some(schema) = &media.schema {
+                self.schema(format!("{ptr}/schema"), schema);
+            }
+
+            for (name, encoding) in &media.encoding {
+                let ptr = json_pointer::append(&format!("{ptr}/encoding"), name);
+                self.headers(&format!("{ptr}/headers"), &encoding.headers);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_with_pointers() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets/{id}:
+                parameters:
+                  - name: id
+                    in: path
+                    required: true
+                    schema:
+                      type: string
+                get:
+                  callbacks:
+                    onChange:
+                      '{$request.query.url}':
+                        post:
+                          requestBody:
+                            content:
+                              application/json:
+                                schema:
+                                  type: object
+                          responses:
+                            '204':
+                              description: No Content
+                    onDelete:
+                      $ref: '#/components/callbacks/Deleted'
+                  responses:
+                    '200':
+                      description: OK
+                      headers:
+                        X-Rate-Limit:
+                          schema:
+                            type: integer
+                      content:
+                        application/json:
+                          schema:
+                            $ref: '#/components/schemas/Pet'
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  properties:
+                    extra:
+                      additionalProperties:
+                        oneOf:
+                          - type: string
+                          - type: integer
+                    tags:
+                      type: array
+                      items:
+                        type: string
+              callbacks:
+                Deleted:
+                  '{$request.body#/url}':
+                    post:
+                      parameters:
+                        - name: id
+                          in: query
+                          schema:
+                            type: string
+                      responses:
+                        '204':
+                          description: No Content
+        "})
+        .unwrap();
+
+        let pointers = spec.schemas().map(|(ptr, _)| ptr).collect::<Vec<_>>();
+
+        assert_eq!(
+            pointers,
+            [
+                "#/components/schemas/Pet",
+                "#/components/schemas/Pet/properties/extra",
+                "#/components/schemas/Pet/properties/extra/additionalProperties",
+                "#/components/schemas/Pet/properties/extra/additionalProperties/oneOf/0",
+                "#/components/schemas/Pet/properties/extra/additionalProperties/oneOf/1",
+                "#/components/schemas/Pet/properties/tags",
+                "#/components/schemas/Pet/properties/tags/items",
+                "#/components/callbacks/Deleted/{$request.body#~1url}/post/parameters/0/schema",
+                "#/paths/~1pets~1{id}/parameters/0/schema",
+                "#/paths/~1pets~1{id}/get/responses/200/headers/X-Rate-Limit/schema",
+                "#/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema",
+                "#/paths/~1pets~1{id}/get/callbacks/onChange/{$request.query.url}/post/requestBody\
+                 /content/application~1json/schema",
+            ],
+        );
+
+        let (_, schema) = spec
+            .schemas()
+            .find(|(ptr, _)| ptr.ends_with("application~1json/schema"))
+            .unwrap();
+        assert!(matches!(schema, ObjectOrReference::Ref { .. }));
+    }
+
+    #[test]
+    fn enums() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            components:
+              schemas:
+                Pet:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                    status:
+                      type: [string, 'null']
+                      enum: [available, sold, null]
+                Size:
+                  enum: [1, 2.5]
+        "})
+        .unwrap();
+
+        assert_eq!(
+            spec.enums(),
+            [
+                EnumDefinition {
+                    pointer: "#/components/schemas/Pet/properties/status".to_owned(),
+                    values: vec![
+                        serde_json::json!("available"),
+                        serde_json::json!("sold"),
+                        serde_json::Value::Null,
+                    ],
+                    value_type: Some(SchemaType::String),
+                },
+                EnumDefinition {
+                    pointer: "#/components/schemas/Size".to_owned(),
+                    values: vec![serde_json::json!(1), serde_json::json!(2.5)],
+                    value_type: Some(SchemaType::Number),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn for_each_media_type() {
+        let spec = serde_yml::from_str::<Spec>(indoc::indoc! {"
+            openapi: 3.1.0
+            info:
+              title: test
+              version: v1
+            paths:
+              /pets:
+                post:
+                  requestBody:
+                    $ref: '#/components/requestBodies/NewPet'
+                  responses:
+                    '201':
+                      description: Created
+                      content:
+                        application/json: {}
+                        application/xml: {}
+                    default:
+                      $ref: '#/components/responses/Error'
+            components:
+              requestBodies:
+                NewPet:
+                  content:
+                    application/json: {}
+              responses:
+                Error:
+                  description: Error
+                  content:
+                    application/problem+json: {}
+        "})
+        .unwrap();
+
+        let mut locations = vec![];
+        spec.for_each_media_type(|location, _| locations.push(location));
+
+        assert_eq!(locations.len(), 4);
+        assert_eq!(
+            locations[0],
+            MediaTypeLocation {
+                path: "/pets".to_owned(),
+                method: Method::POST,
+                direction: MediaTypeDirection::Request,
+                content_type: "application/json".to_owned(),
+            },
+        );
+        assert_eq!(
+            locations[3].direction,
+            MediaTypeDirection::Response {
+                status: "default".to_owned(),
+            },
+        );
+        assert_eq!(locations[3].content_type, "application/problem+json");
+    }
+}
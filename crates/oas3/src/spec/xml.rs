@@ -0,0 +1,98 @@
+//! XML object for [OpenAPI 3.1](https://spec.openapis.org/oas/v3.1.0)
+
+use serde::{Deserialize, Serialize};
+
+/// A metadata object that allows for more fine-tuned XML model definitions.
+///
+/// When using arrays, XML element names are not inferred (for singular/plural forms) and the `name`
+/// property SHOULD be used to add that information.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#xml-object>.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct Xml {
+    /// Replaces the name of the element/attribute used for the described schema property.
+    ///
+    /// When defined within `items`, it will affect the name of the individual XML elements within
+    /// the list. When defined alongside `type` being `array` (outside the `items`), it will affect
+    /// the wrapping element and only if `wrapped` is `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The URI of the namespace definition.
+    ///
+    /// This MUST be in the form of an absolute URI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// The prefix to be used for the name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// Declares whether the property definition translates to an attribute instead of an element.
+    ///
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<bool>,
+
+    /// Signifies whether the array is wrapped (for example,
+    /// `<books><book/><book/></books>`) or unwrapped (`<book/><book/>`).
+    ///
+    /// Default value is `false`. The definition takes effect only when defined alongside `type`
+    /// being `array` (outside the `items`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapped: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::ObjectSchema;
+
+    #[test]
+    fn xml_round_trip() {
+        let yaml = indoc::indoc! {"
+            type: array
+            items:
+              type: string
+              xml:
+                name: animal
+            xml:
+              name: aliens
+              namespace: https://example.com/schema/sample
+              prefix: sample
+              attribute: false
+              wrapped: true
+        "};
+
+        let schema = serde_yml::from_str::<ObjectSchema>(yaml).unwrap();
+        assert_eq!(
+            schema.xml,
+            Some(Xml {
+                name: Some("aliens".to_owned()),
+                namespace: Some("https://example.com/schema/sample".to_owned()),
+                prefix: Some("sample".to_owned()),
+                attribute: Some(false),
+                wrapped: Some(true),
+            }),
+        );
+
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            value["xml"],
+            serde_json::json!({
+                "name": "aliens",
+                "namespace": "https://example.com/schema/sample",
+                "prefix": "sample",
+                "attribute": false,
+                "wrapped": true,
+            }),
+        );
+        assert_eq!(
+            value["items"]["xml"],
+            serde_json::json!({ "name": "animal" })
+        );
+
+        let round_trip = serde_json::from_value::<ObjectSchema>(value).unwrap();
+        assert_eq!(round_trip, schema);
+    }
+}
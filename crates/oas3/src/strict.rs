@@ -0,0 +1,99 @@
+//! Strict parsing that rejects unknown fields.
+
+use std::mem;
+
+use serde_json::Value;
+
+use crate::{json_pointer, Error, Spec};
+
+/// Converts a YAML value to JSON, turning scalar mapping keys into strings.
+///
+/// YAML allows non-string keys, such as unquoted response status codes (`200:`), which cannot be
+/// represented in JSON.
+pub(crate) fn yaml_to_json(mut value: serde_yml::Value) -> Result<Value, serde_yml::Error> {
+    stringify_keys(&mut value);
+    serde_yml::from_value(value)
+}
+
+fn stringify_keys(value: &mut serde_yml::Value) {
+    match value {
+        serde_yml::Value::Mapping(map) => {
+            *map = mem::take(map)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    stringify_keys(&mut value);
+
+                    let key = match key {
+                        serde_yml::Value::Number(num) => serde_yml::Value::String(num.to_string()),
+                        serde_yml::Value::Bool(bool) => serde_yml::Value::String(bool.to_string()),
+                        key => key,
+                    };
+
+                    (key, value)
+                })
+                .collect();
+        }
+
+        serde_yml::Value::Sequence(items) => items.iter_mut().for_each(stringify_keys),
+
+        serde_yml::Value::Tagged(tagged) => stringify_keys(&mut tagged.value),
+
+        _ => {}
+    }
+}
+
+/// Deserializes a spec from a JSON value, rejecting fields that are not part of the model.
+pub(crate) fn from_value_strict(input: Value) -> Result<Spec, Error> {
+    let spec = serde_json::from_value::<Spec>(input.clone())?;
+    let output = serde_json::to_value(&spec)?;
+
+    let mut unknown = Vec::new();
+    unknown_fields(&input, &output, "#", &mut unknown);
+
+    if unknown.is_empty() {
+        Ok(spec)
+    } else {
+        Err(Error::UnknownFields(unknown))
+    }
+}
+
+/// Collects pointers to object keys in `input` that did not survive a round trip to `output`.
+///
+/// Extension keys (`x-`) are always allowed. Keys holding `null` or an empty object or array are
+/// ignored since they are legitimately omitted when serializing. The `summary` and `description`
+/// fields that may accompany a `$ref` are also allowed, though they are not retained.
+fn unknown_fields(input: &Value, output: &Value, ptr: &str, unknown: &mut Vec<String>) {
+    match (input, output) {
+        (Value::Object(input), Value::Object(output)) => {
+            let is_ref = input.contains_key("$ref");
+
+            for (key, value) in input {
+                let child_ptr = format!("{ptr}/{}", json_pointer::escape(key));
+
+                match output.get(key) {
+                    Some(out) => unknown_fields(value, out, &child_ptr, unknown),
+                    None if key.starts_with("x-") || is_empty(value) => {}
+                    None if is_ref && matches!(key.as_str(), "summary" | "description") => {}
+                    None => unknown.push(child_ptr),
+                }
+            }
+        }
+
+        (Value::Array(input), Value::Array(output)) if input.len() == output.len() => {
+            for (idx, (value, out)) in input.iter().zip(output).enumerate() {
+                unknown_fields(value, out, &format!("{ptr}/{idx}"), unknown);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Object(obj) => obj.is_empty(),
+        Value::Array(arr) => arr.is_empty(),
+        _ => false,
+    }
+}
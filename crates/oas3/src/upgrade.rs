@@ -0,0 +1,314 @@
+//! Best-effort conversion of OpenAPI 3.0 documents to 3.1.
+
+use derive_more::derive::{Display, Error, From};
+use serde_json::{Map, Value};
+
+use crate::Spec;
+
+/// Version written to the `openapi` field of upgraded specs.
+const OPENAPI_3_1_VERSION: &str = "3.1.0";
+
+/// Spec upgrade errors.
+#[derive(Debug, Display, Error, From)]
+pub enum UpgradeError {
+    /// Document's `openapi` field is missing or is not a 3.0.x version.
+    #[display("Expected an OpenAPI 3.0 document, found version {:?}", _0)]
+    #[from(ignore)]
+    UnsupportedVersion(#[error(not(source))] String),
+
+    /// Upgraded document could not be deserialized.
+    #[display("Failed to deserialize upgraded spec")]
+    Deserialize(serde_json::Error),
+}
+
+/// Converts an OpenAPI 3.0 document to a 3.1 [`Spec`], on a best-effort basis.
+///
+/// Schemas are rewritten as follows:
+/// - `nullable: true` becomes a `type: [T, "null"]` type set, with `null` added to any `enum`;
+/// - `example` becomes single-entry `examples`;
+/// - boolean `exclusiveMinimum`/`exclusiveMaximum` flags are merged with `minimum`/`maximum` into
+///   their numeric form.
+///
+/// References to `#/definitions/` are rewritten to point to `#/components/schemas/`.
+///
+/// # Examples
+///
+/// ```
+/// let doc = serde_json::json!({
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1.0.0" },
+///     "components": {
+///         "schemas": {
+///             "Name": { "type": "string", "nullable": true },
+///         },
+///     },
+/// });
+///
+/// let spec = oas3::upgrade_from_3_0(doc).unwrap();
+/// assert_eq!(spec.openapi, "3.1.0");
+/// ```
+pub fn upgrade_from_3_0(mut doc: Value) -> Result<Spec, UpgradeError> {
+    let version = doc
+        .get("openapi")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+
+    let is_3_0 = semver::Version::parse(version)
+        .is_ok_and(|version| version.major == 3 && version.minor == 0);
+
+    if !is_3_0 {
+        return Err(UpgradeError::UnsupportedVersion(version.to_owned()));
+    }
+
+    upgrade_value(&mut doc, "#");
+
+    doc["openapi"] = Value::String(OPENAPI_3_1_VERSION.to_owned());
+
+    Ok(serde_json::from_value(doc)?)
+}
+
+/// Walks a non-schema part of the document, upgrading any schemas found within.
+fn upgrade_value(value: &mut Value, ptr: &str) {
+    match value {
+        Value::Object(obj) => {
+            upgrade_ref(obj);
+
+            for (key, value) in obj.iter_mut() {
+                match key.as_str() {
+                    // literal values are not part of the document structure
+                    "example" | "examples" | "default" | "enum" => {}
+
+                    "schema" => upgrade_schema(value),
+
+                    "schemas" if ptr == "#/components" => {
+                        if let Value::Object(schemas) = value {
+                            schemas.values_mut().for_each(upgrade_schema);
+                        }
+                    }
+
+                    _ => upgrade_value(value, &format!("{ptr}/{key}")),
+                }
+            }
+        }
+
+        Value::Array(items) => {
+            for (idx, item) in items.iter_mut().enumerate() {
+                upgrade_value(item, &format!("{ptr}/{idx}"));
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Rewrites a schema's 3.0 keywords in place, including those of its subschemas.
+fn upgrade_schema(schema: &mut Value) {
+    let Value::Object(schema) = schema else {
+        return;
+    };
+
+    upgrade_ref(schema);
+
+    if let Some(Value::Bool(nullable)) = schema.remove("nullable") {
+        match schema.get_mut("type") {
+            Some(type_ @ Value::String(_)) if nullable => {
+                *type_ = Value::Array(vec![type_.take(), Value::String("null".to_owned())]);
+            }
+            _ => {}
+        }
+
+        // an enum list would otherwise still reject the null the type set now admits
+        match schema.get_mut("enum") {
+            Some(Value::Array(values)) if nullable && !values.contains(&Value::Null) => {
+                values.push(Value::Null);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(example) = schema.remove("example") {
+        schema
+            .entry("examples")
+            .or_insert_with(|| Value::Array(vec![example]));
+    }
+
+    for (exclusive, inclusive) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(Value::Bool(is_exclusive)) = schema.get(exclusive) {
+            if *is_exclusive {
+                if let Some(bound) = schema.remove(inclusive) {
+                    schema.insert(exclusive.to_owned(), bound);
+                    continue;
+                }
+            }
+
+            schema.remove(exclusive);
+        }
+    }
+
+    for key in ["items", "additionalProperties", "not"] {
+        if let Some(subschema) = schema.get_mut(key) {
+            upgrade_schema(subschema);
+        }
+    }
+
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(subschemas)) = schema.get_mut(key) {
+            subschemas.iter_mut().for_each(upgrade_schema);
+        }
+    }
+
+    if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        properties.values_mut().for_each(upgrade_schema);
+    }
+}
+
+/// Rewrites a `#/definitions/` reference to point to the equivalent schema component.
+fn upgrade_ref(obj: &mut Map<String, Value>) {
+    if let Some(Value::String(ref_path)) = obj.get_mut("$ref") {
+        if let Some(name) = ref_path.strip_prefix("#/definitions/") {
+            *ref_path = format!("#/components/schemas/{name}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::spec::{ObjectOrReference, SchemaType, SchemaTypeSet};
+
+    #[test]
+    fn upgrade_schemas() {
+        let doc = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "test", "version": "v1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{
+                            "name": "limit",
+                            "in": "query",
+                            "example": 10,
+                            "schema": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "exclusiveMinimum": true,
+                                "maximum": 100,
+                                "exclusiveMaximum": false,
+                            },
+                        }],
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "$ref": "#/definitions/Pet" },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string", "nullable": true, "example": "Rex" },
+                        },
+                    },
+                },
+            },
+        });
+
+        let spec = upgrade_from_3_0(doc).unwrap();
+        assert_eq!(spec.openapi, "3.1.0");
+
+        let op = spec.operation(&http::Method::GET, "/pets").unwrap();
+        let ObjectOrReference::Object(param) = &op.parameters[0] else {
+            panic!("expected inline parameter");
+        };
+        assert_eq!(param.example, Some(json!(10)));
+
+        let limit = param.schema.as_ref().unwrap().resolve(&spec).unwrap();
+        assert_eq!(limit.exclusive_minimum, Some(serde_json::Number::from(0)));
+        assert_eq!(limit.maximum, Some(serde_json::Number::from(100)));
+        assert!(limit.exclusive_maximum.is_none());
+
+        let response = serde_json::to_value(&op.responses).unwrap();
+        assert_eq!(
+            response["200"]["content"]["application/json"]["schema"]["items"],
+            json!({ "$ref": "#/components/schemas/Pet" }),
+        );
+
+        let pet = &spec.components.as_ref().unwrap().schemas["Pet"];
+        let ObjectOrReference::Object(pet) = pet else {
+            panic!("expected inline schema");
+        };
+        let name = pet.properties["name"].resolve(&spec).unwrap();
+        assert_eq!(
+            name.schema_type,
+            Some(SchemaTypeSet::Multiple(vec![
+                SchemaType::String,
+                SchemaType::Null,
+            ])),
+        );
+        assert_eq!(name.examples, [json!("Rex")]);
+    }
+
+    #[test]
+    fn upgrade_nullable_enum() {
+        let doc = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "test", "version": "v1" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Status": { "type": "string", "nullable": true, "enum": ["on", "off"] },
+                    "Listed": { "type": "string", "nullable": true, "enum": ["on", null] },
+                    "Strict": { "type": "string", "nullable": false, "enum": ["on", "off"] },
+                },
+            },
+        });
+
+        let spec = upgrade_from_3_0(doc).unwrap();
+        let schemas = &spec.components.as_ref().unwrap().schemas;
+
+        let status = schemas["Status"].resolve(&spec).unwrap();
+        assert_eq!(
+            status.schema_type,
+            Some(SchemaTypeSet::Multiple(vec![
+                SchemaType::String,
+                SchemaType::Null,
+            ])),
+        );
+        assert_eq!(status.enum_values, [json!("on"), json!("off"), json!(null)]);
+
+        let listed = schemas["Listed"].resolve(&spec).unwrap();
+        assert_eq!(listed.enum_values, [json!("on"), json!(null)]);
+
+        let strict = schemas["Strict"].resolve(&spec).unwrap();
+        assert_eq!(strict.enum_values, [json!("on"), json!("off")]);
+    }
+
+    #[test]
+    fn upgrade_rejects_other_versions() {
+        let doc = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "test", "version": "v1" },
+        });
+
+        assert!(matches!(
+            upgrade_from_3_0(doc),
+            Err(UpgradeError::UnsupportedVersion(version)) if version == "3.1.0",
+        ));
+    }
+}
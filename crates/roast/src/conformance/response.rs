@@ -1,18 +1,21 @@
+use std::collections::BTreeMap;
+
 use http::{HeaderMap, StatusCode};
+use oas3::spec::{ObjectSchema, ParameterStyle, ResponseKey};
 use serde_json::Value as JsonValue;
 
-use super::TestOperation;
-use crate::validation::{Error as ValidationError, ValidationTree};
+use super::{test::param_value_to_json, TestOperation};
+use crate::validation::{Error as ValidationError, ValidationContext, ValidationTree};
 
 #[derive(Debug, Clone)]
 pub enum ResponseSpecSource {
-    Status(StatusCode),
+    Status(ResponseKey),
     Schema {
-        status: StatusCode,
+        status: ResponseKey,
         media_type: String,
     },
     Example {
-        status: StatusCode,
+        status: ResponseKey,
         media_type: String,
         name: String,
     },
@@ -27,9 +30,18 @@ pub struct ResponseSpec {
 impl ResponseSpec {
     pub fn from_status(status: u16) -> Self {
         Self {
-            source: ResponseSpecSource::Status(
-                StatusCode::from_u16(status).expect("invalid status code"),
-            ),
+            source: ResponseSpecSource::Status(status_key(status)),
+        }
+    }
+
+    /// Expects a response with any status code in a range, e.g. `4` for `4XX`.
+    ///
+    /// The response documented under the range key is used, falling back to `default`.
+    pub fn from_status_range(class: u8) -> Self {
+        assert!((1..=5).contains(&class), "invalid status code range");
+
+        Self {
+            source: ResponseSpecSource::Status(ResponseKey::Range(class)),
         }
     }
 
@@ -39,7 +51,7 @@ impl ResponseSpec {
     {
         Self {
             source: ResponseSpecSource::Schema {
-                status: StatusCode::from_u16(status).expect("invalid status code"),
+                status: status_key(status),
                 media_type: media_type.into(),
             },
         }
@@ -48,7 +60,7 @@ impl ResponseSpec {
     pub fn from_json_schema(status: u16) -> Self {
         Self {
             source: ResponseSpecSource::Schema {
-                status: StatusCode::from_u16(status).expect("invalid status code"),
+                status: status_key(status),
                 media_type: "application/json".to_owned(),
             },
         }
@@ -61,7 +73,7 @@ impl ResponseSpec {
     {
         Self {
             source: ResponseSpecSource::Example {
-                status: StatusCode::from_u16(status).expect("invalid status code"),
+                status: status_key(status),
                 media_type: media_type.into(),
                 name: name.into(),
             },
@@ -69,16 +81,31 @@ impl ResponseSpec {
     }
 }
 
+fn status_key(status: u16) -> ResponseKey {
+    ResponseKey::Status(StatusCode::from_u16(status).expect("invalid status code"))
+}
+
 #[derive(Debug)]
 pub struct TestResponseSpec {
     pub operation: TestOperation,
-    pub status: StatusCode,
+    pub status: ResponseKey,
     pub body_validator: Option<ValidationTree>,
+    pub header_validators: BTreeMap<String, HeaderValidator>,
+}
+
+/// Expectations for a documented response header.
+#[derive(Debug)]
+pub struct HeaderValidator {
+    pub required: bool,
+    pub style: ParameterStyle,
+    pub explode: bool,
+    pub schema: Option<ObjectSchema>,
+    pub validator: Option<ValidationTree>,
 }
 
 impl TestResponseSpec {
     pub fn validate_status(&self, val: &StatusCode) -> Result<(), ValidationError> {
-        if &self.status == val {
+        if self.status.matches(*val) {
             Ok(())
         } else {
             Err(ValidationError::StatusMismatch(self.status, *val))
@@ -87,7 +114,36 @@ impl TestResponseSpec {
 
     pub fn validate_body(&self, body: &JsonValue) -> Result<(), ValidationError> {
         if let Some(ref validator) = self.body_validator {
-            validator.validate(body)?;
+            validator.validate_in_context(body, ValidationContext::Response)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that required response headers are present and that header values conform to their
+    /// schemas.
+    pub fn validate_headers(&self, headers: &HeaderMap) -> Result<(), ValidationError> {
+        for (name, header) in &self.header_validators {
+            let Some(value) = headers.get(name.as_str()) else {
+                if header.required {
+                    return Err(ValidationError::ResponseHeaderMissing(name.clone()));
+                }
+
+                continue;
+            };
+
+            if let (Some(schema), Some(validator)) = (&header.schema, &header.validator) {
+                let value = String::from_utf8_lossy(value.as_bytes());
+
+                let Some(value) = param_value_to_json(&value, schema, header.style, header.explode)
+                else {
+                    continue;
+                };
+
+                validator.validate(&value).map_err(|err| {
+                    ValidationError::ResponseHeaderTypeMismatch(name.clone(), Box::new(err))
+                })?;
+            }
         }
 
         Ok(())
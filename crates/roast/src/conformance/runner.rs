@@ -1,8 +1,7 @@
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     error::Error as StdError,
     future::Future,
-    ops::Deref,
     string::ToString,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -21,7 +20,7 @@ use url::Url;
 use crate::{
     conformance::{
         ConformanceTestSpec, ParamPosition, ResolvedConformanceTestSpec, TestAuthentication,
-        TestRequest, TestResponse,
+        TestOperation, TestRequest, TestResponse,
     },
     validation::Error as ValidationError,
     Error,
@@ -49,6 +48,25 @@ impl TestRunner {
         }
     }
 
+    /// Creates a runner whose base URL is derived from the spec's server at `server_index`.
+    ///
+    /// Server variables are substituted using `variable_overrides`, falling back to their declared
+    /// defaults. Overrides are checked against each variable's allowed values.
+    pub fn from_spec_server(
+        spec: Spec,
+        server_index: usize,
+        variable_overrides: &BTreeMap<String, String>,
+    ) -> Result<Self, Error> {
+        let server = spec
+            .servers
+            .get(server_index)
+            .ok_or(Error::ServerNotFound(server_index))?;
+
+        let base_url = server.resolve_url(variable_overrides)?;
+
+        Ok(Self::new(base_url.trim_end_matches('/'), spec))
+    }
+
     pub fn add_tests(&mut self, tests: &[ConformanceTestSpec]) {
         self.queue.append(&mut tests.to_owned().into())
     }
@@ -66,7 +84,8 @@ impl TestRunner {
         let client = reqwest::Client::new();
 
         let method: reqwest::Method = req.operation.method.as_str().parse().unwrap();
-        let url: String = [self.base_url.deref(), &req.operation.path].concat();
+        let base_url = self.base_url_for(&req.operation);
+        let url: String = [base_url.as_str(), &req.operation.path].concat();
 
         // path params
         let url = req
@@ -117,6 +136,45 @@ impl TestRunner {
         })
     }
 
+    /// Returns the base URL that requests for the given operation are sent to.
+    ///
+    /// The runner's `base_url` stands in for the spec's root servers. Servers declared on the
+    /// operation or its path item take precedence; relative server URLs are resolved against
+    /// `base_url`.
+    fn base_url_for(&self, op: &TestOperation) -> String {
+        let path_item = self
+            .spec
+            .paths
+            .as_ref()
+            .and_then(|paths| paths.get(&op.path));
+
+        let (Some(path_item), Some(operation)) =
+            (path_item, self.spec.operation(&op.method, &op.path))
+        else {
+            return self.base_url.clone();
+        };
+
+        let servers = operation.effective_servers(path_item, &self.spec);
+        let server = &servers[0];
+
+        if *server == self.spec.primary_server().cloned().unwrap_or_default() {
+            return self.base_url.clone();
+        }
+
+        // substitute variable defaults; the runner has no overrides for non-primary servers
+        let server_url = server
+            .resolve_url(&BTreeMap::new())
+            .unwrap_or_else(|_| server.url.clone());
+
+        // without a trailing slash, joining would replace the base URL's last path segment
+        let base_url = format!("{}/", self.base_url.trim_end_matches('/'));
+
+        match Url::parse(&base_url).and_then(|base| base.join(&server_url)) {
+            Ok(url) => url.as_str().trim_end_matches('/').to_owned(),
+            Err(_) => server_url,
+        }
+    }
+
     async fn run_test(&self, test: ResolvedConformanceTestSpec) -> Result<TestResponse, Error> {
         debug!("request: {:?}", &test.request);
         debug!("response spec: {:?}", &test.response);
@@ -126,6 +184,9 @@ impl TestRunner {
         // validate response status
         test.response.validate_status(&res.status)?;
 
+        // validate response headers
+        test.response.validate_headers(&res.headers)?;
+
         // validate response body
         if test.response.body_validator.is_some() {
             if res.body().is_none() {
@@ -240,3 +301,132 @@ pub fn format_error(err: &dyn StdError) -> ColoredString {
 
     err_str.red()
 }
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use super::*;
+
+    #[test]
+    fn base_url_uses_effective_servers() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Servers
+  version: "0.0.0"
+servers:
+  - url: https://root.example.com
+paths:
+  /inherit:
+    get: {}
+  /relative:
+    servers:
+      - url: /v2
+    get: {}
+  /absolute:
+    get:
+      servers:
+        - url: https://op.example.com
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let runner = TestRunner::new("http://localhost:8080", spec);
+
+        let op = TestOperation::new(Method::GET, "/inherit");
+        assert_eq!(runner.base_url_for(&op), "http://localhost:8080");
+
+        let op = TestOperation::new(Method::GET, "/relative");
+        assert_eq!(runner.base_url_for(&op), "http://localhost:8080/v2");
+
+        let op = TestOperation::new(Method::GET, "/absolute");
+        assert_eq!(runner.base_url_for(&op), "https://op.example.com");
+    }
+
+    #[test]
+    fn base_url_joins_under_base_path() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Servers
+  version: "0.0.0"
+servers:
+  - url: https://root.example.com
+paths:
+  /relative:
+    servers:
+      - url: v2
+    get: {}
+  /templated:
+    get:
+      servers:
+        - url: https://{region}.example.com/{version}
+          variables:
+            region:
+              default: eu
+            version:
+              default: v3
+  /templated-relative:
+    servers:
+      - url: "{version}/items"
+        variables:
+          version:
+            default: v4
+    get: {}
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let runner = TestRunner::new("http://localhost:8080/api", spec.clone());
+
+        let op = TestOperation::new(Method::GET, "/relative");
+        assert_eq!(runner.base_url_for(&op), "http://localhost:8080/api/v2");
+
+        let op = TestOperation::new(Method::GET, "/templated");
+        assert_eq!(runner.base_url_for(&op), "https://eu.example.com/v3");
+
+        let op = TestOperation::new(Method::GET, "/templated-relative");
+        assert_eq!(
+            runner.base_url_for(&op),
+            "http://localhost:8080/api/v4/items",
+        );
+
+        let runner = TestRunner::new("http://localhost:8080/api/", spec);
+
+        let op = TestOperation::new(Method::GET, "/relative");
+        assert_eq!(runner.base_url_for(&op), "http://localhost:8080/api/v2");
+    }
+
+    #[test]
+    fn base_url_from_templated_server() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Servers
+  version: "0.0.0"
+servers:
+  - url: https://{env}.example.com/{version}/
+    variables:
+      env:
+        default: api
+        enum: [api, staging]
+      version:
+        default: v1
+paths: {}
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let runner = TestRunner::from_spec_server(spec.clone(), 0, &BTreeMap::new()).unwrap();
+        assert_eq!(runner.base_url, "https://api.example.com/v1");
+
+        let overrides = BTreeMap::from([("env".to_owned(), "staging".to_owned())]);
+        let runner = TestRunner::from_spec_server(spec.clone(), 0, &overrides).unwrap();
+        assert_eq!(runner.base_url, "https://staging.example.com/v1");
+
+        let overrides = BTreeMap::from([("env".to_owned(), "prod".to_owned())]);
+        let err = TestRunner::from_spec_server(spec.clone(), 0, &overrides).unwrap_err();
+        assert!(matches!(err, Error::Server(_)));
+
+        let err = TestRunner::from_spec_server(spec, 1, &BTreeMap::new()).unwrap_err();
+        assert!(matches!(err, Error::ServerNotFound(1)));
+    }
+}
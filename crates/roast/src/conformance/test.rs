@@ -1,17 +1,24 @@
+use std::collections::BTreeMap;
+
 use bytes::Bytes;
 use http::HeaderMap;
 use log::{debug, trace};
 use oas3::{
-    spec::{Error as SpecError, ParameterIn, RefError},
+    spec::{
+        find_media_type, Error as SpecError, ObjectOrReference, ObjectSchema, Operation,
+        ParameterIn, ParameterStyle, RefError, Response, ResponseKey, Schema, SchemaType,
+    },
     Spec,
 };
+use serde_json::Value as JsonValue;
 
 use super::{
-    OperationSpec, ParamPosition, RequestSource, RequestSpec, ResponseSpec, ResponseSpecSource,
-    TestAuthentication, TestOperation, TestParam, TestRequest, TestResponseSpec,
+    HeaderValidator, OperationSpec, ParamPosition, RequestSource, RequestSpec, ResponseSpec,
+    ResponseSpecSource, TestAuthentication, TestOperation, TestParam, TestRequest,
+    TestResponseSpec,
 };
 use crate::{
-    validation::{Error as ValidationError, ValidationTree},
+    validation::{Error as ValidationError, ValidationContext, ValidationTree},
     Error,
 };
 
@@ -121,7 +128,35 @@ impl ConformanceTestSpec {
                 ParameterIn::Cookie => ParamPosition::Cookie,
             };
 
-            // TODO: validate type
+            // intentionally malformed requests are not expected to match the schema
+            if !self.request.bad {
+                if let Some(schema) = parameter.effective_schema(spec)? {
+                    // inline item and property schemas so that values can be split by style
+                    let schema = ObjectOrReference::Object(schema)
+                        .resolve_deep(spec, 1)
+                        .map_err(SpecError::Ref)?;
+
+                    let style = parameter
+                        .style
+                        .unwrap_or_else(|| ParameterStyle::default_for(parameter.location));
+                    let explode = parameter.explode.unwrap_or(style == ParameterStyle::Form);
+
+                    // values of parameters with `content` are serialized by their media type
+                    let value = match parameter.content {
+                        Some(_) => Some(raw_value_to_json(&param.value, Some(&schema))),
+                        None => param_value_to_json(&param.value, &schema, style, explode),
+                    };
+
+                    if let Some(value) = value {
+                        let validator = ValidationTree::from_schema(&schema, spec)?;
+
+                        debug!("validating param {}: {:?}", &param.name, &value);
+
+                        validator.validate(&value).map_err(Error::Validation)?;
+                    }
+                }
+            }
+
             // TODO: validate other spec options
 
             // insert into test params
@@ -170,9 +205,9 @@ impl ConformanceTestSpec {
                 ref name,
             } => {
                 let req_body = op.request_body(spec)?;
-                let media_spec = req_body.content.get(media_type).ok_or(SpecError::Ref(
-                    RefError::Unresolvable(format!("mediaType/{}", &name)),
-                ))?;
+                let media_spec = find_media_type(&req_body.content, media_type).ok_or(
+                    SpecError::Ref(RefError::Unresolvable(format!("mediaType/{}", &name))),
+                )?;
                 let schema = media_spec.schema(spec)?;
                 let examples = media_spec.examples(spec);
                 let example = examples
@@ -182,7 +217,8 @@ impl ConformanceTestSpec {
                         &name
                     ))))?;
 
-                if let Some(ref ex) = example.value {
+                // intentionally malformed requests are not expected to match the schema
+                if let Some(ex) = example.value.as_ref().filter(|_| !self.request.bad) {
                     // check example validity
                     let validator = ValidationTree::from_schema(&schema, spec)?;
 
@@ -190,8 +226,9 @@ impl ConformanceTestSpec {
                     debug!("against schema: {:?}", &schema);
                     debug!("with validator: {:?}", &validator);
 
-                    // TODO: restore
-                    // validator.validate(&ex)?;
+                    validator
+                        .validate_in_context(ex, ValidationContext::Request)
+                        .map_err(Error::Validation)?;
                 }
 
                 let mut hdrs = HeaderMap::new();
@@ -224,83 +261,90 @@ impl ConformanceTestSpec {
         let test_op = self.resolve_test_operation(spec)?;
         let op = test_op.resolve_operation(spec)?;
 
-        let res_spec =
-            match &self.response_spec.source {
-                ResponseSpecSource::Status(status) => TestResponseSpec {
+        let res_spec = match &self.response_spec.source {
+            ResponseSpecSource::Status(status) => TestResponseSpec {
+                operation: test_op.clone(),
+                status: *status,
+                body_validator: None,
+                header_validators: header_validators(op, *status, spec)?,
+            },
+
+            ResponseSpecSource::Schema { status, media_type } => {
+                // traverse spec
+                let status_spec = response_for_key(op, *status, spec)
+                    .map_err(SpecError::Ref)?
+                    .ok_or(SpecError::Ref(RefError::Unresolvable(format!(
+                        "status/{}",
+                        status
+                    ))))?;
+                let media_spec = find_media_type(&status_spec.content, media_type).ok_or(
+                    SpecError::Ref(RefError::Unresolvable(format!("mediaType/{}", &media_type))),
+                )?;
+                let schema = media_spec.schema(spec)?;
+
+                // create validator
+                let validator = ValidationTree::from_schema(&schema, spec)?;
+
+                TestResponseSpec {
                     operation: test_op.clone(),
                     status: *status,
-                    body_validator: None,
-                },
-
-                ResponseSpecSource::Schema { status, media_type } => {
-                    // traverse spec
-                    let responses = op.responses(spec);
-                    let status_spec = responses.get(status.as_str()).ok_or(SpecError::Ref(
-                        RefError::Unresolvable(format!("status/{}", &status.as_u16())),
-                    ))?;
-                    let media_spec = status_spec.content.get(media_type).ok_or(SpecError::Ref(
-                        RefError::Unresolvable(format!("mediaType/{}", &media_type)),
-                    ))?;
-                    let schema = media_spec.schema(spec)?;
-
-                    // create validator
-                    let validator = ValidationTree::from_schema(&schema, spec)?;
-
-                    TestResponseSpec {
-                        operation: test_op.clone(),
-                        status: *status,
-                        body_validator: Some(validator),
-                    }
+                    body_validator: Some(validator),
+                    header_validators: header_validators(op, *status, spec)?,
                 }
+            }
 
-                ResponseSpecSource::Example {
-                    status,
-                    media_type,
-                    name,
-                } => {
-                    // traverse spec
-                    let reses = op.responses(spec);
-                    let status_spec = reses.get(status.as_str()).ok_or(SpecError::Ref(
-                        RefError::Unresolvable(format!("status/{}", &status.as_u16())),
-                    ))?;
-                    let media_spec = status_spec.content.get(media_type).ok_or(SpecError::Ref(
-                        RefError::Unresolvable(format!("mediaType/{}", &media_type)),
-                    ))?;
-                    let schema = media_spec.schema(spec)?;
-                    let examples = media_spec.examples(spec);
-                    let example =
-                        examples
-                            .get(name)
-                            .ok_or(SpecError::Ref(RefError::Unresolvable(format!(
-                                "example/{}",
-                                &name
-                            ))))?;
-
-                    // create validator
-                    let validator = ValidationTree::from_schema(&schema, spec)?;
+            ResponseSpecSource::Example {
+                status,
+                media_type,
+                name,
+            } => {
+                // traverse spec
+                let status_spec = response_for_key(op, *status, spec)
+                    .map_err(SpecError::Ref)?
+                    .ok_or(SpecError::Ref(RefError::Unresolvable(format!(
+                        "status/{}",
+                        status
+                    ))))?;
+                let media_spec = find_media_type(&status_spec.content, media_type).ok_or(
+                    SpecError::Ref(RefError::Unresolvable(format!("mediaType/{}", &media_type))),
+                )?;
+                let schema = media_spec.schema(spec)?;
+                let examples = media_spec.examples(spec);
+                let example = examples
+                    .get(name)
+                    .ok_or(SpecError::Ref(RefError::Unresolvable(format!(
+                        "example/{}",
+                        &name
+                    ))))?;
 
-                    if let Some(ref ex) = example.value {
-                        // check example validity
+                // create validator
+                let validator = ValidationTree::from_schema(&schema, spec)?;
 
-                        debug!("validating example: {:?}", &ex);
-                        debug!("against schema: {:?}", &schema);
-                        debug!("with validator: {:?}", &validator);
+                if let Some(ref ex) = example.value {
+                    // check example validity
 
-                        validator.validate(ex).map_err(Error::Validation)?;
-                    }
+                    debug!("validating example: {:?}", &ex);
+                    debug!("against schema: {:?}", &schema);
+                    debug!("with validator: {:?}", &validator);
 
-                    let mut hdrs = HeaderMap::new();
-                    hdrs.insert("Content-Type", media_type.clone().parse().unwrap());
+                    validator
+                        .validate_in_context(ex, ValidationContext::Response)
+                        .map_err(Error::Validation)?;
+                }
 
-                    TestResponseSpec {
-                        operation: test_op.clone(),
-                        status: *status,
-                        body_validator: Some(validator),
-                    }
+                let mut hdrs = HeaderMap::new();
+                hdrs.insert("Content-Type", media_type.clone().parse().unwrap());
+
+                TestResponseSpec {
+                    operation: test_op.clone(),
+                    status: *status,
+                    body_validator: Some(validator),
+                    header_validators: header_validators(op, *status, spec)?,
                 }
+            }
 
-                ResponseSpecSource::Exactly(ref _data) => todo!(),
-            };
+            ResponseSpecSource::Exactly(ref _data) => todo!(),
+        };
 
         Ok(res_spec)
     }
@@ -313,8 +357,205 @@ pub struct ResolvedConformanceTestSpec {
     pub response: TestResponseSpec,
 }
 
+/// Resolves the response `op` declares for `key`.
+///
+/// Status codes are matched as described in [`Operation::response_for_status`]. Range and
+/// `default` keys use the response documented under the same key, falling back to `default`.
+fn response_for_key(
+    op: &Operation,
+    key: ResponseKey,
+    spec: &Spec,
+) -> Result<Option<Response>, RefError> {
+    match key {
+        ResponseKey::Status(status) => op.response_for_status(status, spec),
+
+        key => op
+            .responses
+            .iter()
+            .flatten()
+            .filter_map(|(res_key, oor)| Some((res_key.parse::<ResponseKey>().ok()?, oor)))
+            .filter(|(res_key, _)| *res_key == key || *res_key == ResponseKey::Default)
+            .min_by_key(|(res_key, _)| *res_key)
+            .map(|(_, oor)| oor.resolve(spec))
+            .transpose(),
+    }
+}
+
+/// Builds validators for the headers documented on the response `op` declares for `status`.
+///
+/// `Content-Type` headers are ignored, as required by the spec.
+fn header_validators(
+    op: &Operation,
+    status: ResponseKey,
+    spec: &Spec,
+) -> Result<BTreeMap<String, HeaderValidator>, Error> {
+    let Some(res) = response_for_key(op, status, spec).map_err(SpecError::Ref)? else {
+        return Ok(BTreeMap::new());
+    };
+
+    let mut validators = BTreeMap::new();
+
+    for (name, header) in res.headers_resolved(spec).map_err(SpecError::Ref)? {
+        if name.eq_ignore_ascii_case("content-type") {
+            continue;
+        }
+
+        // inline item and property schemas so that values can be split by style
+        let schema = match &header.schema {
+            Some(schema) => Some(schema.resolve_deep(spec, 1).map_err(SpecError::Ref)?),
+            None => None,
+        };
+
+        let validator = match &schema {
+            Some(schema) => Some(ValidationTree::from_schema(schema, spec)?),
+            None => None,
+        };
+
+        let header_validator = HeaderValidator {
+            required: header.required.unwrap_or(false),
+            style: header.style.unwrap_or(ParameterStyle::Simple),
+            explode: header.explode.unwrap_or(false),
+            schema,
+            validator,
+        };
+
+        validators.insert(name, header_validator);
+    }
+
+    Ok(validators)
+}
+
+/// Interprets a raw parameter value as JSON for validation against its `schema`.
+///
+/// Array and object values are split according to the parameter's `style` and `explode` settings,
+/// and each item is interpreted against the corresponding item or property schema. Returns `None`
+/// for encodings that cannot be represented by a single value, such as exploded `form` objects, or
+/// that are not supported, such as `matrix` and `deepObject`.
+///
+/// Item and property schemas that are references are treated as admitting strings; callers should
+/// inline them beforehand, e.g. using [`ObjectOrReference::resolve_deep()`].
+pub(super) fn param_value_to_json(
+    value: &str,
+    schema: &ObjectSchema,
+    style: ParameterStyle,
+    explode: bool,
+) -> Option<JsonValue> {
+    let has_type = |type_| {
+        schema
+            .schema_type
+            .as_ref()
+            .is_some_and(|types| types.contains(type_))
+    };
+
+    let value = match style {
+        ParameterStyle::Label => value.strip_prefix('.').unwrap_or(value),
+        _ => value,
+    };
+
+    if has_type(SchemaType::Array) {
+        let items = match &schema.items {
+            Some(Schema::Object(items)) => inline_schema(items),
+            _ => None,
+        };
+
+        let delimiter = match (style, explode) {
+            (ParameterStyle::Simple, _) | (ParameterStyle::Form | ParameterStyle::Label, false) => {
+                ','
+            }
+            (ParameterStyle::Label, true) => '.',
+            (ParameterStyle::SpaceDelimited, false) => ' ',
+            (ParameterStyle::PipeDelimited, false) => '|',
+
+            // each item is sent as a separate parameter
+            (ParameterStyle::Form, true) => {
+                return Some(JsonValue::Array(vec![raw_value_to_json(value, items)]));
+            }
+
+            _ => return None,
+        };
+
+        let items = value
+            .split(delimiter)
+            .filter(|item| !item.is_empty())
+            .map(|item| raw_value_to_json(item, items))
+            .collect();
+
+        return Some(JsonValue::Array(items));
+    }
+
+    if has_type(SchemaType::Object) {
+        let (delimiter, explode) = match (style, explode) {
+            (ParameterStyle::Simple, explode) => (',', explode),
+            (ParameterStyle::Form | ParameterStyle::Label, false) => (',', false),
+            (ParameterStyle::Label, true) => ('.', true),
+            (ParameterStyle::SpaceDelimited, false) => (' ', false),
+            (ParameterStyle::PipeDelimited, false) => ('|', false),
+            _ => return None,
+        };
+
+        let parts = value.split(delimiter).filter(|part| !part.is_empty());
+
+        let entries = if explode {
+            parts
+                .map(|part| part.split_once('=').unwrap_or((part, "")))
+                .collect::<Vec<_>>()
+        } else {
+            let parts = parts.collect::<Vec<_>>();
+
+            parts
+                .chunks(2)
+                .map(|pair| (pair[0], pair.get(1).copied().unwrap_or("")))
+                .collect()
+        };
+
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                let schema = schema
+                    .properties
+                    .get(key)
+                    .or(schema.additional_properties.as_ref())
+                    .and_then(Schema::as_object)
+                    .and_then(inline_schema);
+
+                (key.to_owned(), raw_value_to_json(value, schema))
+            })
+            .collect();
+
+        return Some(JsonValue::Object(entries));
+    }
+
+    Some(raw_value_to_json(value, Some(schema)))
+}
+
+/// Interprets a raw value as JSON for validation against `schema`.
+///
+/// Values are parsed as JSON unless the schema admits strings, so that e.g. `5` matches an integer
+/// schema. Unparsable values are treated as strings.
+fn raw_value_to_json(value: &str, schema: Option<&ObjectSchema>) -> JsonValue {
+    let admits_string = schema
+        .and_then(|schema| schema.schema_type.as_ref())
+        .map_or(true, |types| types.contains(SchemaType::String));
+
+    if admits_string {
+        return JsonValue::String(value.to_owned());
+    }
+
+    serde_json::from_str(value).unwrap_or_else(|_| JsonValue::String(value.to_owned()))
+}
+
+fn inline_schema(schema: &ObjectOrReference<ObjectSchema>) -> Option<&ObjectSchema> {
+    match schema {
+        ObjectOrReference::Object(schema) => Some(schema),
+        ObjectOrReference::Ref { .. } => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use http::StatusCode;
+    use serde_json::json;
+
     use super::*;
 
     #[test]
@@ -340,4 +581,470 @@ mod tests {
             ResponseSpec::from_schema(401, "application/json"),
         );
     }
+
+    #[test]
+    fn request_example_validated_on_resolve() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /token:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                username: { type: string }
+              required: [username]
+            examples:
+              valid:
+                value: { username: "ferris" }
+              invalid:
+                value: { username: 42 }
+      responses:
+        "200":
+          description: OK
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let test = ConformanceTestSpec::new(
+            OperationSpec::post("/token"),
+            RequestSpec::from_json_example("valid"),
+            ResponseSpec::from_status(200),
+        );
+        let req = test.resolve_request(&spec).unwrap();
+        assert_eq!(req.body.as_ref(), br#"{"username":"ferris"}"#);
+
+        let test = ConformanceTestSpec::new(
+            OperationSpec::post("/token"),
+            RequestSpec::from_json_example("invalid"),
+            ResponseSpec::from_status(200),
+        );
+        let err = test.resolve_request(&spec).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Validation(ValidationError::TypeMismatch(..))
+        ));
+    }
+
+    #[test]
+    fn params_validated_on_resolve() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema: { type: integer }
+        - name: filter
+          in: query
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  name: { type: string }
+      responses:
+        "200":
+          description: OK
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let test = |id: &str, filter: &str| {
+            ConformanceTestSpec::new(
+                OperationSpec::get("/pets/{id}"),
+                RequestSpec::empty()
+                    .add_param("id", id)
+                    .add_param("filter", filter),
+                ResponseSpec::from_status(200),
+            )
+            .resolve_params(&spec)
+        };
+
+        let params = test("5", r#"{"name":"ferris"}"#).unwrap();
+        assert_eq!(params.len(), 2);
+
+        let err = test("five", r#"{"name":"ferris"}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Validation(ValidationError::TypeMismatch(..))
+        ));
+
+        let err = test("5", r#"{"name":5}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Validation(ValidationError::TypeMismatch(..))
+        ));
+    }
+
+    #[test]
+    fn styled_params_validated_on_resolve() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets/{ids}:
+    get:
+      parameters:
+        - name: ids
+          in: path
+          required: true
+          schema:
+            type: array
+            items: { type: integer }
+        - name: tags
+          in: query
+          explode: false
+          schema:
+            type: array
+            items: { type: string }
+        - name: sizes
+          in: query
+          style: pipeDelimited
+          explode: false
+          schema:
+            type: array
+            items:
+              $ref: '#/components/schemas/Size'
+        - name: color
+          in: header
+          explode: true
+          schema:
+            type: object
+            properties:
+              r: { type: integer }
+              g: { type: integer }
+        - name: id
+          in: query
+          schema:
+            type: array
+            items: { type: integer }
+      responses:
+        "200":
+          description: OK
+components:
+  schemas:
+    Size: { type: integer }
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let test = |name: &str, value: &str| {
+            let mut req = RequestSpec::empty();
+
+            if name != "ids" {
+                req = req.add_param("ids", "1");
+            }
+
+            ConformanceTestSpec::new(
+                OperationSpec::get("/pets/{ids}"),
+                req.add_param(name, value),
+                ResponseSpec::from_status(200),
+            )
+            .resolve_params(&spec)
+        };
+
+        test("ids", "1,2,3").unwrap();
+        test("tags", "a,b").unwrap();
+        test("sizes", "1|2").unwrap();
+        test("color", "r=1,g=2").unwrap();
+        // exploded form arrays are sent as one parameter per item
+        test("id", "5").unwrap();
+
+        for (name, value) in [("ids", "1,two"), ("sizes", "1|two"), ("color", "r=red")] {
+            let err = test(name, value).unwrap_err();
+            assert!(matches!(
+                err,
+                Error::Validation(ValidationError::TypeMismatch(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn response_schema_from_range_key() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets:
+    post:
+      responses:
+        "2XX":
+          description: Created
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [id]
+                properties:
+                  id: { type: integer }
+        default:
+          description: Error
+          content:
+            application/json:
+              schema: { type: string }
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let res_spec = ConformanceTestSpec::new(
+            OperationSpec::post("/pets"),
+            RequestSpec::empty(),
+            ResponseSpec::from_json_schema(201),
+        )
+        .resolve_response_spec(&spec)
+        .unwrap();
+
+        assert!(res_spec.validate_body(&json!({ "id": 1 })).is_ok());
+        assert!(res_spec.validate_body(&json!("error")).is_err());
+    }
+
+    #[test]
+    fn response_status_from_range_key() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets:
+    post:
+      responses:
+        "201":
+          description: Created
+        "4XX":
+          description: Client error
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [message]
+                properties:
+                  message: { type: string }
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+        let unprocessable = StatusCode::UNPROCESSABLE_ENTITY;
+
+        let res_spec = ConformanceTestSpec::new(
+            OperationSpec::post("/pets"),
+            RequestSpec::empty(),
+            ResponseSpec::from_json_schema(422),
+        )
+        .resolve_response_spec(&spec)
+        .unwrap();
+
+        res_spec.validate_status(&unprocessable).unwrap();
+        res_spec
+            .validate_body(&json!({ "message": "invalid" }))
+            .unwrap();
+
+        let res_spec = ConformanceTestSpec::new(
+            OperationSpec::post("/pets"),
+            RequestSpec::empty(),
+            ResponseSpec::from_status_range(4),
+        )
+        .resolve_response_spec(&spec)
+        .unwrap();
+
+        res_spec.validate_status(&unprocessable).unwrap();
+        res_spec.validate_status(&StatusCode::NOT_FOUND).unwrap();
+
+        let err = res_spec.validate_status(&StatusCode::CREATED).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Status mismatch: expected 4XX; got 201 Created"
+        );
+    }
+
+    #[test]
+    fn media_type_ranges_and_parameters() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: OK
+          content:
+            application/*:
+              schema: { type: object }
+            "*/*":
+              schema: { type: string }
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let resolve = |media_type: &str| {
+            ConformanceTestSpec::new(
+                OperationSpec::get("/pets"),
+                RequestSpec::empty(),
+                ResponseSpec::from_schema(200, media_type),
+            )
+            .resolve_response_spec(&spec)
+            .unwrap()
+        };
+
+        let res_spec = resolve("Application/JSON; charset=utf-8");
+        res_spec.validate_body(&json!({})).unwrap();
+        res_spec.validate_body(&json!("text")).unwrap_err();
+
+        let res_spec = resolve("text/plain");
+        res_spec.validate_body(&json!("text")).unwrap();
+    }
+
+    #[test]
+    fn response_headers_validated() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: OK
+          headers:
+            X-Rate-Limit:
+              required: true
+              schema: { type: integer }
+            X-Trace:
+              schema: { type: string }
+            Content-Type:
+              required: true
+              schema: { type: string }
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let res_spec = ConformanceTestSpec::new(
+            OperationSpec::get("/pets"),
+            RequestSpec::empty(),
+            ResponseSpec::from_status(200),
+        )
+        .resolve_response_spec(&spec)
+        .unwrap();
+
+        assert_eq!(res_spec.header_validators.len(), 2);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-rate-limit", "100".parse().unwrap());
+        res_spec.validate_headers(&headers).unwrap();
+
+        headers.insert("x-rate-limit", "lots".parse().unwrap());
+        let err = res_spec.validate_headers(&headers).unwrap_err();
+        assert!(matches!(
+            err,
+            ValidationError::ResponseHeaderTypeMismatch(name, _) if name == "X-Rate-Limit"
+        ));
+
+        let err = res_spec.validate_headers(&HeaderMap::new()).unwrap_err();
+        assert!(matches!(
+            err,
+            ValidationError::ResponseHeaderMissing(name) if name == "X-Rate-Limit"
+        ));
+    }
+
+    #[test]
+    fn array_response_headers_validated() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: OK
+          headers:
+            X-Rate:
+              schema:
+                type: array
+                items: { type: integer }
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let res_spec = ConformanceTestSpec::new(
+            OperationSpec::get("/pets"),
+            RequestSpec::empty(),
+            ResponseSpec::from_status(200),
+        )
+        .resolve_response_spec(&spec)
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-rate", "1,2".parse().unwrap());
+        res_spec.validate_headers(&headers).unwrap();
+
+        headers.insert("x-rate", "1,lots".parse().unwrap());
+        let err = res_spec.validate_headers(&headers).unwrap_err();
+        assert!(matches!(
+            err,
+            ValidationError::ResponseHeaderTypeMismatch(name, _) if name == "X-Rate"
+        ));
+    }
+
+    #[test]
+    fn referenced_response_headers_validated() {
+        let spec_str = r#"openapi: "3.1.0"
+info:
+  title: Test API
+  version: "0.1"
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          $ref: '#/components/responses/Pets'
+components:
+  headers:
+    RateLimit:
+      required: true
+      schema: { type: integer }
+  responses:
+    Pets:
+      description: OK
+      headers:
+        X-Rate-Limit:
+          $ref: '#/components/headers/RateLimit'
+"#;
+
+        let spec = oas3::from_str(spec_str).unwrap();
+
+        let res_spec = ConformanceTestSpec::new(
+            OperationSpec::get("/pets"),
+            RequestSpec::empty(),
+            ResponseSpec::from_status(200),
+        )
+        .resolve_response_spec(&spec)
+        .unwrap();
+
+        let header = &res_spec.header_validators["X-Rate-Limit"];
+        assert!(header.required);
+        assert!(header.validator.is_some());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-rate-limit", "60".parse().unwrap());
+        res_spec.validate_headers(&headers).unwrap();
+
+        headers.insert("x-rate-limit", "sixty".parse().unwrap());
+        res_spec.validate_headers(&headers).unwrap_err();
+    }
 }
@@ -24,6 +24,13 @@ pub enum Error {
     #[display("Spec error")]
     Spec(oas3::spec::Error),
 
+    #[display("Server not found at index {}", _0)]
+    #[from(ignore)]
+    ServerNotFound(#[error(not(source))] usize),
+
+    #[display("Server URL error")]
+    Server(oas3::spec::ServerError),
+
     #[display("Validation error")]
     Validation(crate::validation::Error),
 
@@ -0,0 +1,54 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationTree};
+
+/// Rejects every value, as the `false` boolean schema does.
+#[derive(Debug, Clone)]
+pub struct FalseSchema;
+
+impl Validate for FalseSchema {
+    fn validate(&self, _val: &JsonValue, path: Path) -> Result<(), Error> {
+        Err(Error::FalseSchema(path))
+    }
+}
+
+/// Rejects values that are valid against a subschema, as the `not` keyword does.
+#[derive(Debug)]
+pub struct NotSchema {
+    tree: Box<ValidationTree>,
+}
+
+impl NotSchema {
+    pub fn new(tree: ValidationTree) -> Self {
+        Self {
+            tree: Box::new(tree),
+        }
+    }
+}
+
+impl Validate for NotSchema {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        match self.tree.validate(val) {
+            Ok(()) => Err(Error::NotSchemaMatched(path)),
+
+            // errors in the subschema itself are not validation failures
+            Err(err @ Error::Schema(_)) => Err(err),
+
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn false_schema_validation() {
+        valid_vs_invalid!(
+            FalseSchema,
+            &[],
+            &[&NULL, &TRUE, &INTEGER, &STRING, &OBJ_EMPTY]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate};
+
+/// Bounds the number of items in an array (`minItems` / `maxItems`).
+///
+/// Non-array values are ignored.
+#[derive(Debug, Clone)]
+pub struct ItemCount {
+    min: Option<u64>,
+    max: Option<u64>,
+}
+
+impl ItemCount {
+    pub fn new(min: Option<u64>, max: Option<u64>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl Validate for ItemCount {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let Some(items) = val.as_array() else {
+            return Ok(());
+        };
+
+        let count = items.len();
+
+        if let Some(min) = self.min {
+            if (count as u64) < min {
+                return Err(Error::TooFewItems(path, count, min));
+            }
+        }
+
+        if let Some(max) = self.max {
+            if (count as u64) > max {
+                return Err(Error::TooManyItems(path, count, max));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Bounds the number of properties in an object (`minProperties` / `maxProperties`).
+///
+/// All present keys are counted, including undocumented ones. Non-object values are ignored.
+#[derive(Debug, Clone)]
+pub struct PropertyCount {
+    min: Option<u64>,
+    max: Option<u64>,
+}
+
+impl PropertyCount {
+    pub fn new(min: Option<u64>, max: Option<u64>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl Validate for PropertyCount {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let Some(obj) = val.as_object() else {
+            return Ok(());
+        };
+
+        let count = obj.len();
+
+        if let Some(min) = self.min {
+            if (count as u64) < min {
+                return Err(Error::TooFewProperties(path, count, min));
+            }
+        }
+
+        if let Some(max) = self.max {
+            if (count as u64) > max {
+                return Err(Error::TooManyProperties(path, count, max));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn item_count_validation() {
+        let v = ItemCount::new(Some(2), None);
+        let single = json!([1]);
+        valid_vs_invalid!(v, &[&ARRAY_INTS, &ARRAY_MIXED, &STRING], &[&single]);
+
+        let v = ItemCount::new(None, Some(1));
+        valid_vs_invalid!(v, &[&single, &OBJ_NUMS], &[&ARRAY_INTS, &ARRAY_STRS]);
+    }
+
+    #[test]
+    fn property_count_validation() {
+        let v = PropertyCount::new(Some(1), Some(2));
+        valid_vs_invalid!(
+            v,
+            &[&OBJ_NUMS, &OBJ_MIXED, &ARRAY_INTS],
+            &[&OBJ_EMPTY, &OBJ_MIXED2],
+        );
+
+        let err = v.validate(&OBJ_MIXED2, Path::default()).unwrap_err();
+        assert!(matches!(err, Error::TooManyProperties(_, 3, 2)));
+    }
+}
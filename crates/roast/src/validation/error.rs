@@ -1,8 +1,11 @@
-use std::fmt;
+use std::{fmt, io};
 
 use derive_more::derive::{Display, Error};
 use http::{Method, StatusCode};
-use oas3::{spec::SchemaTypeSet, Error as SchemaError};
+use oas3::{
+    spec::{ResponseKey, SchemaTypeSet},
+    Error as SchemaError,
+};
 use serde_json::Value as JsonValue;
 
 use super::Path;
@@ -48,6 +51,15 @@ pub enum Error {
     #[display("Schema error")]
     Schema(SchemaError),
 
+    #[display("I/O error")]
+    Io(io::Error),
+
+    #[display("Line {}: {}", _0, _1)]
+    NdjsonLine(usize, #[error(source)] Box<Error>),
+
+    #[display("Invalid JSON")]
+    InvalidJson(serde_json::Error),
+
     //
     // Leaf Errors
     //
@@ -64,7 +76,38 @@ pub enum Error {
     UndocumentedField(#[error(not(source))] String),
 
     #[display("Status mismatch: expected {}; got {}", _0, _1)]
-    StatusMismatch(StatusCode, StatusCode),
+    StatusMismatch(ResponseKey, StatusCode),
+
+    #[display("Response header missing: {}", _0)]
+    ResponseHeaderMissing(#[error(not(source))] String),
+
+    #[display("Response header type mismatch: {}", _0)]
+    ResponseHeaderTypeMismatch(String, #[error(source)] Box<Error>),
+
+    #[display("Too few array items: {}; expected at least {}, got {}", _0, _2, _1)]
+    TooFewItems(Path, usize, u64),
+
+    #[display("Too many array items: {}; expected at most {}, got {}", _0, _2, _1)]
+    TooManyItems(Path, usize, u64),
+
+    #[display(
+        "Too few object properties: {}; expected at least {}, got {}",
+        _0,
+        _2,
+        _1
+    )]
+    TooFewProperties(Path, usize, u64),
+
+    #[display(
+        "Too many object properties: {}; expected at most {}, got {}",
+        _0,
+        _2,
+        _1
+    )]
+    TooManyProperties(Path, usize, u64),
+
+    #[display("String does not match pattern: {}; expected to match {}", _0, _1)]
+    PatternMismatch(Path, String),
 
     #[display("Required field missing: {}", _0)]
     RequiredFieldMissing(#[error(not(source))] Path),
@@ -72,9 +115,21 @@ pub enum Error {
     #[display("Type did not match any `anyOf` variant: {}\n{}", _0, _1)]
     OneOfNoMatch(Path, AggregateError),
 
+    #[display("Value not allowed by `false` schema: {}", _0)]
+    FalseSchema(#[error(not(source))] Path),
+
+    #[display("Value matches `not` schema: {}", _0)]
+    NotSchemaMatched(#[error(not(source))] Path),
+
     #[display("Non-nullable field was null: {}", _0)]
     InvalidNull(#[error(not(source))] Path),
 
+    #[display("Read-only field in request: {}", _0)]
+    ReadOnlyInRequest(#[error(not(source))] Path),
+
+    #[display("Write-only field in response: {}", _0)]
+    WriteOnlyInResponse(#[error(not(source))] Path),
+
     #[display("Operation not found: {} {}", _0, _1)]
     OperationNotFound(Method, String),
 
@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use oas3::{
+    json_pointer,
+    spec::{
+        MediaTypeDirection, MediaTypeExamples, MediaTypeLocation, ObjectOrReference, ObjectSchema,
+    },
+    Spec,
+};
+use serde_json::Value as JsonValue;
+
+use super::{Error, ValidationTree};
+
+/// Validates every example in `spec` against its governing schema.
+///
+/// Examples of operation request and response media types are validated against the media type's
+/// schema, and `example`/`examples` of schemas against the schema itself. Returns a JSON pointer
+/// to each example that fails validation, along with the error. Examples without a schema, and
+/// unresolvable example references, are skipped.
+///
+/// Schemas are built lazily, so recursive schemas are supported and schema errors are reported
+/// against each example that reaches them.
+pub fn validate_examples(spec: &Spec) -> Vec<(String, Error)> {
+    let mut failures = vec![];
+
+    // lazily built trees keep a shared handle to the spec
+    let shared_spec = Arc::new(spec.clone());
+
+    spec.for_each_media_type(|location, media_type| {
+        let Some(examples) = &media_type.examples else {
+            return;
+        };
+
+        let Some(Ok(schema)) = media_type
+            .schema
+            .as_ref()
+            .map(|schema| schema.resolve(spec))
+        else {
+            return;
+        };
+
+        let ptr = media_type_pointer(&location);
+
+        let (example, named_examples) = match examples {
+            MediaTypeExamples::Example { example } => (Some(example), None),
+            MediaTypeExamples::Examples { examples } => (None, Some(examples)),
+            MediaTypeExamples::ExampleAndExamples { example, examples } => {
+                (Some(example), Some(examples))
+            }
+        };
+
+        let mut values = vec![];
+
+        if let Some(example) = example {
+            values.push((json_pointer::append(&ptr, "example"), example.clone()));
+        }
+
+        for (name, oor) in named_examples.into_iter().flatten() {
+            if let Some(value) = oor.resolve(spec).ok().and_then(|example| example.value) {
+                let example_ptr =
+                    json_pointer::append(&json_pointer::append(&ptr, "examples"), name);
+                values.push((json_pointer::append(&example_ptr, "value"), value));
+            }
+        }
+
+        validate_all(&shared_spec, &schema, values, &mut failures);
+    });
+
+    for (ptr, schema) in spec.schemas() {
+        let ObjectOrReference::Object(schema) = schema else {
+            continue;
+        };
+
+        let mut values = vec![];
+
+        if let Some(example) = &schema.example {
+            values.push((json_pointer::append(&ptr, "example"), example.clone()));
+        }
+
+        for (idx, example) in schema.examples.iter().enumerate() {
+            let examples_ptr = json_pointer::append(&ptr, "examples");
+            values.push((
+                json_pointer::append(&examples_ptr, &idx.to_string()),
+                example.clone(),
+            ));
+        }
+
+        validate_all(&shared_spec, schema, values, &mut failures);
+    }
+
+    failures
+}
+
+/// Validates each of `values` against `schema`, recording failures.
+fn validate_all(
+    spec: &Arc<Spec>,
+    schema: &ObjectSchema,
+    values: Vec<(String, JsonValue)>,
+    failures: &mut Vec<(String, Error)>,
+) {
+    if values.is_empty() {
+        return;
+    }
+
+    let validator = ValidationTree::from_schema_lazy(schema, Arc::clone(spec));
+
+    for (ptr, value) in values {
+        if let Err(err) = validator.validate(&value) {
+            failures.push((ptr, err));
+        }
+    }
+}
+
+fn media_type_pointer(location: &MediaTypeLocation) -> String {
+    let op_ptr = json_pointer::append(
+        &json_pointer::append("#/paths", &location.path),
+        &location.method.as_str().to_lowercase(),
+    );
+
+    let content_ptr = match &location.direction {
+        MediaTypeDirection::Request => json_pointer::append(&op_ptr, "requestBody"),
+        MediaTypeDirection::Response { status } => {
+            json_pointer::append(&json_pointer::append(&op_ptr, "responses"), status)
+        }
+    };
+
+    json_pointer::append(
+        &json_pointer::append(&content_ptr, "content"),
+        &location.content_type,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_failures() {
+        let spec = oas3::from_str(
+            r#"
+openapi: 3.1.0
+info:
+  title: test
+  version: v1
+paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+            examples:
+              valid:
+                value: { name: Rex }
+              invalid:
+                value: { name: 5 }
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: array
+              example: { name: Rex }
+        '204':
+          description: No Content
+          content:
+            text/plain:
+              example: no schema
+        '206':
+          description: Partial Content
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Node'
+              example:
+                children:
+                  - children: []
+                  - children: [{ children: [5] }]
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+      examples:
+        - name: Rex
+        - tag: dog
+    Node:
+      type: object
+      properties:
+        children:
+          type: array
+          items:
+            $ref: '#/components/schemas/Node'
+      example:
+        children: [{ children: [] }]
+"#,
+        )
+        .unwrap();
+
+        let failures = validate_examples(&spec);
+
+        let mut ptrs = failures
+            .iter()
+            .map(|(ptr, _)| ptr.as_str())
+            .collect::<Vec<_>>();
+        ptrs.sort_unstable();
+
+        assert_eq!(
+            ptrs,
+            [
+                "#/components/schemas/Pet/examples/1",
+                "#/paths/~1pets/post/requestBody/content/application~1json/examples/invalid/value",
+                "#/paths/~1pets/post/responses/200/content/application~1json/example",
+                "#/paths/~1pets/post/responses/206/content/application~1json/example",
+            ],
+        );
+    }
+}
@@ -8,14 +8,22 @@ use serde_json::Value as JsonValue;
 #[macro_use]
 mod test_macros;
 
+mod boolean;
+mod count;
 mod error;
+mod examples;
 mod path;
+mod pattern;
 mod required;
 mod r#type;
 mod validator;
 
+pub use boolean::*;
+pub use count::*;
 pub use error::*;
+pub use examples::*;
 pub use path::Path;
+pub use pattern::*;
 pub use r#type::*;
 pub use required::*;
 pub use validator::*;
@@ -0,0 +1,52 @@
+use regex::Regex;
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate};
+
+/// Requires strings to match a regular expression (`pattern`).
+///
+/// Patterns are not anchored, so they may match anywhere in the string. Non-string values are
+/// ignored.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    pub fn new(regex: Regex) -> Self {
+        Self { regex }
+    }
+}
+
+impl Validate for Pattern {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let Some(string) = val.as_str() else {
+            return Ok(());
+        };
+
+        if !self.regex.is_match(string) {
+            return Err(Error::PatternMismatch(path, self.regex.as_str().to_owned()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn pattern_validation() {
+        let v = Pattern::new(Regex::new("^[a-z]+ [a-z]").unwrap());
+        let other = json!("not-a-match");
+        valid_vs_invalid!(v, &[&STRING, &INTEGER, &ARRAY_STRS], &[&other]);
+
+        let err = v.validate(&other, Path::default()).unwrap_err();
+        assert!(
+            matches!(err, Error::PatternMismatch(_, ref pattern) if pattern == "^[a-z]+ [a-z]")
+        );
+    }
+}
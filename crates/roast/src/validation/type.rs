@@ -51,34 +51,16 @@ impl DataType {
 impl Validate for DataType {
     /// Checks that the value provided is of expected type.
     fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
-        if self.nullable && val.is_null() {
-            return Ok(());
+        if val.is_null() {
+            return if self.nullable || self.type_set.is_nullable() {
+                Ok(())
+            } else {
+                Err(Error::InvalidNull(path))
+            };
         }
 
-        let data_type = match val {
-            JsonValue::Bool(_) => SchemaType::Boolean,
-            JsonValue::Number(num) => {
-                if num.is_i64() {
-                    SchemaType::Integer
-                } else {
-                    SchemaType::Number
-                }
-            }
-            JsonValue::String(_) => SchemaType::String,
-            JsonValue::Array(_) => SchemaType::Array,
-            JsonValue::Object(_) => SchemaType::Object,
-
-            // already checked and returned
-            JsonValue::Null => return Err(Error::InvalidNull(path)),
-        };
-
-        // check type equality
-        if !self.type_set.contains(data_type) {
-            // integers also count as numbers
-            if val.is_i64() && self.type_set.contains(SchemaType::Number) {
-                return Ok(());
-            }
-
+        // passes if any type in the set matches, with integers also counting as numbers
+        if !self.type_set.matches(val) {
             return Err(Error::TypeMismatch(path, self.type_set.clone()));
         }
 
@@ -153,6 +135,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn null_type_validation() {
+        let val = DataType::new(SchemaTypeSet::Single(SchemaType::Null));
+
+        valid_vs_invalid!(
+            val,
+            &[&NULL],
+            &[&TRUE, &INTEGER, &FLOAT, &STRING, &ARRAY_INTS, &OBJ_EMPTY],
+        );
+    }
+
+    #[test]
+    fn multiple_type_validation() {
+        let val = DataType::new(SchemaTypeSet::Multiple(vec![
+            SchemaType::Integer,
+            SchemaType::Null,
+        ]));
+
+        valid_vs_invalid!(
+            val,
+            &[&INTEGER, &NULL],
+            &[&FLOAT, &TRUE, &STRING, &ARRAY_INTS, &OBJ_EMPTY],
+        );
+
+        let val = DataType::new(SchemaTypeSet::Multiple(vec![
+            SchemaType::String,
+            SchemaType::Array,
+        ]));
+
+        valid_vs_invalid!(
+            val,
+            &[&STRING, &ARRAY_INTS],
+            &[&NULL, &INTEGER, &TRUE, &OBJ_EMPTY],
+        );
+    }
+
     #[test]
     fn array_validation() {
         let val = DataType::array();
@@ -14,9 +14,11 @@ fn validate_passing_samples() {
 
 #[test]
 fn validate_failing_samples() {
-    // TODO: implement validation for one-of: [paths, components, webhooks]
     // see https://spec.openapis.org/oas/v3.1.0#openapi-document
-    // oas3::from_str(include_str!("samples/fail/no_containers.yaml")).unwrap_err();
+    oas3::from_str(include_str!("samples/fail/no_containers.yaml"))
+        .unwrap()
+        .validate()
+        .unwrap_err();
 
     // TODO: implement validation for non-empty server enum
     // oas3::from_str(include_str!("samples/fail/server_enum_empty.yaml")).unwrap_err();